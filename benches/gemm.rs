@@ -0,0 +1,42 @@
+extern crate criterion;
+extern crate lin;
+
+use criterion::{BenchmarkId, Criterion, black_box};
+use criterion::{criterion_group, criterion_main};
+use lin::blas::gemm;
+use lin::matrix::{MatrixN, MatrixView, MatrixViewMut};
+
+/// The textbook triple loop, for comparison with `gemm`.
+fn naive_gemm( alpha: f64, a: MatrixView<f64>, b: MatrixView<f64>
+             , beta: f64, mut c: MatrixViewMut<f64>) {
+    for y in 0..c.nrows() {
+        for x in 0..c.ncols() {
+            let mut sum = 0.0;
+            for k in 0..a.ncols() { sum += a[(k, y)] * b[(x, k)] }
+            c[(x, y)] = alpha * sum + beta * c[(x, y)];
+        }
+    }
+}
+
+fn bench_gemm(c: &mut Criterion) {
+    let mut group = c.benchmark_group("gemm_f64");
+    for &n in &[64, 256, 512] {
+        let a = MatrixN::from_fn(n, n, |x, y| {
+            (x * 7 + y * 3) as f64 / n as f64
+        });
+        let b = a.transpose();
+        let mut out = MatrixN::from_elem(n, n, 0.0);
+        group.bench_with_input(BenchmarkId::new("naive", n), &n, |bench, _| {
+            bench.iter(|| naive_gemm( 1.0, black_box(a.view()), b.view()
+                                    , 0.0, out.view_mut()))
+        });
+        group.bench_with_input(BenchmarkId::new("blocked", n), &n, |bench, _| {
+            bench.iter(|| gemm( 1.0, black_box(a.view()), b.view()
+                              , 0.0, out.view_mut()).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_gemm);
+criterion_main!(benches);
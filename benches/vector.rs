@@ -0,0 +1,36 @@
+extern crate criterion;
+extern crate lin;
+
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use lin::vector::Vector2;
+
+fn bench_v2_addition_simple(c: &mut Criterion) {
+    let v1 = Vector2 { x: 1, y: 1 };
+    let v2 = Vector2 { x: 2, y: 2 };
+    c.bench_function("v2_addition_simple", |b| {
+        b.iter(|| black_box(v1) + black_box(v2))
+    });
+}
+
+fn bench_v2_subtraction_simple(c: &mut Criterion) {
+    let v1 = Vector2 { x: 2, y: 2 };
+    let v2 = Vector2 { x: 1, y: 1 };
+    c.bench_function("v2_subtraction_simple", |b| {
+        b.iter(|| black_box(v1) - black_box(v2))
+    });
+}
+
+fn bench_v2_dot_simple(c: &mut Criterion) {
+    let v1 = Vector2 { x: 2, y: 2 };
+    let v2 = Vector2 { x: 2, y: 3 };
+    c.bench_function("v2_dot_simple", |b| {
+        b.iter(|| black_box(v1) * black_box(v2))
+    });
+}
+
+criterion_group!( benches
+                , bench_v2_addition_simple
+                , bench_v2_subtraction_simple
+                , bench_v2_dot_simple
+                );
+criterion_main!(benches);
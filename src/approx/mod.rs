@@ -0,0 +1,260 @@
+//! Approximate equality comparisons.
+//!
+//! The derived `PartialEq` implementations on vectors and matrices compare
+//! elements exactly, which is rarely what is wanted for floating-point
+//! elements after any arithmetic has been performed on them. `ApproxEq`
+//! provides three kinds of tolerant comparison:
+//!
+//!   - `abs_diff_eq`: the absolute difference between two values is at most
+//!     `epsilon`
+//!   - `relative_eq`: the difference is at most `max_relative` times the
+//!     larger of the two values (or at most `epsilon`, for values near zero)
+//!   - `ulps_eq`: the two values are at most `max_ulps` representable
+//!     floating-point numbers apart (or at most `epsilon`, for values near
+//!     zero)
+//!
+//! For integers, all three comparisons are equivalent to `abs_diff_eq`.
+
+/// Something which can be compared for approximate equality.
+pub trait ApproxEq {
+    /// The type used to represent tolerances
+    type Epsilon: Copy;
+
+    /// The tolerance used when none is given
+    fn default_epsilon() -> Self::Epsilon;
+
+    /// The relative tolerance used by `relative_eq` when none is given
+    fn default_max_relative() -> Self::Epsilon;
+
+    /// The number of units in the last place used by `ulps_eq` when none is
+    /// given
+    fn default_max_ulps() -> u32;
+
+    /// Returns true if the absolute difference between `self` and `other`
+    /// is no greater than `epsilon`.
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool;
+
+    /// Returns true if `self` and `other` are equal within a tolerance
+    /// relative to their magnitudes.
+    fn relative_eq( &self, other: &Self
+                  , epsilon: Self::Epsilon
+                  , max_relative: Self::Epsilon) -> bool;
+
+    /// Returns true if `self` and `other` are no more than `max_ulps`
+    /// representable values apart.
+    fn ulps_eq(&self, other: &Self, epsilon: Self::Epsilon, max_ulps: u32)
+              -> bool;
+
+    /// Compares `self` and `other` using `relative_eq` with the default
+    /// tolerances.
+    #[inline]
+    fn approx_eq(&self, other: &Self) -> bool {
+        self.relative_eq( other
+                        , Self::default_epsilon()
+                        , Self::default_max_relative())
+    }
+}
+
+macro_rules! impl_approx_float {
+    ($($t: ident, $bits: ty),+) => { $(
+        impl ApproxEq for $t {
+            type Epsilon = $t;
+
+            #[inline] fn default_epsilon() -> $t { $t::EPSILON }
+            #[inline] fn default_max_relative() -> $t { $t::EPSILON }
+            #[inline] fn default_max_ulps() -> u32 { 4 }
+
+            #[inline]
+            fn abs_diff_eq(&self, other: &$t, epsilon: $t) -> bool {
+                (self - other).abs() <= epsilon
+            }
+
+            fn relative_eq(&self, other: &$t, epsilon: $t, max_relative: $t)
+                          -> bool {
+                if self == other { return true }
+                if self.is_infinite() || other.is_infinite() { return false }
+
+                let diff = (self - other).abs();
+                if diff <= epsilon { return true }
+
+                let largest = self.abs().max(other.abs());
+                diff <= largest * max_relative
+            }
+
+            fn ulps_eq(&self, other: &$t, epsilon: $t, max_ulps: u32)
+                      -> bool {
+                if self.abs_diff_eq(other, epsilon) { return true }
+                if self.is_nan() || other.is_nan()
+                || self.is_sign_positive() != other.is_sign_positive() {
+                    return false
+                }
+
+                // floats of the same sign are ordered the same way as their
+                // bit patterns, so the difference between the bit patterns
+                // is the number of representable values between them.
+                let a = self.to_bits() as $bits;
+                let b = other.to_bits() as $bits;
+                a.wrapping_sub(b).unsigned_abs() <= max_ulps.into()
+            }
+        }
+    )+}
+}
+
+impl_approx_float! { f32, i32, f64, i64 }
+
+macro_rules! impl_approx_int {
+    ($($t: ty),+) => { $(
+        impl ApproxEq for $t {
+            type Epsilon = $t;
+
+            #[inline] fn default_epsilon() -> $t { 0 }
+            #[inline] fn default_max_relative() -> $t { 0 }
+            #[inline] fn default_max_ulps() -> u32 { 0 }
+
+            #[inline]
+            fn abs_diff_eq(&self, other: &$t, epsilon: $t) -> bool {
+                let diff = if self > other { *self as i128 - *other as i128 }
+                           else { *other as i128 - *self as i128 };
+                diff <= epsilon as i128
+            }
+
+            #[inline]
+            fn relative_eq(&self, other: &$t, epsilon: $t, _: $t) -> bool {
+                self.abs_diff_eq(other, epsilon)
+            }
+
+            #[inline]
+            fn ulps_eq(&self, other: &$t, epsilon: $t, _: u32) -> bool {
+                self.abs_diff_eq(other, epsilon)
+            }
+        }
+    )+}
+}
+
+impl_approx_int! { u8, u16, u32, u64, usize
+                 , i8, i16, i32, i64, isize
+                 }
+
+#[doc(hidden)]
+pub fn check_abs_diff_eq<T>(a: &T, b: &T, epsilon: Option<T::Epsilon>) -> bool
+where T: ApproxEq {
+    a.abs_diff_eq(b, epsilon.unwrap_or_else(T::default_epsilon))
+}
+
+#[doc(hidden)]
+pub fn check_relative_eq<T>( a: &T, b: &T
+                           , epsilon: Option<T::Epsilon>
+                           , max_relative: Option<T::Epsilon>) -> bool
+where T: ApproxEq {
+    a.relative_eq( b
+                 , epsilon.unwrap_or_else(T::default_epsilon)
+                 , max_relative.unwrap_or_else(T::default_max_relative))
+}
+
+#[doc(hidden)]
+pub fn check_ulps_eq<T>( a: &T, b: &T
+                       , epsilon: Option<T::Epsilon>
+                       , max_ulps: Option<u32>) -> bool
+where T: ApproxEq {
+    a.ulps_eq( b
+             , epsilon.unwrap_or_else(T::default_epsilon)
+             , max_ulps.unwrap_or_else(T::default_max_ulps))
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __assert_approx {
+    ($check:ident, $left:expr, $right:expr, $($arg:expr),+) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if !$crate::approx::$check(left, right, $($arg),+) {
+                    panic!( "assertion failed: `{} ≈ {}` ({})\n  left: `{:?}`\n \
+                             right: `{:?}`"
+                          , stringify!($left), stringify!($right)
+                          , stringify!($check), left, right)
+                }
+            }
+        }
+    }
+}
+
+/// Asserts that two expressions are equal using `ApproxEq::abs_diff_eq`.
+///
+/// # Example
+///   ```ignore
+///   assert_abs_diff_eq!(a, b);
+///   assert_abs_diff_eq!(a, b, epsilon = 0.001);
+///   ```
+#[macro_export]
+macro_rules! assert_abs_diff_eq {
+    ($left:expr, $right:expr) => {
+        $crate::__assert_approx!(check_abs_diff_eq, $left, $right, None)
+    };
+    ($left:expr, $right:expr, epsilon = $eps:expr) => {
+        $crate::__assert_approx!( check_abs_diff_eq, $left, $right
+                                , Some($eps))
+    };
+}
+
+/// Asserts that two expressions are equal using `ApproxEq::relative_eq`.
+///
+/// # Example
+///   ```ignore
+///   assert_relative_eq!(a, b);
+///   assert_relative_eq!(a, b, epsilon = 0.001, max_relative = 0.01);
+///   ```
+#[macro_export]
+macro_rules! assert_relative_eq {
+    ($left:expr, $right:expr) => {
+        $crate::__assert_approx!( check_relative_eq, $left, $right
+                                , None, None)
+    };
+    ($left:expr, $right:expr, epsilon = $eps:expr) => {
+        $crate::__assert_approx!( check_relative_eq, $left, $right
+                                , Some($eps), None)
+    };
+    ($left:expr, $right:expr, max_relative = $rel:expr) => {
+        $crate::__assert_approx!( check_relative_eq, $left, $right
+                                , None, Some($rel))
+    };
+    ($left:expr, $right:expr, epsilon = $eps:expr, max_relative = $rel:expr) => {
+        $crate::__assert_approx!( check_relative_eq, $left, $right
+                                , Some($eps), Some($rel))
+    };
+}
+
+/// Asserts that two expressions are equal using `ApproxEq::ulps_eq`.
+///
+/// # Example
+///   ```ignore
+///   assert_ulps_eq!(a, b);
+///   assert_ulps_eq!(a, b, epsilon = 0.001, max_ulps = 8);
+///   ```
+#[macro_export]
+macro_rules! assert_ulps_eq {
+    ($left:expr, $right:expr) => {
+        $crate::__assert_approx!(check_ulps_eq, $left, $right, None, None)
+    };
+    ($left:expr, $right:expr, epsilon = $eps:expr) => {
+        $crate::__assert_approx!( check_ulps_eq, $left, $right
+                                , Some($eps), None)
+    };
+    ($left:expr, $right:expr, max_ulps = $ulps:expr) => {
+        $crate::__assert_approx!( check_ulps_eq, $left, $right
+                                , None, Some($ulps))
+    };
+    ($left:expr, $right:expr, epsilon = $eps:expr, max_ulps = $ulps:expr) => {
+        $crate::__assert_approx!( check_ulps_eq, $left, $right
+                                , Some($eps), Some($ulps))
+    };
+}
+
+/// Asserts that two expressions are approximately equal, using
+/// `ApproxEq::relative_eq` with the default tolerances.
+#[macro_export]
+macro_rules! assert_approx_eq {
+    ($left:expr, $right:expr) => { $crate::assert_relative_eq!($left, $right) };
+}
+
+// declared after the assertion macros so that the tests can use them.
+#[cfg(test)] mod test;
@@ -0,0 +1,64 @@
+use super::*;
+use vector::Vector3;
+use matrix::Matrix2;
+
+#[test]
+fn test_f32_abs_diff_eq() {
+    assert!(1.0f32.abs_diff_eq(&1.0005, 0.001));
+    assert!(!1.0f32.abs_diff_eq(&1.002, 0.001));
+    assert!(!f32::NAN.abs_diff_eq(&f32::NAN, 1.0))
+}
+
+#[test]
+fn test_f64_relative_eq() {
+    assert!(1.0e10f64.relative_eq(&(1.0e10 + 1.0), 0.0, 1.0e-9));
+    assert!(!1.0e10f64.relative_eq(&(1.0e10 + 100.0), 0.0, 1.0e-9));
+    assert!(f64::INFINITY.relative_eq(&f64::INFINITY, 0.0, 0.0));
+    assert!(!f64::INFINITY.relative_eq(&f64::MAX, 0.0, 1.0))
+}
+
+#[test]
+fn test_f32_ulps_eq() {
+    let a = 1.0f32;
+    let b = f32::from_bits(a.to_bits() + 3);
+    assert!(a.ulps_eq(&b, 0.0, 4));
+    assert!(!a.ulps_eq(&b, 0.0, 2));
+    assert!(0.0f32.ulps_eq(&-0.0, 0.0, 0));
+    assert!(!1.0f32.ulps_eq(&-1.0, 0.0, u32::MAX))
+}
+
+#[test]
+fn test_int_abs_diff_eq() {
+    assert!(5u8.abs_diff_eq(&7, 2));
+    assert!(!5u8.abs_diff_eq(&8, 2));
+    assert!(!i8::MIN.abs_diff_eq(&i8::MAX, 127));
+    assert!(3i32.approx_eq(&3))
+}
+
+#[test]
+fn test_vector_approx_eq() {
+    let v = Vector3 { x: 0.1f32, y: 0.2, z: 0.3 };
+    let w = Vector3 { x: 0.3f32, y: 0.6, z: 0.9 } / 3.0;
+    assert!(v.approx_eq(&w));
+    assert!(!v.abs_diff_eq(&Vector3 { x: 0.1, y: 0.2, z: 0.4 }, 0.01));
+    assert_approx_eq!(v, w);
+    assert_ulps_eq!(v, w, max_ulps = 8);
+    assert_abs_diff_eq!(v, Vector3 { x: 0.1, y: 0.2, z: 0.31 }, epsilon = 0.1)
+}
+
+#[test]
+fn test_matrix_approx_eq() {
+    let m = Matrix2 { x1y1: 1.0f64, x2y1: 2.0
+                    , x1y2: 3.0, x2y2: 4.0
+                    };
+    let n = m.map(|e| e * 0.1 * 10.0);
+    assert_relative_eq!(m, n);
+    assert_relative_eq!(m, n, epsilon = 1.0e-12, max_relative = 1.0e-12)
+}
+
+#[test]
+#[should_panic]
+fn test_assert_approx_eq_fails() {
+    assert_approx_eq!(Vector3 { x: 1.0f32, y: 2.0, z: 3.0 }
+                     , Vector3 { x: 1.0, y: 2.0, z: 3.1 })
+}
@@ -0,0 +1,193 @@
+//! Interchangeable implementations of the most expensive dense kernels.
+//!
+//! A `Backend` provides matrix multiplication and the LU, Cholesky,
+//! symmetric eigenvalue and singular value decompositions, named after the
+//! LAPACK routines they correspond to. Algorithms written against the trait
+//! can run on any implementation:
+//!
+//!   - `Native`, the default, is written in pure Rust and is always
+//!     available
+//!   - `System`, enabled by the `system-blas` feature, calls the CBLAS and
+//!     LAPACKE interfaces of a library installed on the machine, such as
+//!     OpenBLAS
+//!
+//! ```ignore
+//! fn solve_normal_equations<B: Backend<f64>>(backend: &B, ...) { ... }
+//!
+//! solve_normal_equations(&Native, ...);
+//! ```
+//!
+//! Results from different backends agree up to rounding, except that
+//! eigenvectors and singular vectors are only determined up to sign.
+//!
+//! The tests checking `System` against `Native` only build with the
+//! `system-blas` feature, which needs OpenBLAS installed to link (the
+//! `libopenblas-dev` package on Debian and Ubuntu). CI installs it and runs
+//! them as a separate step, with `cargo test --features system-blas`.
+
+use blas::{Scalar, Triangle};
+use matrix::{MatrixView, MatrixViewMut};
+use error::{ShapeError, DecompositionError};
+use Real;
+
+#[cfg(test)] mod test;
+mod native;
+#[cfg(feature = "system-blas")]
+mod system;
+
+#[cfg(feature = "system-blas")]
+pub use self::system::System;
+
+/// An implementation of the heavy dense kernels for elements of type `N`.
+pub trait Backend<N>
+where N: Scalar {
+
+    /// Computes `c = alpha * a * b + beta * c`, as `blas::gemm` does.
+    ///
+    /// # Returns:
+    ///   - `Ok(())` if `a` is `m` by `k`, `b` is `k` by `n` and `c` is `m`
+    ///     by `n`
+    ///   - `Err(ShapeError)` otherwise, in which case `c` is left unchanged
+    ///
+    fn gemm( &self, alpha: N, a: MatrixView<N>, b: MatrixView<N>
+           , beta: N, c: MatrixViewMut<N>) -> Result<(), ShapeError>;
+
+    /// Computes the LU decomposition of `a` with partial pivoting, so that
+    /// `a = P * L * U`.
+    ///
+    /// `L` is unit lower triangular and is stored below the diagonal of `a`,
+    /// and `U` is upper triangular and is stored in the rest of `a`. Row `i`
+    /// was interchanged with row `pivots[i]`, in order of increasing `i`.
+    ///
+    /// # Returns:
+    ///   - `Ok(())` if the decomposition succeeded
+    ///   - `Err(DecompositionError::Shape)` if `pivots` does not have as
+    ///     many elements as the smaller dimension of `a`
+    ///   - `Err(DecompositionError::Singular)` if `U` has a zero on its
+    ///     diagonal, in which case the decomposition is still complete, but
+    ///     `U` cannot be used to solve a system of equations
+    ///
+    fn getrf(&self, a: MatrixViewMut<N>, pivots: &mut [usize])
+            -> Result<(), DecompositionError>;
+
+    /// Computes the Cholesky decomposition of the symmetric positive
+    /// definite matrix `a`, reading and overwriting only its `triangle`.
+    ///
+    /// For the `Lower` triangle, this computes `L` such that
+    /// `a = L * Lᵀ`; for the `Upper` triangle, `U` such that
+    /// `a = Uᵀ * U`.
+    ///
+    /// # Returns:
+    ///   - `Ok(())` if the decomposition succeeded
+    ///   - `Err(DecompositionError::Shape)` if `a` is not square
+    ///   - `Err(DecompositionError::NotPositiveDefinite)` if `a` is not
+    ///     positive definite, in which case `a` is left partially factored
+    ///
+    fn potrf(&self, triangle: Triangle, a: MatrixViewMut<N>)
+            -> Result<(), DecompositionError>;
+
+    /// Computes the eigenvalues and eigenvectors of the symmetric matrix
+    /// `a`, reading only its `triangle`.
+    ///
+    /// The eigenvalues are written to `w` in ascending order, and `a` is
+    /// overwritten with the corresponding orthonormal eigenvectors, as its
+    /// columns.
+    ///
+    /// # Returns:
+    ///   - `Ok(())` if the decomposition succeeded
+    ///   - `Err(DecompositionError::Shape)` if `a` is not square, or `w`
+    ///     does not have one element for each of its rows
+    ///   - `Err(DecompositionError::NoConvergence)` if the algorithm failed
+    ///     to converge
+    ///
+    fn syev(&self, triangle: Triangle, a: MatrixViewMut<N>, w: &mut [N])
+           -> Result<(), DecompositionError>;
+
+    /// Computes the thin singular value decomposition `a = U * Σ * Vᵀ` of
+    /// the `m` by `n` matrix `a`, destroying the contents of `a`.
+    ///
+    /// With `k` the smaller of `m` and `n`, the `k` singular values are
+    /// written to `s` in descending order, the `m` by `k` matrix `U` to `u`
+    /// and the `k` by `n` matrix `Vᵀ` to `vt`.
+    ///
+    /// # Returns:
+    ///   - `Ok(())` if the decomposition succeeded
+    ///   - `Err(DecompositionError::Shape)` describing the first output
+    ///     with the wrong shape
+    ///   - `Err(DecompositionError::NoConvergence)` if the algorithm failed
+    ///     to converge
+    ///
+    fn gesdd( &self, a: MatrixViewMut<N>, s: &mut [N]
+            , u: MatrixViewMut<N>, vt: MatrixViewMut<N>)
+            -> Result<(), DecompositionError>;
+}
+
+/// The pure-Rust implementation of `Backend`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Native;
+
+impl<N> Backend<N> for Native
+where N: Scalar + Real {
+
+    #[inline]
+    fn gemm( &self, alpha: N, a: MatrixView<N>, b: MatrixView<N>
+           , beta: N, c: MatrixViewMut<N>) -> Result<(), ShapeError> {
+        ::blas::gemm(alpha, a, b, beta, c)
+    }
+
+    #[inline]
+    fn getrf(&self, a: MatrixViewMut<N>, pivots: &mut [usize])
+            -> Result<(), DecompositionError> {
+        native::getrf(a, pivots)
+    }
+
+    #[inline]
+    fn potrf(&self, triangle: Triangle, a: MatrixViewMut<N>)
+            -> Result<(), DecompositionError> {
+        native::potrf(triangle, a)
+    }
+
+    #[inline]
+    fn syev(&self, triangle: Triangle, a: MatrixViewMut<N>, w: &mut [N])
+           -> Result<(), DecompositionError> {
+        native::syev(triangle, a, w)
+    }
+
+    #[inline]
+    fn gesdd( &self, a: MatrixViewMut<N>, s: &mut [N]
+            , u: MatrixViewMut<N>, vt: MatrixViewMut<N>)
+            -> Result<(), DecompositionError> {
+        native::gesdd(a, s, u, vt)
+    }
+}
+
+/// Returns an error unless `found` has the `expected` shape.
+#[inline]
+fn check_shape(expected: (usize, usize), found: (usize, usize))
+              -> Result<(), ShapeError> {
+    if expected == found { Ok(()) }
+    else { Err(ShapeError { expected, found }) }
+}
+
+/// Returns an error unless the shapes of the operands of `getrf` agree.
+#[inline]
+fn check_getrf(a: (usize, usize), pivots: usize) -> Result<(), ShapeError> {
+    check_shape((a.0.min(a.1), 1), (pivots, 1))
+}
+
+/// Returns an error unless `a` is square, and otherwise its size.
+#[inline]
+fn check_square(a: (usize, usize)) -> Result<usize, ShapeError> {
+    check_shape((a.0, a.0), a).map(|_| a.0)
+}
+
+/// Returns an error unless the shapes of the operands of `gesdd` agree.
+fn check_gesdd( a: (usize, usize), s: usize
+              , u: (usize, usize), vt: (usize, usize))
+              -> Result<(), ShapeError> {
+    let (m, n) = a;
+    let k = m.min(n);
+    check_shape((k, 1), (s, 1))?;
+    check_shape((m, k), u)?;
+    check_shape((k, n), vt)
+}
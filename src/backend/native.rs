@@ -0,0 +1,285 @@
+//! The pure-Rust implementations of the decompositions.
+//!
+//! The LU and Cholesky decompositions are the unblocked textbook
+//! algorithms. The eigenvalue and singular value decompositions use Jacobi
+//! rotations, which are slower than the algorithms used by LAPACK for large
+//! matrices, but simple, and accurate even for small eigenvalues and
+//! singular values.
+
+use super::{check_shape, check_getrf, check_square, check_gesdd};
+use blas::{self, Scalar, Triangle};
+use matrix::{MatrixN, MatrixViewMut};
+use error::DecompositionError;
+use Real;
+
+use alloc::vec::Vec;
+use std::cmp::Ordering;
+
+/// The number of sweeps of Jacobi rotations after which an iteration is
+/// considered not to converge. Each sweep roughly squares the size of the
+/// off-diagonal elements, so in practice about ten are needed.
+const MAX_SWEEPS: usize = 64;
+
+pub fn getrf<N>(mut a: MatrixViewMut<N>, pivots: &mut [usize])
+               -> Result<(), DecompositionError>
+where N: Scalar {
+    check_getrf(a.shape(), pivots.len())?;
+    let (m, n) = a.shape();
+    let mut singular = None;
+    for j in 0..pivots.len() {
+        let p = (j..m).fold(j, |p, i| {
+            if a[(j, i)].abs() > a[(j, p)].abs() { i } else { p }
+        });
+        pivots[j] = p;
+        if a[(j, p)] == N::ZERO {
+            // the column is already zero below the diagonal
+            singular = singular.or(Some(j));
+            continue
+        }
+        if p != j {
+            for x in 0..n {
+                let t = a[(x, j)];
+                a[(x, j)] = a[(x, p)];
+                a[(x, p)] = t;
+            }
+        }
+        let d = a[(j, j)];
+        for i in j + 1..m {
+            let l = a[(j, i)] / d;
+            a[(j, i)] = l;
+            for x in j + 1..n {
+                let u = a[(x, j)];
+                a[(x, i)] -= l * u;
+            }
+        }
+    }
+    match singular {
+        Some(index) => Err(DecompositionError::Singular { index })
+      , None => Ok(())
+    }
+}
+
+pub fn potrf<N>(triangle: Triangle, a: MatrixViewMut<N>)
+               -> Result<(), DecompositionError>
+where N: Scalar + Real {
+    let n = check_square(a.shape())?;
+    // the upper triangle of `a` is the lower triangle of its transpose, and
+    // `Uᵀ * U` is `L * Lᵀ` with `L = Uᵀ`.
+    let mut a = match triangle { Triangle::Lower => a
+                               , Triangle::Upper => a.transpose() };
+    for j in 0..n {
+        let d = (0..j).fold(a[(j, j)], |d, k| d - a[(k, j)] * a[(k, j)]);
+        // NaN is unordered, so it is rejected too
+        if d.partial_cmp(&N::ZERO) != Some(Ordering::Greater) {
+            return Err(DecompositionError::NotPositiveDefinite { index: j })
+        }
+        let l = d.sqrt();
+        a[(j, j)] = l;
+        for i in j + 1..n {
+            let t = (0..j).fold(a[(j, i)], |t, k| t - a[(k, i)] * a[(k, j)]);
+            a[(j, i)] = t / l;
+        }
+    }
+    Ok(())
+}
+
+pub fn syev<N>(triangle: Triangle, mut a: MatrixViewMut<N>, w: &mut [N])
+              -> Result<(), DecompositionError>
+where N: Scalar + Real {
+    let n = check_square(a.shape())?;
+    check_shape((n, 1), (w.len(), 1))?;
+    let mut s = MatrixN::from_fn(n, n, |x, y| {
+        let stored = match triangle { Triangle::Upper => y <= x
+                                    , Triangle::Lower => y >= x };
+        if stored { a[(x, y)] } else { a[(y, x)] }
+    });
+    let mut v = identity(n);
+
+    // rotate away each off-diagonal element in turn, until all of them are
+    // negligible next to the diagonal.
+    let mut converged = false;
+    for _ in 0..MAX_SWEEPS {
+        let mut rotated = false;
+        for p in 0..n {
+            for q in p + 1..n {
+                let (app, aqq, apq) = (s[(p, p)], s[(q, q)], s[(q, p)]);
+                if negligible(apq, app, aqq) { continue }
+                rotated = true;
+                let (c, sn) = rotation((aqq - app) / (apq + apq));
+                rotate_cols(&mut s, p, q, c, sn);
+                rotate_rows(&mut s, p, q, c, sn);
+                rotate_cols(&mut v, p, q, c, sn);
+            }
+        }
+        if !rotated { converged = true; break }
+    }
+    if !converged { return Err(DecompositionError::NoConvergence) }
+
+    let order = sorted(n, |i| s[(i, i)], Ordering::Less);
+    for (i, &j) in order.iter().enumerate() {
+        w[i] = s[(j, j)];
+        for y in 0..n { a[(i, y)] = v[(j, y)] }
+    }
+    Ok(())
+}
+
+pub fn gesdd<N>( a: MatrixViewMut<N>, s: &mut [N]
+               , mut u: MatrixViewMut<N>, mut vt: MatrixViewMut<N>)
+               -> Result<(), DecompositionError>
+where N: Scalar + Real {
+    check_gesdd(a.shape(), s.len(), u.shape(), vt.shape())?;
+    let (m, n) = a.shape();
+    // `a = U * Σ * Vᵀ` if and only if `aᵀ = V * Σ * Uᵀ`, so only tall
+    // matrices need to be handled.
+    if m < n {
+        return gesdd(a.transpose(), s, vt.transpose(), u.transpose())
+    }
+
+    // rotate pairs of columns of `a` until they are all orthogonal, at
+    // which point they are the columns of `U * Σ`.
+    let mut w = MatrixN::from_fn(m, n, |x, y| a[(x, y)]);
+    let mut v = identity(n);
+    let mut converged = false;
+    for _ in 0..MAX_SWEEPS {
+        let mut rotated = false;
+        for p in 0..n {
+            for q in p + 1..n {
+                let (mut alpha, mut beta) = (N::ZERO, N::ZERO);
+                let mut gamma = N::ZERO;
+                for y in 0..m {
+                    let (wp, wq) = (w[(p, y)], w[(q, y)]);
+                    alpha += wp * wp;
+                    beta += wq * wq;
+                    gamma += wp * wq;
+                }
+                if negligible(gamma, alpha, beta) { continue }
+                rotated = true;
+                let (c, sn) = rotation((beta - alpha) / (gamma + gamma));
+                rotate_cols(&mut w, p, q, c, sn);
+                rotate_cols(&mut v, p, q, c, sn);
+            }
+        }
+        if !rotated { converged = true; break }
+    }
+    if !converged { return Err(DecompositionError::NoConvergence) }
+
+    let norms = (0..n).map(|x| {
+        blas::nrm2(w.view().column(x).expect("column lies within w"))
+    }).collect::<Vec<_>>();
+    let order = sorted(n, |j| norms[j], Ordering::Greater);
+    let mut basis = MatrixN::from_elem(m, n, N::ZERO);
+    for (i, &j) in order.iter().enumerate() {
+        s[i] = norms[j];
+        for x in 0..n { vt[(x, i)] = v[(j, x)] }
+        if norms[j] != N::ZERO {
+            for y in 0..m { basis[(i, y)] = w[(j, y)] / norms[j] }
+        }
+    }
+    // the columns of `U` for zero singular values are arbitrary, as long as
+    // `U` is orthonormal.
+    for i in (0..n).filter(|&i| s[i] == N::ZERO) {
+        complete(&mut basis, i);
+    }
+    for x in 0..n {
+        for y in 0..m { u[(x, y)] = basis[(x, y)] }
+    }
+    Ok(())
+}
+
+/// Returns the `n` by `n` identity matrix.
+fn identity<N>(n: usize) -> MatrixN<N>
+where N: Scalar {
+    MatrixN::from_fn(n, n, |x, y| if x == y { N::ONE } else { N::ZERO })
+}
+
+/// Returns true if the off-diagonal element `apq` of a symmetric matrix is
+/// too small relative to the diagonal elements `app` and `aqq` to affect
+/// its eigenvalues.
+#[inline]
+fn negligible<N>(apq: N, app: N, aqq: N) -> bool
+where N: Scalar + Real {
+    apq == N::ZERO
+    || Scalar::abs(apq) <= N::EPSILON * Scalar::abs(app).sqrt()
+                                      * Scalar::abs(aqq).sqrt()
+}
+
+/// Returns the cosine and sine of the Jacobi rotation which eliminates the
+/// off-diagonal element `apq` of a symmetric matrix, given
+/// `theta = (aqq - app) / (2 * apq)`.
+///
+/// This is the smaller of the two possible rotations, which keeps the
+/// iteration stable.
+#[inline]
+fn rotation<N>(theta: N) -> (N, N)
+where N: Scalar + Real {
+    let sign = if theta < N::ZERO { -N::ONE } else { N::ONE };
+    let t = sign / (Scalar::abs(theta) + (theta * theta + N::ONE).sqrt());
+    let c = N::ONE / (t * t + N::ONE).sqrt();
+    (c, t * c)
+}
+
+/// Replaces columns `p` and `q` of `m` with `c * p - s * q` and
+/// `s * p + c * q`.
+fn rotate_cols<N>(m: &mut MatrixN<N>, p: usize, q: usize, c: N, s: N)
+where N: Scalar {
+    for y in 0..m.nrows() {
+        let (mp, mq) = (m[(p, y)], m[(q, y)]);
+        m[(p, y)] = c * mp - s * mq;
+        m[(q, y)] = s * mp + c * mq;
+    }
+}
+
+/// Replaces rows `p` and `q` of `m` with `c * p - s * q` and
+/// `s * p + c * q`.
+fn rotate_rows<N>(m: &mut MatrixN<N>, p: usize, q: usize, c: N, s: N)
+where N: Scalar {
+    for x in 0..m.ncols() {
+        let (mp, mq) = (m[(x, p)], m[(x, q)]);
+        m[(x, p)] = c * mp - s * mq;
+        m[(x, q)] = s * mp + c * mq;
+    }
+}
+
+/// Returns the indices `0..n`, sorted so that `key` is in `order`.
+fn sorted<N, F>(n: usize, key: F, order: Ordering) -> Vec<usize>
+where N: Scalar
+    , F: Fn(usize) -> N {
+    let mut indices = (0..n).collect::<Vec<_>>();
+    indices.sort_by(|&i, &j| {
+        let cmp = key(i).partial_cmp(&key(j)).unwrap_or(Ordering::Equal);
+        if order == Ordering::Less { cmp } else { cmp.reverse() }
+    });
+    indices
+}
+
+/// Sets column `i` of `basis` to a unit vector orthogonal to all of its
+/// other nonzero columns.
+fn complete<N>(basis: &mut MatrixN<N>, i: usize)
+where N: Scalar + Real {
+    let (m, n) = basis.shape();
+    // of the standard basis vectors, the one with the largest component
+    // orthogonal to the other columns.
+    let orthogonal = |r: usize| {
+        let mut e = (0..m).map(|y| if y == r { N::ONE } else { N::ZERO })
+                          .collect::<Vec<_>>();
+        // twice, so that the result is orthogonal to working precision
+        for _ in 0..2 {
+            for x in (0..n).filter(|&x| x != i) {
+                let col = basis.view().column(x).expect("column lies within");
+                let d = blas::dot(col, &e[..]).expect("lengths match");
+                blas::axpy(-d, col, &mut e[..]).expect("lengths match");
+            }
+        }
+        e
+    };
+    let mut best: Option<(Vec<N>, N)> = None;
+    for r in 0..m {
+        let e = orthogonal(r);
+        let norm = blas::nrm2(&e[..]);
+        if best.as_ref().is_none_or(|&(_, b)| norm > b) {
+            best = Some((e, norm))
+        }
+    }
+    let (e, norm) = best.expect("basis has at least one row");
+    for y in 0..m { basis[(i, y)] = e[y] / norm }
+}
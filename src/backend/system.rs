@@ -0,0 +1,225 @@
+//! The `Backend` implementation which calls a CBLAS and LAPACKE library
+//! installed on the machine.
+//!
+//! This links against `libopenblas`, which provides both interfaces.
+//! Operands are copied into column-major buffers for each call, which takes
+//! time proportional to their size, against the cubic time taken by the
+//! kernels themselves.
+
+use super::{Backend, check_shape, check_getrf, check_square, check_gesdd};
+use blas::Triangle;
+use matrix::{MatrixView, MatrixViewMut};
+use error::{ShapeError, DecompositionError};
+
+use std::cmp::{max, min};
+use std::convert::TryInto;
+use std::os::raw::{c_char, c_int};
+
+/// `CblasColMajor` and `LAPACK_COL_MAJOR`
+const COL_MAJOR: c_int = 102;
+/// `CblasNoTrans`
+const NO_TRANS: c_int = 111;
+
+#[link(name = "openblas")]
+extern "C" {
+    fn cblas_sgemm( layout: c_int, transa: c_int, transb: c_int
+                  , m: c_int, n: c_int, k: c_int
+                  , alpha: f32, a: *const f32, lda: c_int
+                  , b: *const f32, ldb: c_int
+                  , beta: f32, c: *mut f32, ldc: c_int);
+    fn cblas_dgemm( layout: c_int, transa: c_int, transb: c_int
+                  , m: c_int, n: c_int, k: c_int
+                  , alpha: f64, a: *const f64, lda: c_int
+                  , b: *const f64, ldb: c_int
+                  , beta: f64, c: *mut f64, ldc: c_int);
+
+    fn LAPACKE_sgetrf( layout: c_int, m: c_int, n: c_int
+                     , a: *mut f32, lda: c_int, ipiv: *mut c_int) -> c_int;
+    fn LAPACKE_dgetrf( layout: c_int, m: c_int, n: c_int
+                     , a: *mut f64, lda: c_int, ipiv: *mut c_int) -> c_int;
+
+    fn LAPACKE_spotrf( layout: c_int, uplo: c_char, n: c_int
+                     , a: *mut f32, lda: c_int) -> c_int;
+    fn LAPACKE_dpotrf( layout: c_int, uplo: c_char, n: c_int
+                     , a: *mut f64, lda: c_int) -> c_int;
+
+    fn LAPACKE_ssyev( layout: c_int, jobz: c_char, uplo: c_char, n: c_int
+                    , a: *mut f32, lda: c_int, w: *mut f32) -> c_int;
+    fn LAPACKE_dsyev( layout: c_int, jobz: c_char, uplo: c_char, n: c_int
+                    , a: *mut f64, lda: c_int, w: *mut f64) -> c_int;
+
+    fn LAPACKE_sgesdd( layout: c_int, jobz: c_char, m: c_int, n: c_int
+                     , a: *mut f32, lda: c_int, s: *mut f32
+                     , u: *mut f32, ldu: c_int
+                     , vt: *mut f32, ldvt: c_int) -> c_int;
+    fn LAPACKE_dgesdd( layout: c_int, jobz: c_char, m: c_int, n: c_int
+                     , a: *mut f64, lda: c_int, s: *mut f64
+                     , u: *mut f64, ldu: c_int
+                     , vt: *mut f64, ldvt: c_int) -> c_int;
+}
+
+/// The implementation of `Backend` which calls a system CBLAS and LAPACKE.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct System;
+
+/// Converts a dimension to a LAPACK integer.
+#[inline]
+fn int(n: usize) -> c_int {
+    n.try_into().expect("dimension fits in a LAPACK integer")
+}
+
+/// Returns the leading dimension of a column-major matrix with `nrows`
+/// rows, which LAPACK requires to be at least one.
+#[inline]
+fn ld(nrows: usize) -> c_int { int(max(nrows, 1)) }
+
+/// Returns the LAPACK name of a triangle.
+#[inline]
+fn uplo(triangle: Triangle) -> c_char {
+    match triangle { Triangle::Upper => b'U' as c_char
+                   , Triangle::Lower => b'L' as c_char }
+}
+
+/// Panics if LAPACKE reports that one of its arguments was invalid, which
+/// can only happen if the checks made before calling it are wrong.
+#[inline]
+fn check_info(info: c_int) {
+    assert!(info >= 0, "LAPACKE rejected argument {}", -info);
+}
+
+/// Copies the elements of `a` into a new column-major buffer.
+fn to_col_major<N>(a: &MatrixView<N>) -> Vec<N>
+where N: Copy {
+    (0..a.ncols()).flat_map(|x| (0..a.nrows()).map(move |y| a[(x, y)]))
+                  .collect()
+}
+
+/// Copies the elements of a column-major buffer into `a`.
+fn from_col_major<N>(elems: &[N], mut a: MatrixViewMut<N>)
+where N: Copy {
+    let m = a.nrows();
+    for (i, &elem) in elems.iter().enumerate() { a[(i / m, i % m)] = elem }
+}
+
+macro_rules! impl_system {
+    ($($t: ty, $gemm: ident, $getrf: ident, $potrf: ident, $syev: ident
+           , $gesdd: ident);+) => { $(
+        impl Backend<$t> for System {
+
+            fn gemm( &self, alpha: $t, a: MatrixView<$t>, b: MatrixView<$t>
+                   , beta: $t, c: MatrixViewMut<$t>)
+                   -> Result<(), ShapeError> {
+                let (m, k) = a.shape();
+                let n = b.ncols();
+                check_shape((k, n), b.shape())?;
+                check_shape((m, n), c.shape())?;
+                if m == 0 || n == 0 { return Ok(()) }
+
+                let (a, b) = (to_col_major(&a), to_col_major(&b));
+                // with a `beta` of zero, `c` is not read, and may be NaN
+                let mut elems = if beta == 0.0 { vec![0.0; m * n] }
+                                else { to_col_major(&c.as_view()) };
+                unsafe {
+                    $gemm( COL_MAJOR, NO_TRANS, NO_TRANS
+                         , int(m), int(n), int(k)
+                         , alpha, a.as_ptr(), ld(m), b.as_ptr(), ld(k)
+                         , beta, elems.as_mut_ptr(), ld(m));
+                }
+                from_col_major(&elems, c);
+                Ok(())
+            }
+
+            fn getrf(&self, a: MatrixViewMut<$t>, pivots: &mut [usize])
+                    -> Result<(), DecompositionError> {
+                check_getrf(a.shape(), pivots.len())?;
+                if pivots.is_empty() { return Ok(()) }
+
+                let (m, n) = a.shape();
+                let mut elems = to_col_major(&a.as_view());
+                let mut ipiv = vec![0; pivots.len()];
+                let info = unsafe {
+                    $getrf( COL_MAJOR, int(m), int(n)
+                          , elems.as_mut_ptr(), ld(m), ipiv.as_mut_ptr())
+                };
+                check_info(info);
+                from_col_major(&elems, a);
+                // LAPACK counts from one
+                for (p, &i) in pivots.iter_mut().zip(&ipiv) {
+                    *p = i as usize - 1;
+                }
+                if info > 0 {
+                    Err(DecompositionError::Singular {
+                        index: info as usize - 1
+                    })
+                } else { Ok(()) }
+            }
+
+            fn potrf(&self, triangle: Triangle, a: MatrixViewMut<$t>)
+                    -> Result<(), DecompositionError> {
+                let n = check_square(a.shape())?;
+                if n == 0 { return Ok(()) }
+
+                let mut elems = to_col_major(&a.as_view());
+                let info = unsafe {
+                    $potrf( COL_MAJOR, uplo(triangle), int(n)
+                          , elems.as_mut_ptr(), ld(n))
+                };
+                check_info(info);
+                from_col_major(&elems, a);
+                if info > 0 {
+                    Err(DecompositionError::NotPositiveDefinite {
+                        index: info as usize - 1
+                    })
+                } else { Ok(()) }
+            }
+
+            fn syev( &self, triangle: Triangle, a: MatrixViewMut<$t>
+                   , w: &mut [$t]) -> Result<(), DecompositionError> {
+                let n = check_square(a.shape())?;
+                check_shape((n, 1), (w.len(), 1))?;
+                if n == 0 { return Ok(()) }
+
+                let mut elems = to_col_major(&a.as_view());
+                let info = unsafe {
+                    $syev( COL_MAJOR, b'V' as c_char, uplo(triangle), int(n)
+                         , elems.as_mut_ptr(), ld(n), w.as_mut_ptr())
+                };
+                check_info(info);
+                if info > 0 { return Err(DecompositionError::NoConvergence) }
+                from_col_major(&elems, a);
+                Ok(())
+            }
+
+            fn gesdd( &self, a: MatrixViewMut<$t>, s: &mut [$t]
+                    , u: MatrixViewMut<$t>, vt: MatrixViewMut<$t>)
+                    -> Result<(), DecompositionError> {
+                check_gesdd(a.shape(), s.len(), u.shape(), vt.shape())?;
+                let (m, n) = a.shape();
+                let k = min(m, n);
+                if k == 0 { return Ok(()) }
+
+                let mut elems = to_col_major(&a.as_view());
+                let (mut u_elems, mut vt_elems) = ( vec![0.0; m * k]
+                                                  , vec![0.0; k * n]);
+                let info = unsafe {
+                    $gesdd( COL_MAJOR, b'S' as c_char, int(m), int(n)
+                          , elems.as_mut_ptr(), ld(m), s.as_mut_ptr()
+                          , u_elems.as_mut_ptr(), ld(m)
+                          , vt_elems.as_mut_ptr(), ld(k))
+                };
+                check_info(info);
+                if info > 0 { return Err(DecompositionError::NoConvergence) }
+                from_col_major(&u_elems, u);
+                from_col_major(&vt_elems, vt);
+                Ok(())
+            }
+        }
+    )+ }
+}
+
+impl_system! {
+    f32, cblas_sgemm, LAPACKE_sgetrf, LAPACKE_spotrf, LAPACKE_ssyev
+       , LAPACKE_sgesdd;
+    f64, cblas_dgemm, LAPACKE_dgetrf, LAPACKE_dpotrf, LAPACKE_dsyev
+       , LAPACKE_dgesdd
+}
@@ -0,0 +1,256 @@
+use super::*;
+use blas::{self, Triangle};
+use matrix::MatrixN;
+use error::{ShapeError, DecompositionError};
+
+/// A small deterministic generator of elements in `[-1, 1)`.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self) -> f64 {
+        self.0 = self.0.wrapping_mul(6364136223846793005)
+                       .wrapping_add(1442695040888963407);
+        (self.0 >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+    }
+
+    fn matrix(&mut self, nrows: usize, ncols: usize) -> MatrixN<f64> {
+        MatrixN::from_fn(nrows, ncols, |_, _| self.next())
+    }
+
+    /// A random symmetric positive definite matrix
+    fn spd(&mut self, n: usize) -> MatrixN<f64> {
+        let b = self.matrix(n, n);
+        let mut a = MatrixN::from_fn(n, n, |x, y| {
+            if x == y { n as f64 } else { 0.0 }
+        });
+        blas::gemm(1.0, b.view(), b.view().transpose(), 1.0, a.view_mut())
+            .unwrap();
+        a
+    }
+}
+
+fn mul(a: &MatrixN<f64>, b: &MatrixN<f64>) -> MatrixN<f64> {
+    let mut c = MatrixN::from_elem(a.nrows(), b.ncols(), 0.0);
+    blas::gemm(1.0, a.view(), b.view(), 0.0, c.view_mut()).unwrap();
+    c
+}
+
+fn assert_close(a: &MatrixN<f64>, b: &MatrixN<f64>) {
+    assert_eq!(a.shape(), b.shape());
+    for (i, (&x, &y)) in a.iter().zip(b.iter()).enumerate() {
+        assert!( (x - y).abs() <= 1e-10
+               , "element {} differs: {:?} != {:?}", i, x, y);
+    }
+}
+
+fn assert_orthonormal_columns(q: &MatrixN<f64>) {
+    let n = q.ncols();
+    let identity = MatrixN::from_fn(n, n, |x, y| {
+        if x == y { 1.0 } else { 0.0 }
+    });
+    assert_close(&mul(&q.transpose(), q), &identity);
+}
+
+/// Returns the factors `L` and `U`, multiplied together and with the rows
+/// interchanged back into their original order.
+fn unpivot(lu: &MatrixN<f64>, pivots: &[usize]) -> MatrixN<f64> {
+    let (m, n) = lu.shape();
+    let k = pivots.len();
+    let l = MatrixN::from_fn(m, k, |x, y| {
+        if x == y { 1.0 } else if x < y { lu[(x, y)] } else { 0.0 }
+    });
+    let u = MatrixN::from_fn(k, n, |x, y| {
+        if x >= y { lu[(x, y)] } else { 0.0 }
+    });
+    let mut a = mul(&l, &u);
+    for (i, &p) in pivots.iter().enumerate().rev() {
+        for x in 0..n {
+            let t = a[(x, i)];
+            a[(x, i)] = a[(x, p)];
+            a[(x, p)] = t;
+        }
+    }
+    a
+}
+
+fn check_getrf<B>(backend: &B, a: &MatrixN<f64>) -> MatrixN<f64>
+where B: Backend<f64> {
+    let mut lu = a.clone();
+    let mut pivots = vec![0; a.nrows().min(a.ncols())];
+    backend.getrf(lu.view_mut(), &mut pivots).unwrap();
+    assert_close(&unpivot(&lu, &pivots), a);
+    lu
+}
+
+fn check_potrf<B>(backend: &B, a: &MatrixN<f64>) -> MatrixN<f64>
+where B: Backend<f64> {
+    let n = a.nrows();
+    let mut l = a.clone();
+    backend.potrf(Triangle::Lower, l.view_mut()).unwrap();
+    let l = MatrixN::from_fn(n, n, |x, y| {
+        if x <= y { l[(x, y)] } else { 0.0 }
+    });
+    assert_close(&mul(&l, &l.transpose()), a);
+
+    // only the upper triangle is read or written
+    let mut u = MatrixN::from_fn(n, n, |x, y| {
+        if x >= y { a[(x, y)] } else { f64::NAN }
+    });
+    backend.potrf(Triangle::Upper, u.view_mut()).unwrap();
+    assert!((0..n).all(|y| (0..y).all(|x| u[(x, y)].is_nan())));
+    let u = u.map(|x| if x.is_nan() { 0.0 } else { x });
+    assert_close(&u, &l.transpose());
+    l
+}
+
+fn check_syev<B>(backend: &B, a: &MatrixN<f64>) -> Vec<f64>
+where B: Backend<f64> {
+    let n = a.nrows();
+    let mut v = MatrixN::from_fn(n, n, |x, y| {
+        if x <= y { a[(x, y)] } else { f64::NAN }
+    });
+    let mut w = vec![0.0; n];
+    backend.syev(Triangle::Lower, v.view_mut(), &mut w).unwrap();
+    assert!(w.windows(2).all(|w| w[0] <= w[1]));
+    assert_orthonormal_columns(&v);
+    let vw = MatrixN::from_fn(n, n, |x, y| v[(x, y)] * w[x]);
+    assert_close(&mul(a, &v), &vw);
+    w
+}
+
+fn check_gesdd<B>(backend: &B, a: &MatrixN<f64>) -> Vec<f64>
+where B: Backend<f64> {
+    let (m, n) = a.shape();
+    let k = m.min(n);
+    let (mut s, mut u, mut vt) = ( vec![0.0; k]
+                                 , MatrixN::from_elem(m, k, f64::NAN)
+                                 , MatrixN::from_elem(k, n, f64::NAN));
+    backend.gesdd(a.clone().view_mut(), &mut s, u.view_mut(), vt.view_mut())
+           .unwrap();
+    assert!(s.windows(2).all(|s| s[0] >= s[1]));
+    assert!(s.iter().all(|&s| s >= 0.0));
+    assert_orthonormal_columns(&u);
+    assert_orthonormal_columns(&vt.transpose());
+    let us = MatrixN::from_fn(m, k, |x, y| u[(x, y)] * s[x]);
+    assert_close(&mul(&us, &vt), a);
+    s
+}
+
+#[test]
+fn test_native_getrf() {
+    let mut rng = Lcg(1);
+    for &(m, n) in &[(1, 1), (5, 5), (8, 3), (3, 8), (20, 20)] {
+        check_getrf(&Native, &rng.matrix(m, n));
+    }
+    // the largest element of the first column is chosen as the pivot
+    let mut a = MatrixN::from_vec(2, 2, vec![1.0, 2.0, -3.0, 4.0]).unwrap();
+    let mut pivots = [0; 2];
+    Native.getrf(a.view_mut(), &mut pivots).unwrap();
+    assert_eq!(pivots, [1, 1]);
+    assert_eq!(a.as_slice(), &[-3.0, 4.0, -1.0 / 3.0, 2.0 + 4.0 / 3.0]);
+}
+
+#[test]
+fn test_native_getrf_singular() {
+    let a = MatrixN::from_vec(3, 3, vec![ 1.0, 2.0, 3.0
+                                        , 2.0, 4.0, 6.0
+                                        , 1.0, 2.0, 4.0 ]).unwrap();
+    let mut lu = a.clone();
+    let mut pivots = [0; 3];
+    assert_eq!( Native.getrf(lu.view_mut(), &mut pivots)
+              , Err(DecompositionError::Singular { index: 1 }));
+    // the decomposition is still complete
+    assert_close(&unpivot(&lu, &pivots), &a);
+}
+
+#[test]
+fn test_native_potrf() {
+    let mut rng = Lcg(2);
+    for &n in &[1, 2, 7, 16] {
+        check_potrf(&Native, &rng.spd(n));
+    }
+    let mut a = MatrixN::from_vec(2, 2, vec![1.0, 2.0, 2.0, 1.0]).unwrap();
+    assert_eq!( Native.potrf(Triangle::Lower, a.view_mut())
+              , Err(DecompositionError::NotPositiveDefinite { index: 1 }));
+}
+
+#[test]
+fn test_native_syev() {
+    let mut rng = Lcg(3);
+    for &n in &[1, 2, 5, 12] {
+        let b = rng.matrix(n, n);
+        let a = MatrixN::from_fn(n, n, |x, y| b[(x, y)] + b[(y, x)]);
+        check_syev(&Native, &a);
+    }
+    let a = MatrixN::from_vec(2, 2, vec![2.0, 1.0, 1.0, 2.0]).unwrap();
+    let w = check_syev(&Native, &a);
+    assert_close( &MatrixN::from_vec(2, 1, w).unwrap()
+                , &MatrixN::from_vec(2, 1, vec![1.0, 3.0]).unwrap());
+}
+
+#[test]
+fn test_native_gesdd() {
+    let mut rng = Lcg(4);
+    for &(m, n) in &[(1, 1), (6, 6), (9, 4), (4, 9), (15, 15)] {
+        check_gesdd(&Native, &rng.matrix(m, n));
+    }
+    // a matrix of rank one has a single nonzero singular value, and for
+    // the zero matrix every column of `U` must be made up
+    let x = rng.matrix(6, 1);
+    let y = rng.matrix(1, 4);
+    let s = check_gesdd(&Native, &mul(&x, &y));
+    assert!(s[1..].iter().all(|&s| s < 1e-12));
+    let s = check_gesdd(&Native, &MatrixN::from_elem(3, 5, 0.0));
+    assert_eq!(s, vec![0.0; 3]);
+}
+
+#[test]
+fn test_native_shape_errors() {
+    let mut a = MatrixN::from_elem(2, 3, 1.0);
+    let mut w = [0.0; 3];
+    assert_eq!( Native.getrf(a.view_mut(), &mut [0; 3])
+              , Err(ShapeError { expected: (2, 1), found: (3, 1) }.into()));
+    assert_eq!( Native.potrf(Triangle::Lower, a.view_mut())
+              , Err(ShapeError { expected: (2, 2), found: (2, 3) }.into()));
+    assert_eq!( Native.syev( Triangle::Lower
+                           , a.view_mut().into_block(0, 0, 2, 2).unwrap()
+                           , &mut w)
+              , Err(ShapeError { expected: (2, 1), found: (3, 1) }.into()));
+    let mut u = MatrixN::from_elem(2, 2, 0.0);
+    let mut vt = MatrixN::from_elem(3, 3, 0.0);
+    assert_eq!( Native.gesdd( a.view_mut(), &mut w[..2]
+                            , u.view_mut(), vt.view_mut())
+              , Err(ShapeError { expected: (2, 3), found: (3, 3) }.into()));
+    assert!(a.iter().all(|&x| x == 1.0));
+}
+
+#[cfg(feature = "system-blas")]
+#[test]
+fn test_system_matches_native() {
+    fn assert_all_close(a: Vec<f64>, b: Vec<f64>) {
+        let n = a.len();
+        assert_close( &MatrixN::from_vec(n, 1, a).unwrap()
+                    , &MatrixN::from_vec(n, 1, b).unwrap());
+    }
+
+    let mut rng = Lcg(5);
+    for &(m, k, n) in &[(1, 1, 1), (5, 3, 9), (64, 70, 33)] {
+        let (a, b) = (rng.matrix(m, k), rng.matrix(k, n));
+        let c = rng.matrix(m, n);
+        let (mut native, mut system) = (c.clone(), c.transpose());
+        Native.gemm(2.0, a.view(), b.view(), 0.5, native.view_mut()).unwrap();
+        System.gemm( 2.0, a.view(), b.view().transpose().transpose(), 0.5
+                   , system.view_mut().transpose()).unwrap();
+        assert_close(&native, &system.transpose());
+    }
+    for &(m, n) in &[(1, 1), (8, 3), (3, 8), (20, 20)] {
+        let a = rng.matrix(m, n);
+        assert_close(&check_getrf(&Native, &a), &check_getrf(&System, &a));
+        assert_all_close(check_gesdd(&Native, &a), check_gesdd(&System, &a));
+    }
+    for &n in &[1, 7, 16] {
+        let a = rng.spd(n);
+        assert_close(&check_potrf(&Native, &a), &check_potrf(&System, &a));
+        assert_all_close(check_syev(&Native, &a), check_syev(&System, &a));
+    }
+}
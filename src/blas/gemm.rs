@@ -0,0 +1,169 @@
+//! General matrix multiplication.
+//!
+//! `gemm` follows the structure of the GotoBLAS and BLIS kernels: the
+//! operands are split into blocks sized to stay resident in cache, each
+//! block is packed into a contiguous buffer of narrow panels, and a
+//! micro-kernel computes an `MR` by `NR` tile of the result in registers
+//! from one panel of each operand.
+
+use super::Scalar;
+use matrix::{MatrixView, MatrixViewMut};
+use error::ShapeError;
+
+use std::cmp::min;
+use std::convert::TryInto;
+
+/// The number of rows of the result computed by each call of the
+/// micro-kernel
+const MR: usize = 4;
+
+/// The number of columns of the result computed by each call of the
+/// micro-kernel
+const NR: usize = 8;
+
+/// The number of rows of `a` packed at a time, so that a packed block of
+/// `a` stays in the L2 cache
+const MC: usize = 128;
+
+/// The number of columns of `a` and rows of `b` packed at a time, so that a
+/// packed panel of `b` stays in the L1 cache
+const KC: usize = 256;
+
+/// The number of columns of `b` packed at a time, so that a packed block of
+/// `b` stays in the L3 cache
+const NC: usize = 4096;
+
+/// Computes `c = alpha * a * b + beta * c`.
+///
+/// When `beta` is zero, `c` is overwritten without being read, so it need
+/// not be initialized to anything in particular (even NaN). Transposed
+/// operands are passed as transposed views.
+///
+/// # Returns:
+///   - `Ok(())` if `a` is `m` by `k`, `b` is `k` by `n` and `c` is `m` by
+///     `n`
+///   - `Err(ShapeError)` describing the first operand with the wrong shape
+///     otherwise, in which case `c` is left unchanged
+///
+pub fn gemm<N>( alpha: N, a: MatrixView<N>, b: MatrixView<N>
+              , beta: N, mut c: MatrixViewMut<N>) -> Result<(), ShapeError>
+where N: Scalar {
+    let (m, k) = a.shape();
+    let n = b.ncols();
+    if b.nrows() != k {
+        return Err(ShapeError { expected: (k, n), found: b.shape() })
+    }
+    if c.shape() != (m, n) {
+        return Err(ShapeError { expected: (m, n), found: c.shape() })
+    }
+
+    scale(beta, c.reborrow());
+    if m == 0 || n == 0 || k == 0 || alpha == N::ZERO { return Ok(()) }
+
+    let mut packed_a = vec![N::ZERO; MC * min(KC, k)];
+    let mut packed_b = vec![N::ZERO; round_up(min(NC, n), NR) * min(KC, k)];
+    for jc in (0..n).step_by(NC) {
+        let nc = min(NC, n - jc);
+        for pc in (0..k).step_by(KC) {
+            let kc = min(KC, k - pc);
+            let b = b.block(pc, jc, kc, nc).expect("block lies within b");
+            pack_b(&mut packed_b, b);
+            for ic in (0..m).step_by(MC) {
+                let mc = min(MC, m - ic);
+                let a = a.block(ic, pc, mc, kc).expect("block lies within a");
+                pack_a(&mut packed_a, a);
+                let c = c.block_mut(ic, jc, mc, nc)
+                         .expect("block lies within c");
+                macro_kernel(alpha, &packed_a, &packed_b, kc, c);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Rounds `n` up to the next multiple of `m`.
+#[inline]
+fn round_up(n: usize, m: usize) -> usize { n.next_multiple_of(m) }
+
+/// Computes `c = beta * c`, treating a `beta` of zero as clearing `c`.
+fn scale<N>(beta: N, mut c: MatrixViewMut<N>)
+where N: Scalar {
+    if beta == N::ONE { return }
+    for y in 0..c.nrows() {
+        for elem in c.row_mut(y).expect("row lies within c").iter_mut() {
+            *elem = if beta == N::ZERO { N::ZERO } else { beta * *elem };
+        }
+    }
+}
+
+/// Packs a block of `a` into panels of `MR` rows, each stored column by
+/// column, padding the last panel with zeroes.
+fn pack_a<N>(packed: &mut [N], a: MatrixView<N>)
+where N: Scalar {
+    let kc = a.ncols();
+    for (p, panel) in packed.chunks_mut(MR * kc)
+                            .take(a.nrows().div_ceil(MR))
+                            .enumerate() {
+        for (x, column) in panel.chunks_exact_mut(MR).enumerate() {
+            for (i, elem) in column.iter_mut().enumerate() {
+                *elem = a.get(x, p * MR + i).cloned().unwrap_or(N::ZERO);
+            }
+        }
+    }
+}
+
+/// Packs a block of `b` into panels of `NR` columns, each stored row by row,
+/// padding the last panel with zeroes.
+fn pack_b<N>(packed: &mut [N], b: MatrixView<N>)
+where N: Scalar {
+    let kc = b.nrows();
+    for (p, panel) in packed.chunks_mut(NR * kc)
+                            .take(b.ncols().div_ceil(NR))
+                            .enumerate() {
+        for (y, row) in panel.chunks_exact_mut(NR).enumerate() {
+            for (j, elem) in row.iter_mut().enumerate() {
+                *elem = b.get(p * NR + j, y).cloned().unwrap_or(N::ZERO);
+            }
+        }
+    }
+}
+
+/// Multiplies a packed block of `a` by a packed block of `b`, adding
+/// `alpha` times the result to `c`.
+fn macro_kernel<N>( alpha: N, packed_a: &[N], packed_b: &[N], kc: usize
+                  , mut c: MatrixViewMut<N>)
+where N: Scalar {
+    let (mc, nc) = c.shape();
+    for jr in (0..nc).step_by(NR) {
+        let b = &packed_b[jr * kc..(jr + NR) * kc];
+        for ir in (0..mc).step_by(MR) {
+            let a = &packed_a[ir * kc..(ir + MR) * kc];
+            let tile = micro_kernel(a, b);
+            for (i, tile_row) in tile.iter().take(mc - ir).enumerate() {
+                let mut row = c.row_mut(ir + i).expect("row lies within c");
+                for (j, &t) in tile_row.iter().take(nc - jr).enumerate() {
+                    row[jr + j] += alpha * t;
+                }
+            }
+        }
+    }
+}
+
+/// Multiplies a panel of `MR` rows of `a` by a panel of `NR` columns of
+/// `b`.
+///
+/// The accumulators are a fixed-size array so that the compiler can keep
+/// them in (vector) registers for the whole loop.
+#[inline]
+fn micro_kernel<N>(a: &[N], b: &[N]) -> [[N; NR]; MR]
+where N: Scalar {
+    let mut tile = [[N::ZERO; NR]; MR];
+    for (a, b) in a.chunks_exact(MR).zip(b.chunks_exact(NR)) {
+        let a: &[N; MR] = a.try_into().expect("chunk of length MR");
+        let b: &[N; NR] = b.try_into().expect("chunk of length NR");
+        for i in 0..MR {
+            for j in 0..NR { tile[i][j] += a[i] * b[j] }
+        }
+    }
+    tile
+}
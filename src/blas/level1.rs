@@ -0,0 +1,106 @@
+//! Vector-vector operations (BLAS level 1).
+//!
+//! Vectors are passed as anything which converts into a `Strided` or
+//! `StridedMut` view, such as a slice, a row or column of a matrix view, or
+//! another strided view.
+
+use super::Scalar;
+use strided::{Strided, StridedMut};
+use error::LengthError;
+#[cfg(any(feature = "std", feature = "libm"))]
+use Real;
+
+/// Returns an error unless `y` has as many elements as `x`.
+#[inline]
+fn check_len(x: usize, y: usize) -> Result<(), LengthError> {
+    if x == y { Ok(()) }
+    else { Err(LengthError::Exact { expected: x, found: y }) }
+}
+
+/// Computes `y = alpha * x + y`.
+///
+/// # Returns:
+///   - `Ok(())` if `x` and `y` have the same length
+///   - `Err(LengthError)` otherwise, in which case `y` is left unchanged
+///
+pub fn axpy<'x, 'y, N, X, Y>(alpha: N, x: X, y: Y) -> Result<(), LengthError>
+where N: Scalar
+    , X: Into<Strided<'x, N>>
+    , Y: Into<StridedMut<'y, N>> {
+    let (x, mut y) = (x.into(), y.into());
+    check_len(x.len(), y.len())?;
+    if alpha == N::ZERO { return Ok(()) }
+    for (y, &x) in y.iter_mut().zip(x.iter()) { *y += alpha * x }
+    Ok(())
+}
+
+/// Computes `x = alpha * x`.
+pub fn scal<'x, N, X>(alpha: N, x: X)
+where N: Scalar
+    , X: Into<StridedMut<'x, N>> {
+    for x in x.into().iter_mut() { *x *= alpha }
+}
+
+/// Returns the dot product of `x` and `y`.
+///
+/// # Returns:
+///   - `Ok(N)` if `x` and `y` have the same length
+///   - `Err(LengthError)` otherwise
+///
+pub fn dot<'x, 'y, N, X, Y>(x: X, y: Y) -> Result<N, LengthError>
+where N: Scalar
+    , X: Into<Strided<'x, N>>
+    , Y: Into<Strided<'y, N>> {
+    let (x, y) = (x.into(), y.into());
+    check_len(x.len(), y.len())?;
+    Ok(x.iter().zip(y.iter()).fold(N::ZERO, |sum, (&x, &y)| sum + x * y))
+}
+
+/// Returns the Euclidean norm of `x`.
+///
+/// The squares of the elements are scaled by the largest magnitude seen so
+/// far as they are summed, so this neither overflows nor underflows unless
+/// the norm itself does.
+#[cfg(any(feature = "std", feature = "libm"))]
+pub fn nrm2<'x, N, X>(x: X) -> N
+where N: Scalar + Real
+    , X: Into<Strided<'x, N>> {
+    let (mut scale, mut ssq) = (N::ZERO, N::ONE);
+    for &x in x.into().iter().filter(|&&x| x != N::ZERO) {
+        let x = Scalar::abs(x);
+        if scale < x {
+            let r = scale / x;
+            ssq = N::ONE + ssq * r * r;
+            scale = x;
+        } else {
+            let r = x / scale;
+            ssq += r * r;
+        }
+    }
+    scale * ssq.sqrt()
+}
+
+/// Returns the sum of the absolute values of the elements of `x`.
+pub fn asum<'x, N, X>(x: X) -> N
+where N: Scalar
+    , X: Into<Strided<'x, N>> {
+    x.into().iter().fold(N::ZERO, |sum, &x| sum + x.abs())
+}
+
+/// Returns the index of the first element of `x` with the largest absolute
+/// value.
+///
+/// # Returns:
+///   - `Some(usize)` if `x` has any elements
+///   - `None` if `x` is empty
+///
+pub fn iamax<'x, N, X>(x: X) -> Option<usize>
+where N: Scalar
+    , X: Into<Strided<'x, N>> {
+    let mut iter = x.into().iter().map(|x| x.abs()).enumerate();
+    let first = iter.next()?;
+    let (i, _) = iter.fold(first, |(i, max), (j, x)| {
+        if x > max { (j, x) } else { (i, max) }
+    });
+    Some(i)
+}
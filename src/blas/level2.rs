@@ -0,0 +1,172 @@
+//! Matrix-vector operations (BLAS level 2).
+//!
+//! A vector of the wrong length is reported as a `ShapeError` describing it
+//! as a column matrix, so a vector of length 3 where 4 elements were needed
+//! has an expected shape of `(4, 1)` and a found shape of `(3, 1)`.
+
+use super::{Scalar, Triangle, Diagonal};
+use matrix::{MatrixView, MatrixViewMut};
+use strided::{Strided, StridedMut};
+use error::ShapeError;
+
+/// Returns an error unless a vector has length `expected`.
+#[inline]
+fn check_len(expected: usize, found: usize) -> Result<(), ShapeError> {
+    if expected == found { Ok(()) }
+    else { Err(ShapeError { expected: (expected, 1), found: (found, 1) }) }
+}
+
+/// Returns an error unless `a` is square.
+#[inline]
+fn check_square<N>(a: &MatrixView<N>) -> Result<usize, ShapeError> {
+    let n = a.nrows();
+    if a.ncols() == n { Ok(n) }
+    else { Err(ShapeError { expected: (n, n), found: a.shape() }) }
+}
+
+/// Computes `y = beta * y`, treating a `beta` of zero as clearing `y`.
+fn scale<N>(beta: N, y: &mut StridedMut<N>)
+where N: Scalar {
+    if beta == N::ONE { return }
+    for y in y.iter_mut() {
+        *y = if beta == N::ZERO { N::ZERO } else { beta * *y };
+    }
+}
+
+/// Computes `y = alpha * a * x + beta * y`.
+///
+/// When `beta` is zero, `y` is overwritten without being read. A transposed
+/// matrix is passed as a transposed view.
+///
+/// # Returns:
+///   - `Ok(())` if `a` is `m` by `n`, `x` has length `n` and `y` has length
+///     `m`
+///   - `Err(ShapeError)` describing the first vector with the wrong length
+///     otherwise, in which case `y` is left unchanged
+///
+pub fn gemv<'x, 'y, N, X, Y>( alpha: N, a: MatrixView<N>, x: X
+                            , beta: N, y: Y) -> Result<(), ShapeError>
+where N: Scalar
+    , X: Into<Strided<'x, N>>
+    , Y: Into<StridedMut<'y, N>> {
+    let (x, mut y) = (x.into(), y.into());
+    let (m, n) = a.shape();
+    check_len(n, x.len())?;
+    check_len(m, y.len())?;
+
+    scale(beta, &mut y);
+    if alpha == N::ZERO { return Ok(()) }
+    if a.row_stride() < a.col_stride() {
+        // the columns of a column-major `a` are contiguous, so add each
+        // column in turn.
+        for j in 0..n {
+            let t = alpha * x[j];
+            for i in 0..m { y[i] += t * a[(j, i)] }
+        }
+    } else {
+        for i in 0..m {
+            let sum = (0..n).fold(N::ZERO, |sum, j| sum + a[(j, i)] * x[j]);
+            y[i] += alpha * sum;
+        }
+    }
+    Ok(())
+}
+
+/// Computes `a = alpha * x * yᵀ + a`.
+///
+/// # Returns:
+///   - `Ok(())` if `a` is `m` by `n`, `x` has length `m` and `y` has length
+///     `n`
+///   - `Err(ShapeError)` describing the first vector with the wrong length
+///     otherwise, in which case `a` is left unchanged
+///
+pub fn ger<'x, 'y, N, X, Y>(alpha: N, x: X, y: Y, mut a: MatrixViewMut<N>)
+                           -> Result<(), ShapeError>
+where N: Scalar
+    , X: Into<Strided<'x, N>>
+    , Y: Into<Strided<'y, N>> {
+    let (x, y) = (x.into(), y.into());
+    let (m, n) = a.shape();
+    check_len(m, x.len())?;
+    check_len(n, y.len())?;
+
+    if alpha == N::ZERO { return Ok(()) }
+    for j in 0..n {
+        let t = alpha * y[j];
+        for i in 0..m { a[(j, i)] += x[i] * t }
+    }
+    Ok(())
+}
+
+/// Solves `a * x = b` in place, where `a` is triangular and `x` initially
+/// holds `b`.
+///
+/// Only the `triangle` of `a`, including the diagonal unless `diagonal` is
+/// `Unit`, is read. The transpose of a triangular matrix is passed as a
+/// transposed view of the opposite triangle. If `a` is singular, `x` will
+/// contain infinities or NaNs, as with any other division by zero.
+///
+/// # Returns:
+///   - `Ok(())` if `a` is `n` by `n` and `x` has length `n`
+///   - `Err(ShapeError)` if `a` is not square or `x` has the wrong length,
+///     in which case `x` is left unchanged
+///
+pub fn trsv<'x, N, X>( triangle: Triangle, diagonal: Diagonal
+                     , a: MatrixView<N>, x: X) -> Result<(), ShapeError>
+where N: Scalar
+    , X: Into<StridedMut<'x, N>> {
+    let mut x = x.into();
+    let n = check_square(&a)?;
+    check_len(n, x.len())?;
+
+    // substitute forward through a lower triangle, or backward through an
+    // upper one, so that each `x[j]` is solved before it is needed.
+    for step in 0..n {
+        let (i, solved) = match triangle {
+            Triangle::Lower => (step, 0..step)
+          , Triangle::Upper => (n - 1 - step, n - step..n)
+        };
+        let sum = solved.fold(N::ZERO, |sum, j| sum + a[(j, i)] * x[j]);
+        let t = x[i] - sum;
+        x[i] = match diagonal { Diagonal::Unit => t
+                              , Diagonal::NonUnit => t / a[(i, i)] };
+    }
+    Ok(())
+}
+
+/// Computes `y = alpha * a * x + beta * y`, where `a` is symmetric.
+///
+/// Only the `triangle` of `a` is read; the other is assumed to be its
+/// mirror image. When `beta` is zero, `y` is overwritten without being
+/// read.
+///
+/// # Returns:
+///   - `Ok(())` if `a` is `n` by `n` and `x` and `y` have length `n`
+///   - `Err(ShapeError)` if `a` is not square or either vector has the
+///     wrong length, in which case `y` is left unchanged
+///
+pub fn symv<'x, 'y, N, X, Y>( triangle: Triangle
+                            , alpha: N, a: MatrixView<N>, x: X
+                            , beta: N, y: Y) -> Result<(), ShapeError>
+where N: Scalar
+    , X: Into<Strided<'x, N>>
+    , Y: Into<StridedMut<'y, N>> {
+    let (x, mut y) = (x.into(), y.into());
+    let n = check_square(&a)?;
+    check_len(n, x.len())?;
+    check_len(n, y.len())?;
+
+    scale(beta, &mut y);
+    if alpha == N::ZERO { return Ok(()) }
+    // the element at row `i` and column `j`, read from `triangle`.
+    let elem = |i: usize, j: usize| match triangle {
+        Triangle::Upper if i > j => a[(i, j)]
+      , Triangle::Lower if i < j => a[(i, j)]
+      , _ => a[(j, i)]
+    };
+    for i in 0..n {
+        let sum = (0..n).fold(N::ZERO, |sum, j| sum + elem(i, j) * x[j]);
+        y[i] += alpha * sum;
+    }
+    Ok(())
+}
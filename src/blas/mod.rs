@@ -0,0 +1,81 @@
+//! Dense linear algebra kernels in the style of BLAS.
+//!
+//! These operate on `MatrixView`s and `MatrixViewMut`s, so they apply
+//! equally to `MatrixN`s, to blocks of larger matrices and to matrices
+//! borrowed from other libraries' storage. Vectors are slices or strided
+//! views, such as the rows and columns of a matrix view. A transposed
+//! operand is passed as a transposed view:
+//!
+//! ```ignore
+//! // c = 2 * aᵀ * b
+//! gemm(2.0, a.view().transpose(), b.view(), 0.0, c.view_mut())?;
+//! // y = a * (first column of b) + y
+//! gemv(1.0, a.view(), b.view().column(0).unwrap(), 1.0, &mut y[..])?;
+//! ```
+
+use std::fmt::Debug;
+use std::ops::{Add, Sub, Mul, Div, Neg};
+use std::ops::{AddAssign, SubAssign, MulAssign};
+
+#[cfg(test)] mod test;
+mod gemm;
+mod level1;
+mod level2;
+
+pub use self::gemm::gemm;
+pub use self::level1::{axpy, scal, dot, asum, iamax};
+#[cfg(any(feature = "std", feature = "libm"))]
+pub use self::level1::nrm2;
+pub use self::level2::{gemv, ger, trsv, symv};
+
+/// A triangle of a square matrix, including its diagonal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Triangle {
+    /// The diagonal and the elements above it
+    Upper,
+    /// The diagonal and the elements below it
+    Lower,
+}
+
+/// The diagonal of a triangular matrix.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Diagonal {
+    /// The diagonal is assumed to be all ones, and is never read
+    Unit,
+    /// The diagonal is read from the matrix
+    NonUnit,
+}
+
+/// A floating-point element type supported by the kernels in this module.
+pub trait Scalar: Copy + Default + Debug + PartialOrd + Send + Sync
+                + Add<Output = Self> + AddAssign
+                + Sub<Output = Self> + SubAssign
+                + Mul<Output = Self> + MulAssign
+                + Div<Output = Self>
+                + Neg<Output = Self>
+                + 'static {
+
+    /// The additive identity
+    const ZERO: Self;
+    /// The multiplicative identity
+    const ONE: Self;
+    /// The difference between one and the next larger representable value
+    const EPSILON: Self;
+
+    /// Returns the absolute value
+    fn abs(self) -> Self;
+}
+
+macro_rules! impl_scalar {
+    ($($t: ty),+) => { $(
+        impl Scalar for $t {
+            const ZERO: $t = 0.0;
+            const ONE: $t = 1.0;
+            const EPSILON: $t = <$t>::EPSILON;
+
+            #[inline] fn abs(self) -> $t { <$t>::abs(self) }
+        }
+    )+ }
+}
+
+impl_scalar! { f32, f64 }
@@ -0,0 +1,254 @@
+use super::*;
+use matrix::{MatrixN, MatrixView, MatrixViewMut};
+use error::{ShapeError, LengthError};
+
+/// A small deterministic generator of elements in `[-1, 1)`.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self) -> f64 {
+        self.0 = self.0.wrapping_mul(6364136223846793005)
+                       .wrapping_add(1442695040888963407);
+        (self.0 >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+    }
+
+    fn matrix(&mut self, nrows: usize, ncols: usize) -> MatrixN<f64> {
+        MatrixN::from_fn(nrows, ncols, |_, _| self.next())
+    }
+}
+
+/// The textbook triple loop, as a reference for `gemm`.
+fn naive_gemm<N>( alpha: N, a: MatrixView<N>, b: MatrixView<N>
+                , beta: N, mut c: MatrixViewMut<N>)
+where N: Scalar {
+    for y in 0..c.nrows() {
+        for x in 0..c.ncols() {
+            let mut sum = N::ZERO;
+            for k in 0..a.ncols() { sum += a[(k, y)] * b[(x, k)] }
+            let prev = if beta == N::ZERO { N::ZERO } else { beta * c[(x, y)] };
+            c[(x, y)] = alpha * sum + prev;
+        }
+    }
+}
+
+fn assert_close<N>(a: &MatrixN<N>, b: &MatrixN<N>, tolerance: N)
+where N: Scalar {
+    assert_eq!(a.shape(), b.shape());
+    for (i, (&x, &y)) in a.iter().zip(b.iter()).enumerate() {
+        assert!( (x - y).abs() <= tolerance
+               , "element {} differs: {:?} != {:?}", i, x, y);
+    }
+}
+
+#[test]
+fn test_gemm_matches_naive_across_blocks() {
+    let mut rng = Lcg(1);
+    // crosses the MC and KC block boundaries and leaves partial tiles in
+    // both directions.
+    for &(m, k, n) in &[(1, 1, 1), (5, 3, 9), (131, 259, 19), (64, 300, 70)] {
+        let a = rng.matrix(m, k);
+        let b = rng.matrix(k, n);
+        let c0 = rng.matrix(m, n);
+        let (mut c, mut expected) = (c0.clone(), c0);
+        gemm(1.5, a.view(), b.view(), -0.5, c.view_mut()).unwrap();
+        naive_gemm(1.5, a.view(), b.view(), -0.5, expected.view_mut());
+        assert_close(&c, &expected, 1e-12);
+    }
+}
+
+#[test]
+fn test_gemm_transposed_operands() {
+    let mut rng = Lcg(2);
+    let a = rng.matrix(37, 23);
+    let b = rng.matrix(41, 37);
+    let mut c = MatrixN::from_elem(23, 41, 0.0);
+    let mut expected = c.clone();
+    gemm( 1.0, a.view().transpose(), b.view().transpose()
+        , 0.0, c.view_mut()).unwrap();
+    naive_gemm( 1.0, a.view().transpose(), b.view().transpose()
+              , 0.0, expected.view_mut());
+    assert_close(&c, &expected, 1e-12);
+
+    // writing the transpose of the product through a transposed view of c
+    let mut ct = MatrixN::from_elem(41, 23, 0.0);
+    gemm( 1.0, a.view().transpose(), b.view().transpose()
+        , 0.0, ct.view_mut().transpose()).unwrap();
+    assert_close(&ct, &expected.transpose(), 1e-12);
+}
+
+#[test]
+fn test_gemm_f32_blocks_of_larger_matrices() {
+    let mut rng = Lcg(3);
+    let a = rng.matrix(20, 30).map(|x| x as f32);
+    let b = rng.matrix(30, 20).map(|x| x as f32);
+    let mut c = MatrixN::from_elem(20, 20, 7.0f32);
+    let mut expected = c.clone();
+    let (a, b) = ( a.view().block(2, 5, 10, 17).unwrap()
+                 , b.view().block(1, 3, 17, 12).unwrap());
+    gemm( 2.0, a, b, 1.0
+        , c.view_mut().into_block(4, 6, 10, 12).unwrap()).unwrap();
+    naive_gemm( 2.0, a, b, 1.0
+              , expected.view_mut().into_block(4, 6, 10, 12).unwrap());
+    assert_close(&c, &expected, 1e-4);
+    assert_eq!(c[(0, 0)], 7.0);
+}
+
+#[test]
+fn test_gemm_zero_beta_overwrites_nan() {
+    let a = MatrixN::from_vec(2, 2, vec![1.0, 2.0, 3.0, 4.0]).unwrap();
+    let mut c = MatrixN::from_elem(2, 2, f64::NAN);
+    gemm(1.0, a.view(), a.view(), 0.0, c.view_mut()).unwrap();
+    assert_eq!(c.as_slice(), &[7.0, 10.0, 15.0, 22.0]);
+    gemm(0.0, a.view(), a.view(), 2.0, c.view_mut()).unwrap();
+    assert_eq!(c.as_slice(), &[14.0, 20.0, 30.0, 44.0]);
+}
+
+#[test]
+fn test_gemm_shape_errors() {
+    let a = MatrixN::from_elem(2, 3, 1.0);
+    let mut c = MatrixN::from_elem(2, 2, 1.0);
+    assert_eq!( gemm(1.0, a.view(), a.view(), 0.0, c.view_mut())
+              , Err(ShapeError { expected: (3, 3), found: (2, 3) }));
+    assert_eq!( gemm(1.0, a.view(), a.view().transpose(), 0.0, c.view_mut())
+              , Ok(()));
+    assert_eq!( gemm(1.0, a.view().transpose(), a.view(), 0.0, c.view_mut())
+              , Err(ShapeError { expected: (3, 3), found: (2, 2) }));
+    assert_eq!(c.as_slice(), &[3.0, 3.0, 3.0, 3.0]);
+}
+
+#[test]
+fn test_level1_on_slices_and_strided_views() {
+    let m = MatrixN::from_vec(3, 2, vec![1.0, -4.0, 2.0, 0.5, -3.0, 4.0])
+                    .unwrap();
+    let x = [1.0, 2.0, 3.0];
+    assert_eq!(dot(&x[..], m.view().column(0).unwrap()), Ok(-4.0));
+    assert_eq!(dot(m.view().row(1).unwrap(), &x[..2]), Ok(3.0));
+    assert_eq!(asum(m.view().column(1).unwrap()), 8.5);
+    assert_eq!(iamax(m.view().column(1).unwrap()), Some(0));
+    assert_eq!(iamax(&[0.0, -2.0, 1.0, 2.0][..]), Some(1));
+    assert_eq!(iamax(&[] as &[f64]), None);
+    assert_eq!(nrm2(&[3.0, -4.0][..]), 5.0);
+    // squaring these would overflow
+    assert!((nrm2(&[0.0, 3e200, 4e200][..]) / 5e200 - 1.0).abs() < 1e-15);
+    assert_eq!(nrm2(&[] as &[f32]), 0.0);
+
+    let mut y = [1.0, 1.0, 1.0];
+    axpy(2.0, m.view().column(0).unwrap(), &mut y[..]).unwrap();
+    assert_eq!(y, [3.0, 5.0, -5.0]);
+    scal(-1.0, &mut y[..]);
+    assert_eq!(y, [-3.0, -5.0, 5.0]);
+}
+
+#[test]
+fn test_level1_length_errors() {
+    let (x, mut y) = ([1.0, 2.0], [1.0; 3]);
+    assert_eq!( dot(&x[..], &y[..])
+              , Err(LengthError::Exact { expected: 2, found: 3 }));
+    assert_eq!( axpy(1.0, &x[..], &mut y[..])
+              , Err(LengthError::Exact { expected: 2, found: 3 }));
+    assert_eq!(y, [1.0; 3]);
+}
+
+#[test]
+fn test_gemv_ger_match_naive() {
+    let mut rng = Lcg(4);
+    let a = rng.matrix(7, 5);
+    let (x, y0) = (rng.matrix(5, 1), rng.matrix(7, 1));
+    // a column-major copy of `a`, to take the column-wise path
+    let at = a.transpose();
+    let mut expected = y0.clone();
+    naive_gemm(2.0, a.view(), x.view(), 0.5, expected.view_mut());
+    for a in &[a.view(), at.view().transpose()] {
+        let mut y = y0.clone();
+        gemv(2.0, *a, x.as_slice(), 0.5, y.as_mut_slice()).unwrap();
+        assert_close(&y, &expected, 1e-12);
+    }
+
+    let mut expected = a.clone();
+    naive_gemm(3.0, y0.view(), x.view().transpose(), 1.0, expected.view_mut());
+    let mut b = a.clone();
+    ger(3.0, y0.as_slice(), x.as_slice(), b.view_mut()).unwrap();
+    assert_close(&b, &expected, 1e-12);
+
+    let mut y = MatrixN::from_elem(5, 1, f64::NAN);
+    gemv(1.0, a.view().transpose(), y0.as_slice(), 0.0, y.as_mut_slice())
+        .unwrap();
+    let mut expected = MatrixN::from_elem(5, 1, 0.0);
+    naive_gemm(1.0, a.view().transpose(), y0.view(), 0.0, expected.view_mut());
+    assert_close(&y, &expected, 1e-12);
+}
+
+#[test]
+fn test_trsv_solves_triangular_systems() {
+    let mut rng = Lcg(5);
+    let n = 6;
+    // well-conditioned, with a dominant diagonal and garbage in the
+    // triangle that should never be read
+    let a = MatrixN::from_fn(n, n, |x, y| {
+        if x == y { 4.0 + x as f64 } else { rng.next() }
+    });
+    let b = rng.matrix(n, 1);
+    let cases = [ (Triangle::Lower, Diagonal::NonUnit)
+                , (Triangle::Upper, Diagonal::NonUnit)
+                , (Triangle::Lower, Diagonal::Unit)
+                , (Triangle::Upper, Diagonal::Unit) ];
+    for &(triangle, diagonal) in &cases {
+        for a in &[a.view(), a.view().transpose()] {
+            let t = MatrixN::from_fn(n, n, |x, y| match (triangle, diagonal) {
+                (_, Diagonal::Unit) if x == y => 1.0
+              , (Triangle::Lower, _) if x > y => 0.0
+              , (Triangle::Upper, _) if x < y => 0.0
+              , _ => a[(x, y)]
+            });
+            let mut x = b.clone();
+            trsv(triangle, diagonal, *a, x.as_mut_slice()).unwrap();
+            let mut tx = MatrixN::from_elem(n, 1, 0.0);
+            naive_gemm(1.0, t.view(), x.view(), 0.0, tx.view_mut());
+            assert_close(&tx, &b, 1e-12);
+        }
+    }
+}
+
+#[test]
+fn test_symv_reads_one_triangle() {
+    let mut rng = Lcg(6);
+    let n = 5;
+    let s = rng.matrix(n, n);
+    let full = MatrixN::from_fn(n, n, |x, y| s[(x.max(y), x.min(y))]);
+    let (x, y0) = (rng.matrix(n, 1), rng.matrix(n, 1));
+    let mut expected = y0.clone();
+    naive_gemm(1.5, full.view(), x.view(), -1.0, expected.view_mut());
+
+    let mut y = y0.clone();
+    symv( Triangle::Upper, 1.5, full.view().transpose(), x.as_slice()
+        , -1.0, y.as_mut_slice()).unwrap();
+    assert_close(&y, &expected, 1e-12);
+    let upper = MatrixN::from_fn(n, n, |x, y| {
+        if x >= y { full[(x, y)] } else { f64::NAN }
+    });
+    let mut y = y0.clone();
+    symv( Triangle::Upper, 1.5, upper.view(), x.as_slice()
+        , -1.0, y.as_mut_slice()).unwrap();
+    assert_close(&y, &expected, 1e-12);
+    let mut y = y0.clone();
+    symv( Triangle::Lower, 1.5, upper.view().transpose(), x.as_slice()
+        , -1.0, y.as_mut_slice()).unwrap();
+    assert_close(&y, &expected, 1e-12);
+}
+
+#[test]
+fn test_level2_shape_errors() {
+    let a = MatrixN::from_elem(2, 3, 1.0);
+    let (x, mut y) = ([1.0; 3], [1.0; 3]);
+    assert_eq!( gemv(1.0, a.view(), &x[..], 0.0, &mut y[..])
+              , Err(ShapeError { expected: (2, 1), found: (3, 1) }));
+    assert_eq!( gemv(1.0, a.view(), &x[..2], 0.0, &mut y[..2])
+              , Err(ShapeError { expected: (3, 1), found: (2, 1) }));
+    assert_eq!( trsv( Triangle::Lower, Diagonal::Unit, a.view()
+                    , &mut y[..])
+              , Err(ShapeError { expected: (2, 2), found: (2, 3) }));
+    assert_eq!( symv( Triangle::Lower, 1.0, a.view().block(0, 0, 2, 2).unwrap()
+                    , &x[..], 0.0, &mut y[..2])
+              , Err(ShapeError { expected: (2, 1), found: (3, 1) }));
+    assert_eq!(y, [1.0; 3]);
+}
@@ -0,0 +1,123 @@
+//! Conversions between numeric element types.
+//!
+//! `LossyCast` converts between any two primitive numeric types with the
+//! semantics of an `as` expression, while `CheckedCast` only succeeds if
+//! the value lies within the range of the target type. Note that
+//! `CheckedCast` from a floating-point type to an integer type truncates
+//! any fractional part, just as `as` does; only values whose integer part
+//! is out of range (or which are NaN) are rejected.
+
+use std::convert::TryFrom;
+
+#[cfg(test)] mod test;
+
+/// Conversion to `T` using the semantics of `as`.
+pub trait LossyCast<T>: Copy {
+    fn lossy_cast(self) -> T;
+}
+
+/// Range-checked conversion to `T`.
+pub trait CheckedCast<T>: Copy {
+
+    /// Convert `self` to a `T`.
+    ///
+    /// # Returns:
+    ///   - `Some(T)` if `self` lies within the range of `T`
+    ///   - `None` if `self` is out of range for `T`, or is NaN and `T` is an
+    ///     integer type
+    ///
+    fn checked_cast(self) -> Option<T>;
+}
+
+macro_rules! impl_lossy_cast {
+    (@from $from: ty; $($to: ty),+) => { $(
+        impl LossyCast<$to> for $from {
+            #[inline] fn lossy_cast(self) -> $to { self as $to }
+        }
+    )+ };
+    ($($from: ty),+) => { $(
+        impl_lossy_cast! { @from $from; u8, u16, u32, u64, usize
+                                      , i8, i16, i32, i64, isize
+                                      , f32, f64
+                                      }
+    )+ };
+}
+
+impl_lossy_cast! { u8, u16, u32, u64, usize
+                 , i8, i16, i32, i64, isize
+                 , f32, f64
+                 }
+
+macro_rules! impl_int_checked_cast {
+    (@from $from: ty; $($to: ty),+) => { $(
+        impl CheckedCast<$to> for $from {
+            #[inline] fn checked_cast(self) -> Option<$to> {
+                <$to>::try_from(self).ok()
+            }
+        }
+    )+ };
+    ($($from: ty),+) => { $(
+        impl_int_checked_cast! { @from $from; u8, u16, u32, u64, usize
+                                            , i8, i16, i32, i64, isize
+                                            }
+        // every integer is within the range of both float types.
+        impl CheckedCast<f32> for $from {
+            #[inline] fn checked_cast(self) -> Option<f32> {
+                Some(self as f32)
+            }
+        }
+        impl CheckedCast<f64> for $from {
+            #[inline] fn checked_cast(self) -> Option<f64> {
+                Some(self as f64)
+            }
+        }
+    )+ };
+}
+
+impl_int_checked_cast! { u8, u16, u32, u64, usize
+                       , i8, i16, i32, i64, isize
+                       }
+
+macro_rules! impl_float_checked_cast {
+    (@signed $from: ty; $($to: ty),+) => { $(
+        impl CheckedCast<$to> for $from {
+            #[inline] fn checked_cast(self) -> Option<$to> {
+                // both bounds are powers of two, and so are represented
+                // exactly. NaN fails both comparisons.
+                let lower = <$to>::MIN as $from;
+                let upper = -lower;
+                if self >= lower && self < upper { Some(self as $to) }
+                else { None }
+            }
+        }
+    )+ };
+    (@unsigned $from: ty; $($to: ty),+) => { $(
+        impl CheckedCast<$to> for $from {
+            #[inline] fn checked_cast(self) -> Option<$to> {
+                let upper = (<$to>::MAX / 2 + 1) as $from * 2.0;
+                if self > -1.0 && self < upper { Some(self as $to) }
+                else { None }
+            }
+        }
+    )+ };
+    ($($from: ty),+) => { $(
+        impl_float_checked_cast! { @signed $from; i8, i16, i32, i64, isize }
+        impl_float_checked_cast! { @unsigned $from; u8, u16, u32, u64, usize }
+        impl CheckedCast<f64> for $from {
+            #[inline] fn checked_cast(self) -> Option<f64> {
+                Some(self as f64)
+            }
+        }
+        impl CheckedCast<f32> for $from {
+            #[inline] fn checked_cast(self) -> Option<f32> {
+                if self.is_finite() && self.abs() > f32::MAX as $from {
+                    None
+                } else {
+                    Some(self as f32)
+                }
+            }
+        }
+    )+ };
+}
+
+impl_float_checked_cast! { f32, f64 }
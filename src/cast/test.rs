@@ -0,0 +1,53 @@
+use super::*;
+use vector::{Vector2, Vector3};
+use matrix::Matrix2;
+
+#[test]
+fn test_int_checked_cast() {
+    assert_eq!(CheckedCast::<u8>::checked_cast(255i32), Some(255u8));
+    assert_eq!(CheckedCast::<u8>::checked_cast(256i32), None);
+    assert_eq!(CheckedCast::<u32>::checked_cast(-1i8), None);
+    assert_eq!(CheckedCast::<i8>::checked_cast(-128i64), Some(-128i8))
+}
+
+#[test]
+fn test_float_to_int_checked_cast() {
+    assert_eq!(CheckedCast::<u8>::checked_cast(255.9f32), Some(255u8));
+    assert_eq!(CheckedCast::<u8>::checked_cast(256.0f32), None);
+    assert_eq!(CheckedCast::<u8>::checked_cast(-0.5f32), Some(0u8));
+    assert_eq!(CheckedCast::<u8>::checked_cast(-1.0f32), None);
+    assert_eq!( CheckedCast::<i32>::checked_cast(-2147483648.0f64)
+              , Some(i32::MIN));
+    assert_eq!(CheckedCast::<i32>::checked_cast(2147483648.0f64), None);
+    assert_eq!(CheckedCast::<u64>::checked_cast(f64::NAN), None);
+    assert_eq!(CheckedCast::<i64>::checked_cast(f32::INFINITY), None)
+}
+
+#[test]
+fn test_float_to_float_checked_cast() {
+    assert_eq!(CheckedCast::<f32>::checked_cast(1.0e39f64), None);
+    assert_eq!(CheckedCast::<f32>::checked_cast(1.5f64), Some(1.5f32));
+    assert_eq!( CheckedCast::<f32>::checked_cast(f64::INFINITY)
+              , Some(f32::INFINITY))
+}
+
+#[test]
+fn test_vector_cast() {
+    let v = Vector3 { x: 1.7f32, y: -2.2, z: 300.0 };
+    assert_eq!(v.cast::<i32>(), Vector3 { x: 1, y: -2, z: 300 });
+    assert_eq!(v.cast::<u8>(), Vector3 { x: 1, y: 0, z: 255 });
+    assert_eq!(v.try_cast::<u8>(), None);
+    assert_eq!(v.try_cast::<i16>(), Some(Vector3 { x: 1, y: -2, z: 300 }))
+}
+
+#[test]
+fn test_widening_from() {
+    let v: Vector3<f64> = Vector3 { x: 0.5f32, y: 1.0, z: 2.0 }.into();
+    assert_eq!(v, Vector3 { x: 0.5f64, y: 1.0, z: 2.0 });
+    let w = Vector2::<i32>::from(Vector2 { x: -3i16, y: 4 });
+    assert_eq!(w, Vector2 { x: -3i32, y: 4 });
+    let m = Matrix2::<f64>::from(Matrix2 { x1y1: 1u8, x2y1: 2
+                                         , x1y2: 3, x2y2: 4
+                                         });
+    assert_eq!(m.x2y2, 4.0)
+}
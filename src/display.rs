@@ -0,0 +1,90 @@
+//! Helpers for the `Display` implementations of the matrix types.
+//!
+//! These measure each element by formatting it into a counter rather than a
+//! `String`, so that matrices can be displayed without an allocator.
+
+use std::fmt::{self, Write};
+
+/// Counts the characters written to it.
+struct Counter(usize);
+
+impl Write for Counter {
+    #[inline] fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.chars().count();
+        Ok(())
+    }
+}
+
+/// The flags of a formatter which apply to each element individually.
+#[derive(Clone, Copy)]
+struct Spec { precision: Option<usize>
+            , sign_plus: bool
+            }
+
+/// Writes a single element, respecting the precision and sign flags of
+/// `spec`.
+fn write_elem<W, N>(w: &mut W, elem: &N, spec: Spec) -> fmt::Result
+where W: Write
+    , N: fmt::Display {
+    match (spec.precision, spec.sign_plus) {
+        (Some(p), true) => write!(w, "{:+.*}", p, elem)
+      , (Some(p), false) => write!(w, "{:.*}", p, elem)
+      , (None, true) => write!(w, "{:+}", elem)
+      , (None, false) => write!(w, "{}", elem)
+    }
+}
+
+/// Returns the number of characters `elem` occupies when written with `spec`.
+#[inline]
+fn elem_width<N>(elem: &N, spec: Spec) -> Result<usize, fmt::Error>
+where N: fmt::Display {
+    let mut counter = Counter(0);
+    write_elem(&mut counter, elem, spec)?;
+    Ok(counter.0)
+}
+
+#[inline]
+fn write_spaces(f: &mut fmt::Formatter, n: usize) -> fmt::Result {
+    for _ in 0..n { f.write_char(' ')? }
+    Ok(())
+}
+
+/// Writes the rows of a matrix on separate lines, with each column padded
+/// to the same width.
+///
+/// `widths` is scratch space for the width of each column, and must hold at
+/// least `ncols` elements.
+#[doc(hidden)]
+pub fn write_matrix<N>( f: &mut fmt::Formatter, elems: &[N]
+                      , nrows: usize, ncols: usize, widths: &mut [usize])
+                      -> fmt::Result
+where N: fmt::Display {
+    let spec = Spec { precision: f.precision(), sign_plus: f.sign_plus() };
+    let min_width = f.width().unwrap_or(0);
+    for x in 0..ncols {
+        widths[x] = min_width;
+        for y in 0..nrows {
+            let width = elem_width(&elems[x + y * ncols], spec)?;
+            widths[x] = ::std::cmp::max(widths[x], width);
+        }
+    }
+    for y in 0..nrows {
+        if y > 0 { f.write_str("\n")? }
+        f.write_str("[")?;
+        for x in 0..ncols {
+            if x > 0 { f.write_str(" ")? }
+            let elem = &elems[x + y * ncols];
+            let pad = widths[x] - elem_width(elem, spec)?;
+            let (before, after) = match f.align() {
+                Some(fmt::Alignment::Left) => (0, pad)
+              , Some(fmt::Alignment::Center) => (pad / 2, pad - pad / 2)
+              , _ => (pad, 0)
+            };
+            write_spaces(f, before)?;
+            write_elem(f, elem, spec)?;
+            write_spaces(f, after)?;
+        }
+        f.write_str("]")?;
+    }
+    Ok(())
+}
@@ -0,0 +1,86 @@
+//! Error types shared across the crate.
+
+use std::error::Error;
+use std::fmt;
+
+/// An error indicating that a slice had the wrong length for a conversion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LengthError {
+    /// The slice should have contained exactly `expected` elements
+    Exact { expected: usize, found: usize },
+    /// The slice should have contained a multiple of `of` elements
+    Multiple { of: usize, found: usize },
+}
+
+impl fmt::Display for LengthError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LengthError::Exact { expected, found } =>
+                write!( f, "expected a slice of length {}, found length {}"
+                      , expected, found)
+          , LengthError::Multiple { of, found } =>
+                write!( f, "expected a slice whose length is a multiple of \
+                            {}, found length {}"
+                      , of, found)
+        }
+    }
+}
+
+impl Error for LengthError {}
+
+/// An error indicating that a matrix had the wrong number of rows or columns
+/// for an operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShapeError {
+    /// The expected shape, as `(rows, columns)`
+    pub expected: (usize, usize),
+    /// The shape which was found, as `(rows, columns)`
+    pub found: (usize, usize),
+}
+
+impl fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!( f, "expected a {}x{} matrix, found a {}x{} matrix"
+              , self.expected.0, self.expected.1
+              , self.found.0, self.found.1)
+    }
+}
+
+impl Error for ShapeError {}
+
+/// An error indicating that a matrix decomposition could not be computed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecompositionError {
+    /// An operand had the wrong shape
+    Shape(ShapeError),
+    /// The matrix is singular: the `index`th diagonal element of its
+    /// triangular factor is exactly zero
+    Singular { index: usize },
+    /// The matrix is not positive definite: its leading minor of order
+    /// `index + 1` is not positive
+    NotPositiveDefinite { index: usize },
+    /// An iterative algorithm did not converge
+    NoConvergence,
+}
+
+impl From<ShapeError> for DecompositionError {
+    #[inline] fn from(e: ShapeError) -> Self { DecompositionError::Shape(e) }
+}
+
+impl fmt::Display for DecompositionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DecompositionError::Shape(ref e) => e.fmt(f)
+          , DecompositionError::Singular { index } =>
+                write!(f, "matrix is singular: pivot {} is zero", index)
+          , DecompositionError::NotPositiveDefinite { index } =>
+                write!( f, "matrix is not positive definite: leading minor \
+                            of order {} is not positive"
+                      , index + 1)
+          , DecompositionError::NoConvergence =>
+                write!(f, "decomposition did not converge")
+        }
+    }
+}
+
+impl Error for DecompositionError {}
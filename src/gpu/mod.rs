@@ -0,0 +1,368 @@
+//! Encoding vectors and matrices for GPU buffers.
+//!
+//! GLSL and WGSL lay out uniform and storage buffers according to the
+//! `std140` and `std430` rules, which differ from the `repr(C)` layout of
+//! this crate's types: a `vec3` is aligned to 16 bytes, matrices are stored
+//! as arrays of column vectors, and under `std140` every matrix column and
+//! array element is padded to 16 bytes.
+//!
+//! `write_std140` and `write_std430` encode a single value, while
+//! `BlockWriter` lays out a whole block member by member:
+//!
+//! ```ignore
+//! // layout(std140) uniform Block { mat4 mvp; vec3 light; float power; };
+//! let mut block = BlockWriter::new(Layout::Std140);
+//! block.write(&mvp);
+//! block.write(&light);
+//! block.write(&power);  // packed into the padding after `light`
+//! let bytes = block.into_bytes();
+//! ```
+//!
+//! The `Padded*` types are `repr(C)` types whose layout matches the GPU's
+//! for 32-bit scalars, for use with APIs which copy structs directly.
+//!
+//! All values are encoded little-endian. `BlockWriter` and the `write_*`
+//! functions require the `alloc` feature; without it, values may still be
+//! encoded into a caller's buffer with `GpuType::encode`.
+
+use vector::{Vector2, Vector3, Vector4};
+use matrix::{Matrix2, Matrix3, Matrix4};
+use Columnar;
+use pod::Pod;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use std::convert;
+
+#[cfg(test)] mod test;
+
+/// The rules used to lay out a buffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layout {
+    /// The layout of uniform buffers
+    Std140,
+    /// The layout of storage buffers, which omits `std140`'s padding of
+    /// arrays and matrix columns to 16 bytes
+    Std430,
+}
+
+/// Rounds `offset` up to the next multiple of `align`.
+#[inline]
+fn round_up(offset: usize, align: usize) -> usize {
+    offset.next_multiple_of(align)
+}
+
+impl Layout {
+
+    /// Returns the alignment of an array or structure whose elements or
+    /// members have alignment `align`
+    #[inline]
+    pub fn aggregate_align(self, align: usize) -> usize {
+        match self {
+            Layout::Std140 => round_up(align, 16)
+          , Layout::Std430 => align
+        }
+    }
+
+    /// Returns the distance between consecutive elements of an array of `T`
+    #[inline]
+    pub fn array_stride<T>(self) -> usize
+    where T: GpuType {
+        round_up(T::size(self), self.aggregate_align(T::align(self)))
+    }
+}
+
+/// A scalar type which may be stored in a GPU buffer.
+pub trait Scalar: Copy {
+    /// The size of this type in bytes
+    const SIZE: usize;
+
+    /// Write the little-endian bytes of this value to the start of `out`
+    fn put(self, out: &mut [u8]);
+}
+
+/// A scalar type four bytes wide, for which the `Padded*` types have the
+/// same layout as on the GPU.
+pub trait Scalar32: Scalar {}
+
+macro_rules! impl_scalar {
+    ($($t: ty),+) => { $(
+        impl Scalar for $t {
+            const SIZE: usize = ::std::mem::size_of::<$t>();
+
+            #[inline] fn put(self, out: &mut [u8]) {
+                out[..Self::SIZE].copy_from_slice(&self.to_le_bytes())
+            }
+        }
+    )+ };
+}
+
+impl_scalar! { f32, f64, i32, u32 }
+impl Scalar32 for f32 {}
+impl Scalar32 for i32 {}
+impl Scalar32 for u32 {}
+
+/// A type which may be stored in a GPU buffer.
+pub trait GpuType {
+
+    /// Returns the base alignment of this type in bytes
+    fn align(layout: Layout) -> usize;
+
+    /// Returns the size of this type in bytes, not including any padding
+    /// which may follow it
+    fn size(layout: Layout) -> usize;
+
+    /// Encode this value into the start of `out`, which must be at least
+    /// `size(layout)` bytes long
+    fn encode(&self, layout: Layout, out: &mut [u8]);
+}
+
+impl<N> GpuType for N
+where N: Scalar {
+    #[inline] fn align(_: Layout) -> usize { N::SIZE }
+    #[inline] fn size(_: Layout) -> usize { N::SIZE }
+    #[inline] fn encode(&self, _: Layout, out: &mut [u8]) { self.put(out) }
+}
+
+macro_rules! impl_gpu_vector {
+    ($($v: ident, $c: expr, $align: expr);+) => { $(
+        impl<N> GpuType for $v<N>
+        where N: Scalar {
+            #[inline] fn align(_: Layout) -> usize { $align * N::SIZE }
+            #[inline] fn size(_: Layout) -> usize { $c * N::SIZE }
+
+            #[inline] fn encode(&self, _: Layout, out: &mut [u8]) {
+                for (i, &elem) in self.as_elems().iter().enumerate() {
+                    elem.put(&mut out[i * N::SIZE..]);
+                }
+            }
+        }
+    )+ };
+}
+
+// a three-element vector is aligned as if it had four elements.
+impl_gpu_vector! { Vector2, 2, 2; Vector3, 3, 4; Vector4, 4, 4 }
+
+macro_rules! impl_gpu_matrix {
+    ($($m: ident, $c: expr, $col: ident);+) => { $(
+        // matrices are stored as arrays of their columns.
+        impl<N> GpuType for $m<N>
+        where N: Scalar {
+            #[inline] fn align(layout: Layout) -> usize {
+                layout.aggregate_align($col::<N>::align(layout))
+            }
+            #[inline] fn size(layout: Layout) -> usize {
+                $c * layout.array_stride::<$col<N>>()
+            }
+
+            fn encode(&self, layout: Layout, out: &mut [u8]) {
+                let stride = layout.array_stride::<$col<N>>();
+                for i in 0..$c {
+                    let column = self.column_owned(i)
+                                     .expect("column index is in bounds");
+                    column.encode(layout, &mut out[i * stride..]);
+                }
+            }
+        }
+    )+ };
+}
+
+impl_gpu_matrix! { Matrix2, 2, Vector2
+                 ; Matrix3, 3, Vector3
+                 ; Matrix4, 4, Vector4 }
+
+impl<T, const K: usize> GpuType for [T; K]
+where T: GpuType {
+    #[inline] fn align(layout: Layout) -> usize {
+        layout.aggregate_align(T::align(layout))
+    }
+    #[inline] fn size(layout: Layout) -> usize {
+        K * layout.array_stride::<T>()
+    }
+
+    fn encode(&self, layout: Layout, out: &mut [u8]) {
+        let stride = layout.array_stride::<T>();
+        for (i, elem) in self.iter().enumerate() {
+            elem.encode(layout, &mut out[i * stride..]);
+        }
+    }
+}
+
+/// Lays out the members of a buffer block, or of a structure within one.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub struct BlockWriter { layout: Layout
+                       , bytes: Vec<u8>
+                       , align: usize
+                       }
+
+#[cfg(feature = "alloc")]
+impl BlockWriter {
+
+    /// Begin writing an empty block.
+    #[inline]
+    pub fn new(layout: Layout) -> Self {
+        BlockWriter { layout, bytes: Vec::new(), align: 1 }
+    }
+
+    /// Returns the offset at which the next member would begin if it had
+    /// alignment 1
+    #[inline] pub fn len(&self) -> usize { self.bytes.len() }
+
+    /// Returns true if nothing has been written yet
+    #[inline] pub fn is_empty(&self) -> bool { self.bytes.is_empty() }
+
+    /// Pads the block to `align` and reserves `size` bytes, returning their
+    /// offset.
+    fn reserve(&mut self, align: usize, size: usize) -> usize {
+        let offset = round_up(self.bytes.len(), align);
+        self.bytes.resize(offset + size, 0);
+        self.align = ::std::cmp::max(self.align, align);
+        offset
+    }
+
+    /// Append a member, returning its offset.
+    pub fn write<T>(&mut self, value: &T) -> usize
+    where T: GpuType {
+        let offset = self.reserve(T::align(self.layout), T::size(self.layout));
+        value.encode(self.layout, &mut self.bytes[offset..]);
+        offset
+    }
+
+    /// Append an array member, returning its offset.
+    pub fn write_array<T>(&mut self, values: &[T]) -> usize
+    where T: GpuType {
+        let stride = self.layout.array_stride::<T>();
+        let align = self.layout.aggregate_align(T::align(self.layout));
+        let offset = self.reserve(align, values.len() * stride);
+        for (i, value) in values.iter().enumerate() {
+            value.encode(self.layout, &mut self.bytes[offset + i * stride..]);
+        }
+        offset
+    }
+
+    /// Append a structure member whose own members are written by `f`,
+    /// returning its offset.
+    pub fn write_struct<F>(&mut self, f: F) -> usize
+    where F: FnOnce(&mut BlockWriter) {
+        let mut inner = BlockWriter::new(self.layout);
+        f(&mut inner);
+        let align = self.layout.aggregate_align(inner.align);
+        let bytes = inner.into_bytes();
+        let offset = self.reserve(align, bytes.len());
+        self.bytes[offset..offset + bytes.len()].copy_from_slice(&bytes);
+        offset
+    }
+
+    /// Returns the encoded block, padded to a multiple of its alignment.
+    pub fn into_bytes(mut self) -> Vec<u8> {
+        let align = self.layout.aggregate_align(self.align);
+        let len = round_up(self.bytes.len(), align);
+        self.bytes.resize(len, 0);
+        self.bytes
+    }
+}
+
+/// Encode a value as a `std140` uniform block.
+#[cfg(feature = "alloc")]
+pub fn write_std140<T>(value: &T) -> Vec<u8>
+where T: GpuType {
+    let mut block = BlockWriter::new(Layout::Std140);
+    block.write(value);
+    block.into_bytes()
+}
+
+/// Encode a value as a `std430` storage block.
+#[cfg(feature = "alloc")]
+pub fn write_std430<T>(value: &T) -> Vec<u8>
+where T: GpuType {
+    let mut block = BlockWriter::new(Layout::Std430);
+    block.write(value);
+    block.into_bytes()
+}
+
+/// A two-element vector padded to 16 bytes, laid out like a column of a
+/// `std140` `mat2`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C, align(16))]
+pub struct PaddedVector2<N> { pub x: N
+                            , pub y: N
+                            , _pad: [N; 2]
+                            }
+
+/// A three-element vector padded to 16 bytes, laid out like a `vec3` array
+/// element or matrix column under both layouts.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C, align(16))]
+pub struct PaddedVector3<N> { pub x: N
+                            , pub y: N
+                            , pub z: N
+                            , _pad: N
+                            }
+
+/// A 2x2 matrix laid out like a `std140` `mat2`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct PaddedMatrix2<N> { pub columns: [PaddedVector2<N>; 2] }
+
+/// A 3x3 matrix laid out like a `mat3` under both layouts.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct PaddedMatrix3<N> { pub columns: [PaddedVector3<N>; 3] }
+
+/// A 4x4 matrix laid out like a `mat4` under both layouts.
+///
+/// No padding is needed, but unlike `Matrix4` the elements are stored in
+/// column-major order.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct PaddedMatrix4<N> { pub columns: [Vector4<N>; 4] }
+
+impl<N> convert::From<Vector2<N>> for PaddedVector2<N>
+where N: Scalar32 + Default {
+    #[inline] fn from(v: Vector2<N>) -> Self {
+        PaddedVector2 { x: v.x, y: v.y, _pad: [N::default(); 2] }
+    }
+}
+
+impl<N> convert::From<Vector3<N>> for PaddedVector3<N>
+where N: Scalar32 + Default {
+    #[inline] fn from(v: Vector3<N>) -> Self {
+        PaddedVector3 { x: v.x, y: v.y, z: v.z, _pad: N::default() }
+    }
+}
+
+// with four-byte elements, the explicit padding fields fill the space which
+// alignment would otherwise leave as padding bytes.
+unsafe impl<N> Pod for PaddedVector2<N>
+where N: Pod + Scalar32 {}
+unsafe impl<N> Pod for PaddedVector3<N>
+where N: Pod + Scalar32 {}
+unsafe impl<N> Pod for PaddedMatrix2<N>
+where N: Pod + Scalar32 {}
+unsafe impl<N> Pod for PaddedMatrix3<N>
+where N: Pod + Scalar32 {}
+unsafe impl<N> Pod for PaddedMatrix4<N>
+where N: Pod + Scalar32 {}
+
+macro_rules! impl_padded_matrix {
+    ($($m: ident, $padded: ident, $c: expr);+) => { $(
+        impl<N> convert::From<$m<N>> for $padded<N>
+        where N: Scalar32 + Default {
+            #[inline] fn from(m: $m<N>) -> Self {
+                let mut columns = [Default::default(); $c];
+                for (i, column) in columns.iter_mut().enumerate() {
+                    *column = m.column_owned(i)
+                               .expect("column index is in bounds")
+                               .into();
+                }
+                $padded { columns }
+            }
+        }
+    )+ };
+}
+
+impl_padded_matrix! { Matrix2, PaddedMatrix2, 2
+                    ; Matrix3, PaddedMatrix3, 3
+                    ; Matrix4, PaddedMatrix4, 4 }
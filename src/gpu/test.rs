@@ -0,0 +1,118 @@
+use super::*;
+
+use std::mem;
+
+fn f32_at(bytes: &[u8], offset: usize) -> f32 {
+    let mut b = [0; 4];
+    b.copy_from_slice(&bytes[offset..offset + 4]);
+    f32::from_le_bytes(b)
+}
+
+fn floats(bytes: &[u8]) -> Vec<f32> {
+    (0..bytes.len() / 4).map(|i| f32_at(bytes, i * 4)).collect()
+}
+
+fn m3() -> Matrix3<f32> {
+    Matrix3 { x1y1: 1.0, x2y1: 2.0, x3y1: 3.0
+            , x1y2: 4.0, x2y2: 5.0, x3y2: 6.0
+            , x1y3: 7.0, x2y3: 8.0, x3y3: 9.0 }
+}
+
+#[test]
+fn test_vector_alignment() {
+    assert_eq!(Vector2::<f32>::align(Layout::Std140), 8);
+    assert_eq!(Vector3::<f32>::align(Layout::Std430), 16);
+    assert_eq!(Vector3::<f32>::size(Layout::Std430), 12);
+    assert_eq!(Vector3::<f64>::align(Layout::Std140), 32);
+    assert_eq!(Layout::Std140.array_stride::<f32>(), 16);
+    assert_eq!(Layout::Std430.array_stride::<f32>(), 4);
+    assert_eq!(Layout::Std430.array_stride::<Vector3<f32>>(), 16);
+}
+
+#[test]
+fn test_mat3_columns() {
+    let expected = [ 1.0, 4.0, 7.0, 0.0
+                   , 2.0, 5.0, 8.0, 0.0
+                   , 3.0, 6.0, 9.0, 0.0 ];
+    assert_eq!(floats(&write_std140(&m3())), expected);
+    assert_eq!(floats(&write_std430(&m3())), expected);
+}
+
+#[test]
+fn test_mat2_std140_padding() {
+    let m = Matrix2 { x1y1: 1.0f32, x2y1: 2.0
+                    , x1y2: 3.0, x2y2: 4.0 };
+    assert_eq!( floats(&write_std140(&m))
+              , [1.0, 3.0, 0.0, 0.0, 2.0, 4.0, 0.0, 0.0]);
+    assert_eq!(floats(&write_std430(&m)), [1.0, 3.0, 2.0, 4.0]);
+}
+
+/// Lays out the block
+///
+/// ```glsl
+/// struct Light { vec3 position; float power; };
+/// layout(...) Block { float a; vec3 b; float c; mat3 m;
+///                     vec2 arr[2]; Light light; float d; };
+/// ```
+fn block(layout: Layout) -> (Vec<usize>, Vec<u8>) {
+    let mut w = BlockWriter::new(layout);
+    let offsets = vec![
+        w.write(&1.0f32)
+      , w.write(&Vector3 { x: 2.0f32, y: 3.0, z: 4.0 })
+      , w.write(&5.0f32)
+      , w.write(&m3())
+      , w.write_array(&[ Vector2 { x: 6.0f32, y: 7.0 }
+                       , Vector2 { x: 8.0, y: 9.0 } ])
+      , w.write_struct(|s| {
+            s.write(&Vector3 { x: 10.0f32, y: 11.0, z: 12.0 });
+            s.write(&13.0f32);
+        })
+      , w.write(&14.0f32)
+    ];
+    (offsets, w.into_bytes())
+}
+
+#[test]
+fn test_std140_block_offsets() {
+    let (offsets, bytes) = block(Layout::Std140);
+    assert_eq!(offsets, vec![0, 16, 28, 32, 80, 112, 128]);
+    assert_eq!(bytes.len(), 144);
+    assert_eq!(f32_at(&bytes, 28), 5.0);
+    assert_eq!(f32_at(&bytes, 96), 8.0);
+    assert_eq!(f32_at(&bytes, 124), 13.0);
+    assert_eq!(f32_at(&bytes, 128), 14.0);
+}
+
+#[test]
+fn test_std430_block_offsets() {
+    let (offsets, bytes) = block(Layout::Std430);
+    assert_eq!(offsets, vec![0, 16, 28, 32, 80, 96, 112]);
+    assert_eq!(bytes.len(), 128);
+    assert_eq!(f32_at(&bytes, 88), 8.0);
+    assert_eq!(f32_at(&bytes, 108), 13.0);
+}
+
+#[test]
+fn test_array_of_floats() {
+    assert_eq!(write_std140(&[1.0f32, 2.0]).len(), 32);
+    assert_eq!(floats(&write_std430(&[1.0f32, 2.0])), [1.0, 2.0]);
+}
+
+#[test]
+fn test_padded_types_match_encoding() {
+    assert_eq!(mem::size_of::<PaddedVector3<f32>>(), 16);
+    assert_eq!(mem::size_of::<PaddedMatrix2<f32>>(), 32);
+    assert_eq!(mem::size_of::<PaddedMatrix3<f32>>(), 48);
+    let padded = PaddedMatrix3::from(m3());
+    let bytes = unsafe {
+        ::std::slice::from_raw_parts( &padded as *const _ as *const u8
+                                    , mem::size_of::<PaddedMatrix3<f32>>())
+    };
+    assert_eq!(bytes, &write_std140(&m3())[..]);
+    let m4 = PaddedMatrix4::from(Matrix4 {
+        x1y1: 1, x2y1: 2, x3y1: 3, x4y1: 4
+      , x1y2: 5, x2y2: 6, x3y2: 7, x4y2: 8
+      , x1y3: 9, x2y3: 10, x3y3: 11, x4y3: 12
+      , x1y4: 13, x2y4: 14, x3y4: 15, x4y4: 16u32 });
+    assert_eq!(m4.columns[1], Vector4 { x: 2, y: 6, z: 10, w: 14 });
+}
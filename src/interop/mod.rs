@@ -0,0 +1,83 @@
+//! Conversions to and from the types of the `mint` crate.
+//!
+//! `mint` provides plain types for exchanging vectors and matrices between
+//! crates. Vectors convert to and from `mint`'s vectors and points, and
+//! matrices to and from both its row-major and column-major matrices; a
+//! matrix's `IntoMint` type is the column-major one, as used by most
+//! rendering crates.
+
+use vector::{Vector2, Vector3, Vector4};
+use matrix::{Matrix2, Matrix3, Matrix4};
+
+use mint;
+use std::array;
+use std::convert;
+
+#[cfg(test)] mod test;
+
+macro_rules! impl_mint_vector {
+    ($v: ident, $mint: ident, $($sub: ident),+) => {
+        impl<N> convert::From<$v<N>> for mint::$mint<N> {
+            #[inline] fn from(v: $v<N>) -> Self {
+                mint::$mint { $($sub: v.$sub),+ }
+            }
+        }
+        impl<N> convert::From<mint::$mint<N>> for $v<N> {
+            #[inline] fn from(v: mint::$mint<N>) -> Self {
+                $v { $($sub: v.$sub),+ }
+            }
+        }
+    }
+}
+
+impl_mint_vector! { Vector2, Vector2, x, y }
+impl_mint_vector! { Vector2, Point2, x, y }
+impl_mint_vector! { Vector3, Vector3, x, y, z }
+impl_mint_vector! { Vector3, Point3, x, y, z }
+impl_mint_vector! { Vector4, Vector4, x, y, z, w }
+
+impl<N> mint::IntoMint for Vector2<N> { type MintType = mint::Vector2<N>; }
+impl<N> mint::IntoMint for Vector3<N> { type MintType = mint::Vector3<N>; }
+impl<N> mint::IntoMint for Vector4<N> { type MintType = mint::Vector4<N>; }
+
+/// Swaps the rows and columns of a square nested array.
+fn transpose<N, const K: usize>(a: [[N; K]; K]) -> [[N; K]; K] {
+    let mut a = a.map(|row| row.map(Some));
+    array::from_fn(|i| array::from_fn(|j| {
+        a[j][i].take().expect("each element is moved exactly once")
+    }))
+}
+
+macro_rules! impl_mint_matrix {
+    ($($m: ident, $rows: ident, $cols: ident, $n: expr);+) => { $(
+        impl<N> convert::From<$m<N>> for mint::$rows<N> {
+            #[inline] fn from(m: $m<N>) -> Self {
+                let rows: [[N; $n]; $n] = m.into();
+                rows.into()
+            }
+        }
+        impl<N> convert::From<mint::$rows<N>> for $m<N> {
+            #[inline] fn from(m: mint::$rows<N>) -> Self {
+                let rows: [[N; $n]; $n] = m.into();
+                rows.into()
+            }
+        }
+        impl<N> convert::From<$m<N>> for mint::$cols<N> {
+            #[inline] fn from(m: $m<N>) -> Self {
+                let rows: [[N; $n]; $n] = m.into();
+                transpose(rows).into()
+            }
+        }
+        impl<N> convert::From<mint::$cols<N>> for $m<N> {
+            #[inline] fn from(m: mint::$cols<N>) -> Self {
+                let cols: [[N; $n]; $n] = m.into();
+                transpose(cols).into()
+            }
+        }
+        impl<N> mint::IntoMint for $m<N> { type MintType = mint::$cols<N>; }
+    )+ };
+}
+
+impl_mint_matrix! { Matrix2, RowMatrix2, ColumnMatrix2, 2
+                  ; Matrix3, RowMatrix3, ColumnMatrix3, 3
+                  ; Matrix4, RowMatrix4, ColumnMatrix4, 4 }
@@ -0,0 +1,41 @@
+use super::*;
+
+fn m3() -> Matrix3<i32> {
+    Matrix3 { x1y1: 1, x2y1: 2, x3y1: 3
+            , x1y2: 4, x2y2: 5, x3y2: 6
+            , x1y3: 7, x2y3: 8, x3y3: 9 }
+}
+
+#[test]
+fn test_vector_round_trip() {
+    let v = Vector3 { x: 1.0f32, y: 2.0, z: 3.0 };
+    let m: mint::Vector3<f32> = v.into();
+    assert_eq!((m.x, m.y, m.z), (1.0, 2.0, 3.0));
+    assert_eq!(Vector3::from(m), v);
+    let p: mint::Point2<u8> = Vector2 { x: 4, y: 5 }.into();
+    assert_eq!(Vector2::from(p), Vector2 { x: 4, y: 5 });
+}
+
+#[test]
+fn test_row_major_matrix() {
+    let rows: mint::RowMatrix3<i32> = m3().into();
+    assert_eq!((rows.x.x, rows.x.y, rows.y.x), (1, 2, 4));
+    assert_eq!(Matrix3::from(rows), m3());
+}
+
+#[test]
+fn test_column_major_matrix() {
+    let cols: mint::ColumnMatrix3<i32> = m3().into();
+    // the first column is (x1y1, x1y2, x1y3)
+    assert_eq!((cols.x.x, cols.x.y, cols.x.z), (1, 4, 7));
+    assert_eq!((cols.y.x, cols.z.z), (2, 9));
+    assert_eq!(Matrix3::from(cols), m3());
+}
+
+#[test]
+fn test_into_mint() {
+    fn to_mint<T: mint::IntoMint>(t: T) -> T::MintType { t.into() }
+    let m = Matrix2 { x1y1: 1, x2y1: 2, x1y2: 3, x2y2: 4 };
+    let cols: mint::ColumnMatrix2<i32> = to_mint(m);
+    assert_eq!((cols.x.y, cols.y.x), (3, 2));
+}
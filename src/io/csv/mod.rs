@@ -0,0 +1,279 @@
+//! Reading and writing lists of vectors and matrices as CSV.
+//!
+//! Each line of a file holds one vector or one matrix row. Fields may be
+//! enclosed in double quotes, with `""` standing for a literal quote, but
+//! quoted fields may not span lines. Whitespace around each field is
+//! ignored, and blank lines are skipped.
+//!
+//! When reading vectors from a file with a header row, columns are matched
+//! to subscripts by name, so a file with columns `id,z,y,x` may be read into
+//! `Vector3`s; columns which don't name a subscript are ignored. Without a
+//! header, the columns must appear in subscript order.
+
+use matrix::MatrixN;
+use vector::Vector;
+
+use std::error;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+#[cfg(test)] mod test;
+
+/// How a CSV file is laid out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Options {
+    /// The character separating fields, `,` by default
+    pub delimiter: char,
+    /// Whether the first line of the file names its columns, `true` by
+    /// default. Header rows are written for lists of vectors, but never for
+    /// matrices, whose columns have no names.
+    pub has_header: bool,
+}
+
+impl Default for Options {
+    #[inline] fn default() -> Options {
+        Options { delimiter: ',', has_header: true }
+    }
+}
+
+/// An error encountered while reading or writing a CSV file.
+///
+/// Line and column numbers start at one.
+#[derive(Debug)]
+pub enum Error {
+    /// An I/O error occurred
+    Io(io::Error),
+    /// A quoted field was not closed before the end of its line
+    UnterminatedQuote { line: usize },
+    /// A field could not be parsed as the element type
+    InvalidField { line: usize, column: usize, found: String },
+    /// A line had a different number of fields than the first line
+    WrongFieldCount { line: usize, expected: usize, found: usize },
+    /// The header row did not name one of the vector's subscripts
+    MissingColumn { name: &'static str },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Io(ref err) => write!(f, "I/O error: {}", err)
+          , Error::UnterminatedQuote { line } =>
+                write!(f, "line {}: unterminated quoted field", line)
+          , Error::InvalidField { line, column, ref found } =>
+                write!(f, "line {}, column {}: invalid field `{}`"
+                      , line, column, found)
+          , Error::WrongFieldCount { line, expected, found } =>
+                write!(f, "line {}: expected {} fields, found {}"
+                      , line, expected, found)
+          , Error::MissingColumn { name } =>
+                write!(f, "no column named `{}`", name)
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Error::Io(ref err) => Some(err)
+          , _ => None
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    #[inline] fn from(err: io::Error) -> Error { Error::Io(err) }
+}
+
+/// Splits a line into fields, removing quotes and surrounding whitespace.
+fn split_fields(s: &str, delimiter: char, line: usize)
+               -> Result<Vec<String>, Error> {
+    let mut fields = Vec::new();
+    let mut chars = s.chars().peekable();
+    loop {
+        let mut field = String::new();
+        while chars.peek().is_some_and(|&c| c != delimiter
+                                           && c.is_whitespace()) {
+            chars.next();
+        }
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            loop {
+                match chars.next() {
+                    Some('"') if chars.peek() == Some(&'"') => {
+                        chars.next();
+                        field.push('"');
+                    }
+                  , Some('"') => break
+                  , Some(c) => field.push(c)
+                  , None => return Err(Error::UnterminatedQuote { line })
+                }
+            }
+        }
+        while let Some(&c) = chars.peek() {
+            if c == delimiter { break }
+            field.push(c);
+            chars.next();
+        }
+        fields.push(field.trim().to_owned());
+        if chars.next().is_none() { return Ok(fields) }
+    }
+}
+
+/// The non-blank lines of a file, split into fields.
+struct Records<R> { lines: io::Lines<R>
+                  , line: usize
+                  , delimiter: char
+                  }
+
+impl<R> Records<R>
+where R: BufRead {
+
+    fn new(reader: R, options: &Options) -> Self {
+        Records { lines: reader.lines()
+                , line: 0
+                , delimiter: options.delimiter
+                }
+    }
+
+    fn next(&mut self) -> Result<Option<(usize, Vec<String>)>, Error> {
+        for line in self.lines.by_ref() {
+            let line = line?;
+            self.line += 1;
+            if !line.trim().is_empty() {
+                let fields = split_fields(&line, self.delimiter, self.line)?;
+                return Ok(Some((self.line, fields)))
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[inline]
+fn parse_field<N>(line: usize, column: usize, field: &str) -> Result<N, Error>
+where N: FromStr {
+    field.parse().map_err(|_| Error::InvalidField { line
+                                                  , column: column + 1
+                                                  , found: field.to_owned() })
+}
+
+/// Read a list of vectors, one per line.
+pub fn read_vectors<R, V, N>(reader: R, options: &Options)
+                            -> Result<Vec<V>, Error>
+where R: BufRead
+    , V: Vector<N>
+    , N: FromStr + Copy {
+    let mut records = Records::new(reader, options);
+    // the index of the column holding each subscript
+    let columns = if options.has_header {
+        let header = records.next()?.map(|(_, fields)| fields)
+                                    .unwrap_or_else(Vec::new);
+        V::SUBSCRIPTS.iter().map(|&name| {
+            header.iter().position(|field| field == name)
+                  .ok_or(Error::MissingColumn { name })
+        }).collect::<Result<Vec<_>, _>>()?
+    } else {
+        (0..V::DIM).collect()
+    };
+    let expected = if options.has_header { None } else { Some(V::DIM) };
+    read_rows(records, expected, |line, fields| {
+        let elems = columns.iter()
+            .map(|&c| match fields.get(c) {
+                Some(field) => parse_field(line, c, field)
+              , None => Err(Error::WrongFieldCount { line
+                                                   , expected: c + 1
+                                                   , found: fields.len() })
+            })
+            .collect::<Result<Vec<N>, _>>()?;
+        Ok(V::from_slice(&elems)
+             .expect("one element was read for each subscript"))
+    })
+}
+
+/// Read a matrix, one row per line.
+///
+/// Every line must have as many fields as the first. If the file has a
+/// header row, it is skipped.
+pub fn read_matrix<R, N>(reader: R, options: &Options)
+                        -> Result<MatrixN<N>, Error>
+where R: BufRead
+    , N: FromStr {
+    let mut records = Records::new(reader, options);
+    if options.has_header { records.next()?; }
+    let mut ncols = None;
+    let rows = read_rows(records, None, |line, fields| {
+        let expected = *ncols.get_or_insert(fields.len());
+        if fields.len() != expected {
+            return Err(Error::WrongFieldCount { line
+                                              , expected
+                                              , found: fields.len() })
+        }
+        fields.iter().enumerate()
+              .map(|(c, field)| parse_field(line, c, field))
+              .collect::<Result<Vec<N>, _>>()
+    })?;
+    let ncols = ncols.unwrap_or(0);
+    Ok(MatrixN::from_vec( rows.len(), ncols
+                        , rows.into_iter().flatten().collect())
+               .expect("every row has the same number of fields"))
+}
+
+/// Calls `f` with the line number and fields of each remaining record,
+/// checking that each record has `expected` fields if given.
+fn read_rows<R, T, F>( mut records: Records<R>, expected: Option<usize>
+                     , mut f: F) -> Result<Vec<T>, Error>
+where R: BufRead
+    , F: FnMut(usize, &[String]) -> Result<T, Error> {
+    let mut rows = Vec::new();
+    while let Some((line, fields)) = records.next()? {
+        if let Some(expected) = expected {
+            if fields.len() != expected {
+                return Err(Error::WrongFieldCount { line
+                                                  , expected
+                                                  , found: fields.len() })
+            }
+        }
+        rows.push(f(line, &fields)?);
+    }
+    Ok(rows)
+}
+
+/// Writes one line of fields separated by `delimiter`.
+fn write_record<W, I>(writer: &mut W, delimiter: char, fields: I)
+                     -> io::Result<()>
+where W: Write
+    , I: IntoIterator
+    , I::Item: fmt::Display {
+    for (i, field) in fields.into_iter().enumerate() {
+        if i > 0 { write!(writer, "{}", delimiter)? }
+        write!(writer, "{}", field)?;
+    }
+    writeln!(writer)
+}
+
+/// Write a list of vectors, one per line, preceded by a header row of
+/// subscript names if `options.has_header` is set.
+pub fn write_vectors<W, V, N>(mut writer: W, vectors: &[V], options: &Options)
+                             -> Result<(), Error>
+where W: Write
+    , V: Vector<N>
+    , N: fmt::Display {
+    if options.has_header {
+        write_record(&mut writer, options.delimiter, V::SUBSCRIPTS)?;
+    }
+    for v in vectors {
+        write_record(&mut writer, options.delimiter, v.as_slice())?;
+    }
+    Ok(())
+}
+
+/// Write a matrix, one row per line.
+pub fn write_matrix<W, N>(mut writer: W, m: &MatrixN<N>, options: &Options)
+                         -> Result<(), Error>
+where W: Write
+    , N: fmt::Display {
+    for row in m.as_slice().chunks(m.ncols().max(1)) {
+        write_record(&mut writer, options.delimiter, row)?;
+    }
+    Ok(())
+}
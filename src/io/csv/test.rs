@@ -0,0 +1,90 @@
+use super::*;
+use vector::{Vector2, Vector3};
+
+fn no_header() -> Options {
+    Options { has_header: false, ..Options::default() }
+}
+
+#[test]
+fn test_read_vectors_by_header_name() {
+    let csv = "\
+id, z, \"y\", x
+0, 3.0, 2.0, 1.0
+
+1, -1, 0.5, 1e3
+";
+    let points: Vec<Vector3<f64>> =
+        read_vectors(csv.as_bytes(), &Options::default()).unwrap();
+    assert_eq!(points, vec![ Vector3 { x: 1.0, y: 2.0, z: 3.0 }
+                           , Vector3 { x: 1000.0, y: 0.5, z: -1.0 } ]);
+}
+
+#[test]
+fn test_read_vectors_positional_with_delimiter() {
+    let options = Options { delimiter: '\t', has_header: false };
+    let points: Vec<Vector2<i32>> =
+        read_vectors("1\t2\n -3 \t 4\n".as_bytes(), &options).unwrap();
+    assert_eq!(points, vec![ Vector2 { x: 1, y: 2 }, Vector2 { x: -3, y: 4 } ]);
+}
+
+#[test]
+fn test_read_vector_errors() {
+    match read_vectors::<_, Vector3<f32>, f32>( "x,y\n1,2\n".as_bytes()
+                                              , &Options::default()) {
+        Err(Error::MissingColumn { name: "z" }) => {}
+      , other => panic!("unexpected result {:?}", other)
+    }
+    match read_vectors::<_, Vector2<f32>, f32>( "1,2\n3,4,5\n".as_bytes()
+                                              , &no_header()) {
+        Err(Error::WrongFieldCount { line: 2, expected: 2, found: 3 }) => {}
+      , other => panic!("unexpected result {:?}", other)
+    }
+    match read_vectors::<_, Vector2<u8>, u8>( "x,y\n1,-2\n".as_bytes()
+                                            , &Options::default()) {
+        Err(Error::InvalidField { line: 2, column: 2, ref found })
+            if found == "-2" => {}
+      , other => panic!("unexpected result {:?}", other)
+    }
+    match read_vectors::<_, Vector2<u8>, u8>( "\"x,y\n".as_bytes()
+                                            , &Options::default()) {
+        Err(Error::UnterminatedQuote { line: 1 }) => {}
+      , other => panic!("unexpected result {:?}", other)
+    }
+}
+
+#[test]
+fn test_vectors_round_trip() {
+    let points = vec![ Vector3 { x: 0.1f64, y: -2.5, z: 1.0e-9 }
+                     , Vector3 { x: 4.0, y: 5.0, z: 6.0 } ];
+    let options = Options { delimiter: ';', has_header: true };
+    let mut out = Vec::new();
+    write_vectors(&mut out, &points, &options).unwrap();
+    let written = String::from_utf8(out).unwrap();
+    assert!(written.starts_with("x;y;z\n0.1;-2.5;"));
+    let read: Vec<Vector3<f64>> =
+        read_vectors(written.as_bytes(), &options).unwrap();
+    assert_eq!(read, points);
+}
+
+#[test]
+fn test_matrix_round_trip() {
+    let m = MatrixN::from_vec(2, 3, vec![1, 2, 3, 4, 5, 6]).unwrap();
+    let mut out = Vec::new();
+    write_matrix(&mut out, &m, &Options::default()).unwrap();
+    assert_eq!(String::from_utf8(out.clone()).unwrap(), "1,2,3\n4,5,6\n");
+    assert_eq!(read_matrix::<_, i64>(&out[..], &no_header()).unwrap()
+              , m.clone().map(i64::from));
+
+    let with_header = "a,b,c\n1,2,3\n4,5,6\n";
+    assert_eq!( read_matrix::<_, i32>( with_header.as_bytes()
+                                     , &Options::default()).unwrap()
+              , m);
+}
+
+#[test]
+fn test_read_ragged_matrix() {
+    match read_matrix::<_, f64>("1,2\n3\n".as_bytes(), &no_header()) {
+        Err(Error::WrongFieldCount { line: 2, expected: 2, found: 1 }) => {}
+      , other => panic!("unexpected result {:?}", other)
+    }
+}
@@ -0,0 +1,7 @@
+//! Reading and writing matrices in file formats shared with other tools.
+
+pub mod mtx;
+pub mod npy;
+pub mod csv;
+
+mod zip;
@@ -0,0 +1,486 @@
+//! Reading and writing matrices in the Matrix Market exchange format.
+//!
+//! Both the `coordinate` (sparse) and `array` (dense) formats are supported,
+//! with `real`, `integer` and `pattern` fields and `general`, `symmetric` and
+//! `skew-symmetric` symmetry. Matrices are always read into a dense
+//! `MatrixN`; this may be converted into a fixed-size matrix with `TryFrom`.
+//!
+//! Files containing `complex` fields or `hermitian` symmetry are reported as
+//! `Error::Unsupported`.
+
+use matrix::MatrixN;
+
+use std::error;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+#[cfg(test)] mod test;
+
+/// Whether a file lists only its nonzero entries or every entry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Each line gives the row, column and value of a nonzero entry
+    Coordinate,
+    /// Every entry is listed, in column-major order
+    Array,
+}
+
+/// The kind of values stored in a file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Field {
+    /// Floating-point values
+    Real,
+    /// Integer values
+    Integer,
+    /// No values: every listed entry is one (coordinate format only)
+    Pattern,
+}
+
+/// Which entries of a matrix are stored in a file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Symmetry {
+    /// Every entry is stored
+    General,
+    /// Only entries on or below the diagonal are stored, and `A = Aᵀ`
+    Symmetric,
+    /// Only entries below the diagonal are stored, and `A = -Aᵀ`
+    SkewSymmetric,
+}
+
+/// The header line of a Matrix Market file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Header {
+    pub format: Format,
+    pub field: Field,
+    pub symmetry: Symmetry,
+}
+
+/// An error encountered while reading or writing a Matrix Market file.
+///
+/// Line numbers start at one.
+#[derive(Debug)]
+pub enum Error {
+    /// An I/O error occurred
+    Io(io::Error),
+    /// The header line was missing or malformed
+    InvalidHeader { line: usize },
+    /// The header describes a matrix which cannot be represented, such as
+    /// one with complex entries
+    Unsupported { line: usize, feature: String },
+    /// The size line was missing or malformed
+    InvalidSize { line: usize },
+    /// A data line could not be parsed as an entry of the element type
+    InvalidEntry { line: usize },
+    /// An entry's row or column (counting from one) lay outside the matrix
+    OutOfBounds { line: usize, row: usize, col: usize },
+    /// An entry of a symmetric or skew-symmetric matrix lay outside the
+    /// triangle which such files store: above the diagonal, or on it for a
+    /// skew-symmetric matrix
+    NotStored { line: usize, row: usize, col: usize },
+    /// The file contained a different number of entries than it declared
+    WrongEntryCount { expected: usize, found: usize },
+    /// A symmetric or skew-symmetric matrix was not square
+    NotSquare { nrows: usize, ncols: usize },
+    /// A matrix being written does not have the symmetry given in its
+    /// header; `row` and `col` (counting from one) locate the first entry
+    /// which differs from its mirror image
+    NotSymmetric { row: usize, col: usize },
+    /// The header's field does not match the element type being written,
+    /// or is not valid for the header's format
+    FieldMismatch { field: Field },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Io(ref err) => write!(f, "I/O error: {}", err)
+          , Error::InvalidHeader { line } =>
+                write!(f, "line {}: invalid Matrix Market header", line)
+          , Error::Unsupported { line, ref feature } =>
+                write!(f, "line {}: unsupported matrix type `{}`"
+                      , line, feature)
+          , Error::InvalidSize { line } =>
+                write!(f, "line {}: invalid size line", line)
+          , Error::InvalidEntry { line } =>
+                write!(f, "line {}: invalid entry", line)
+          , Error::OutOfBounds { line, row, col } =>
+                write!(f, "line {}: entry ({}, {}) is outside the matrix"
+                      , line, row, col)
+          , Error::NotStored { line, row, col } =>
+                write!(f, "line {}: entry ({}, {}) lies outside the stored \
+                           triangle"
+                      , line, row, col)
+          , Error::WrongEntryCount { expected, found } =>
+                write!(f, "expected {} entries, found {}", expected, found)
+          , Error::NotSquare { nrows, ncols } =>
+                write!(f, "a {}x{} matrix cannot be symmetric", nrows, ncols)
+          , Error::NotSymmetric { row, col } =>
+                write!(f, "entry ({}, {}) does not match its mirror image"
+                      , row, col)
+          , Error::FieldMismatch { field } =>
+                write!(f, "cannot write elements as {:?} values", field)
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Error::Io(ref err) => Some(err)
+          , _ => None
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    #[inline] fn from(err: io::Error) -> Error { Error::Io(err) }
+}
+
+/// An element type which can be read from and written to Matrix Market
+/// files.
+pub trait Element: Copy + PartialEq + FromStr + fmt::Display {
+    /// The field used when writing elements of this type
+    const FIELD: Field;
+
+    /// The value of entries not listed in a coordinate file
+    fn zero() -> Self;
+
+    /// The value of entries listed in a pattern file
+    fn one() -> Self;
+
+    /// Negation, used to fill in skew-symmetric matrices
+    ///
+    /// # Returns:
+    ///   - `Some(-self)` if the negation can be represented
+    ///   - `None` otherwise
+    ///
+    fn checked_neg(self) -> Option<Self>;
+}
+
+macro_rules! impl_element {
+    (Real: $($t: ty),+) => { $(
+        impl Element for $t {
+            const FIELD: Field = Field::Real;
+            #[inline] fn zero() -> $t { 0.0 }
+            #[inline] fn one() -> $t { 1.0 }
+            #[inline] fn checked_neg(self) -> Option<$t> { Some(-self) }
+        }
+    )+ };
+    (Integer: $($t: ty),+) => { $(
+        impl Element for $t {
+            const FIELD: Field = Field::Integer;
+            #[inline] fn zero() -> $t { 0 }
+            #[inline] fn one() -> $t { 1 }
+            #[inline] fn checked_neg(self) -> Option<$t> {
+                <$t>::checked_neg(self)
+            }
+        }
+    )+ };
+}
+
+impl_element! { Real: f32, f64 }
+impl_element! { Integer: u8, u16, u32, u64, usize
+                       , i8, i16, i32, i64, isize }
+
+impl Header {
+
+    fn parse(s: &str, line: usize) -> Result<Header, Error> {
+        let invalid = Error::InvalidHeader { line };
+        let words = s.split_whitespace()
+                     .map(str::to_lowercase)
+                     .collect::<Vec<_>>();
+        if words.len() != 5 || words[0] != "%%matrixmarket"
+                            || words[1] != "matrix" {
+            return Err(invalid)
+        }
+        let unsupported = |feature: &str| Error::Unsupported {
+            line
+          , feature: feature.to_owned()
+        };
+        let format = match &words[2][..] {
+            "coordinate" => Format::Coordinate
+          , "array" => Format::Array
+          , _ => return Err(invalid)
+        };
+        let field = match &words[3][..] {
+            "real" | "double" => Field::Real
+          , "integer" => Field::Integer
+          , "pattern" if format == Format::Coordinate => Field::Pattern
+          , "complex" => return Err(unsupported("complex"))
+          , _ => return Err(invalid)
+        };
+        let symmetry = match &words[4][..] {
+            "general" => Symmetry::General
+          , "symmetric" => Symmetry::Symmetric
+          , "skew-symmetric" if field != Field::Pattern =>
+                Symmetry::SkewSymmetric
+          , "hermitian" => return Err(unsupported("hermitian"))
+          , _ => return Err(invalid)
+        };
+        Ok(Header { format, field, symmetry })
+    }
+}
+
+impl fmt::Display for Header {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let format = match self.format {
+            Format::Coordinate => "coordinate"
+          , Format::Array => "array"
+        };
+        let field = match self.field {
+            Field::Real => "real"
+          , Field::Integer => "integer"
+          , Field::Pattern => "pattern"
+        };
+        let symmetry = match self.symmetry {
+            Symmetry::General => "general"
+          , Symmetry::Symmetric => "symmetric"
+          , Symmetry::SkewSymmetric => "skew-symmetric"
+        };
+        write!(f, "%%MatrixMarket matrix {} {} {}", format, field, symmetry)
+    }
+}
+
+/// The lines of a file following the header, skipping comments and blank
+/// lines.
+struct DataLines<R> { lines: io::Lines<R>
+                    , line: usize
+                    }
+
+impl<R> DataLines<R>
+where R: BufRead {
+
+    fn next(&mut self) -> Result<Option<(usize, String)>, Error> {
+        for line in self.lines.by_ref() {
+            let line = line?;
+            self.line += 1;
+            let trimmed = line.trim();
+            if !trimmed.is_empty() && !trimmed.starts_with('%') {
+                return Ok(Some((self.line, line)))
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Parses the whitespace-separated integers on a size line.
+fn parse_size(line: usize, s: &str, count: usize)
+             -> Result<Vec<usize>, Error> {
+    let size = s.split_whitespace()
+                .map(usize::from_str)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| Error::InvalidSize { line })?;
+    if size.len() == count { Ok(size) }
+    else { Err(Error::InvalidSize { line }) }
+}
+
+#[inline]
+fn parse_value<N>(line: usize, s: &str) -> Result<N, Error>
+where N: Element {
+    s.parse().map_err(|_| Error::InvalidEntry { line })
+}
+
+/// Returns the first row stored in column `col` of a file.
+///
+/// Only the lower triangle of a symmetric matrix is stored, and the diagonal
+/// of a skew-symmetric matrix (which is always zero) is omitted.
+#[inline]
+fn first_stored_row(col: usize, symmetry: Symmetry) -> usize {
+    match symmetry {
+        Symmetry::General => 0
+      , Symmetry::Symmetric => col
+      , Symmetry::SkewSymmetric => col + 1
+    }
+}
+
+/// Returns the `(row, column)` positions of the entries stored in a file, in
+/// column-major order.
+fn stored_positions(nrows: usize, ncols: usize, symmetry: Symmetry)
+                   -> impl Iterator<Item = (usize, usize)> {
+    (0..ncols).flat_map(move |col| {
+                  (first_stored_row(col, symmetry)..nrows)
+                      .map(move |row| (row, col))
+              })
+}
+
+/// Stores `value` at `(row, col)`, and its mirror image if the matrix is
+/// symmetric.
+fn set_entry<N>( m: &mut MatrixN<N>, symmetry: Symmetry, line: usize
+               , row: usize, col: usize, value: N) -> Result<(), Error>
+where N: Element {
+    m[(col, row)] = value;
+    if row != col {
+        match symmetry {
+            Symmetry::General => {}
+          , Symmetry::Symmetric => m[(row, col)] = value
+          , Symmetry::SkewSymmetric =>
+                m[(row, col)] = value.checked_neg()
+                    .ok_or(Error::InvalidEntry { line })?
+        }
+    }
+    Ok(())
+}
+
+/// Read a matrix from a Matrix Market file.
+pub fn read<R, N>(reader: R) -> Result<MatrixN<N>, Error>
+where R: BufRead
+    , N: Element {
+    read_with_header(reader).map(|(_, m)| m)
+}
+
+/// Read a matrix from a Matrix Market file, along with the file's header.
+pub fn read_with_header<R, N>(reader: R) -> Result<(Header, MatrixN<N>), Error>
+where R: BufRead
+    , N: Element {
+    let mut lines = reader.lines();
+    let header = match lines.next() {
+        Some(line) => Header::parse(&line?, 1)?
+      , None => return Err(Error::InvalidHeader { line: 1 })
+    };
+    let mut lines = DataLines { lines, line: 1 };
+
+    let (line, size) = match lines.next()? {
+        Some((line, size)) => (line, size)
+      , None => return Err(Error::InvalidSize { line: lines.line + 1 })
+    };
+    let size = match header.format {
+        Format::Coordinate => parse_size(line, &size, 3)?
+      , Format::Array => parse_size(line, &size, 2)?
+    };
+    let (nrows, ncols) = (size[0], size[1]);
+    if header.symmetry != Symmetry::General && nrows != ncols {
+        return Err(Error::NotSquare { nrows, ncols })
+    }
+    // the size line is untrusted, so report sizes which cannot be allocated
+    // rather than panicking or aborting
+    let len = nrows.checked_mul(ncols).ok_or(Error::InvalidSize { line })?;
+    let mut elems = Vec::new();
+    elems.try_reserve_exact(len).map_err(|_| Error::InvalidSize { line })?;
+    elems.resize(len, N::zero());
+    let mut m = MatrixN::from_vec(nrows, ncols, elems)
+        .expect("elements were allocated for every entry");
+
+    let (expected, found) = match header.format {
+        Format::Coordinate => {
+            let nnz = size[2];
+            let mut found = 0;
+            while let Some((line, entry)) = lines.next()? {
+                found += 1;
+                if found > nnz { continue }
+                let words = entry.split_whitespace().collect::<Vec<_>>();
+                let expected_words =
+                    if header.field == Field::Pattern { 2 } else { 3 };
+                if words.len() != expected_words {
+                    return Err(Error::InvalidEntry { line })
+                }
+                let index = |s: &str| s.parse::<usize>()
+                    .map_err(|_| Error::InvalidEntry { line });
+                let (row, col) = (index(words[0])?, index(words[1])?);
+                if row == 0 || row > nrows || col == 0 || col > ncols {
+                    return Err(Error::OutOfBounds { line, row, col })
+                }
+                if row - 1 < first_stored_row(col - 1, header.symmetry) {
+                    return Err(Error::NotStored { line, row, col })
+                }
+                let value = if header.field == Field::Pattern { N::one() }
+                            else { parse_value(line, words[2])? };
+                set_entry( &mut m, header.symmetry, line
+                         , row - 1, col - 1, value)?;
+            }
+            (nnz, found)
+        }
+      , Format::Array => {
+            // only square matrices have a symmetry other than `General`
+            let expected = match header.symmetry {
+                Symmetry::General => len
+              , Symmetry::Symmetric => (len + nrows) / 2
+              , Symmetry::SkewSymmetric => (len - nrows) / 2
+            };
+            let mut positions = stored_positions(nrows, ncols, header.symmetry)
+                .take(expected);
+            let mut found = 0;
+            while let Some((line, entry)) = lines.next()? {
+                for word in entry.split_whitespace() {
+                    if let Some((row, col)) = positions.next() {
+                        let value = parse_value(line, word)?;
+                        set_entry( &mut m, header.symmetry, line
+                                 , row, col, value)?;
+                    }
+                    found += 1;
+                }
+            }
+            (expected, found)
+        }
+    };
+    if expected != found {
+        return Err(Error::WrongEntryCount { expected, found })
+    }
+    Ok((header, m))
+}
+
+/// Checks that `m` has the symmetry required to be written with `symmetry`.
+fn check_symmetry<N>(m: &MatrixN<N>, symmetry: Symmetry) -> Result<(), Error>
+where N: Element {
+    if symmetry == Symmetry::General { return Ok(()) }
+    if !m.is_square() {
+        return Err(Error::NotSquare { nrows: m.nrows(), ncols: m.ncols() })
+    }
+    for col in 0..m.ncols() {
+        for row in col..m.nrows() {
+            let (lower, upper) = (m[(col, row)], m[(row, col)]);
+            let mirrored = match symmetry {
+                Symmetry::SkewSymmetric => lower.checked_neg() == Some(upper)
+              , _ => lower == upper
+            };
+            if !mirrored {
+                return Err(Error::NotSymmetric { row: row + 1
+                                               , col: col + 1 })
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Write a matrix to a Matrix Market file with the given header.
+///
+/// Coordinate files list only nonzero entries; pattern files list the
+/// positions of nonzero entries without their values. Symmetric and
+/// skew-symmetric files store only the lower triangle of the matrix, after
+/// checking that the matrix actually has the given symmetry.
+pub fn write<W, N>(mut writer: W, m: &MatrixN<N>, header: &Header)
+                  -> Result<(), Error>
+where W: Write
+    , N: Element {
+    let field_ok = match header.field {
+        Field::Pattern => header.format == Format::Coordinate
+                       && header.symmetry != Symmetry::SkewSymmetric
+      , field => field == N::FIELD
+    };
+    if !field_ok { return Err(Error::FieldMismatch { field: header.field }) }
+    check_symmetry(m, header.symmetry)?;
+
+    let entries = stored_positions(m.nrows(), m.ncols(), header.symmetry)
+        .map(|(row, col)| (row, col, m[(col, row)]));
+
+    writeln!(writer, "{}", header)?;
+    match header.format {
+        Format::Coordinate => {
+            let nonzero = entries.filter(|e| e.2 != N::zero())
+                                 .collect::<Vec<_>>();
+            writeln!(writer, "{} {} {}", m.nrows(), m.ncols(), nonzero.len())?;
+            for (row, col, value) in nonzero {
+                if header.field == Field::Pattern {
+                    writeln!(writer, "{} {}", row + 1, col + 1)?;
+                } else {
+                    writeln!(writer, "{} {} {}", row + 1, col + 1, value)?;
+                }
+            }
+        }
+      , Format::Array => {
+            writeln!(writer, "{} {}", m.nrows(), m.ncols())?;
+            for (_, _, value) in entries { writeln!(writer, "{}", value)? }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,233 @@
+use super::*;
+use matrix::{Matrix2, Matrix3, MatrixN};
+
+use std::convert::TryFrom;
+
+fn read_str<N: Element>(s: &str) -> Result<MatrixN<N>, Error> {
+    read(s.as_bytes())
+}
+
+fn write_string<N: Element>(m: &MatrixN<N>, header: &Header) -> String {
+    let mut out = Vec::new();
+    write(&mut out, m, header).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn test_read_coordinate_general() {
+    let m = read_str::<f64>("\
+%%MatrixMarket matrix coordinate real general
+% a comment
+
+2 3 3
+1 1 1.5
+2 3 -2e1
+1 2 3
+").unwrap();
+    assert_eq!(m.shape(), (2, 3));
+    assert_eq!(m.as_slice(), &[1.5, 3.0, 0.0, 0.0, 0.0, -20.0]);
+}
+
+#[test]
+fn test_read_array_column_major() {
+    let m = read_str::<i32>("\
+%%MatrixMarket matrix array integer general
+2 2
+1
+3
+2
+4
+").unwrap();
+    assert_eq!( Matrix2::try_from(m)
+              , Ok(Matrix2 { x1y1: 1, x2y1: 2, x1y2: 3, x2y2: 4 }));
+}
+
+#[test]
+fn test_read_symmetric_and_skew() {
+    let m = read_str::<f32>("\
+%%MatrixMarket matrix coordinate real symmetric
+3 3 2
+2 1 5
+3 3 1
+").unwrap();
+    assert_eq!(m.as_slice(), &[0.0, 5.0, 0.0, 5.0, 0.0, 0.0, 0.0, 0.0, 1.0]);
+
+    let m = read_str::<i8>("\
+%%MatrixMarket matrix array integer skew-symmetric
+3 3
+1 2
+3
+").unwrap();
+    assert_eq!(m.as_slice(), &[0, -1, -2, 1, 0, -3, 2, 3, 0]);
+}
+
+#[test]
+fn test_read_pattern() {
+    let m = read_str::<u8>("\
+%%MatrixMarket matrix coordinate pattern general
+2 2 2
+1 2
+2 1
+").unwrap();
+    assert_eq!(m.as_slice(), &[0, 1, 1, 0]);
+}
+
+#[test]
+fn test_read_errors() {
+    match read_str::<f64>("%%MatrixMarket matrix sparse real general\n") {
+        Err(Error::InvalidHeader { line: 1 }) => {}
+      , other => panic!("unexpected result {:?}", other)
+    }
+    match read_str::<f64>("%%MatrixMarket matrix array complex general\n") {
+        Err(Error::Unsupported { line: 1, ref feature })
+            if feature == "complex" => {}
+      , other => panic!("unexpected result {:?}", other)
+    }
+    match read_str::<f64>("%%MatrixMarket matrix array real general\n2\n") {
+        Err(Error::InvalidSize { line: 2 }) => {}
+      , other => panic!("unexpected result {:?}", other)
+    }
+    match read_str::<f64>("\
+%%MatrixMarket matrix coordinate real general
+2 2 1
+3 1 1.0
+") {
+        Err(Error::OutOfBounds { line: 3, row: 3, col: 1 }) => {}
+      , other => panic!("unexpected result {:?}", other)
+    }
+    match read_str::<f64>("\
+%%MatrixMarket matrix array real general
+2 2
+1 2 3
+") {
+        Err(Error::WrongEntryCount { expected: 4, found: 3 }) => {}
+      , other => panic!("unexpected result {:?}", other)
+    }
+    match read_str::<i32>("\
+%%MatrixMarket matrix array real general
+1 1
+0.5
+") {
+        Err(Error::InvalidEntry { line: 3 }) => {}
+      , other => panic!("unexpected result {:?}", other)
+    }
+    match read_str::<u8>("\
+%%MatrixMarket matrix coordinate integer skew-symmetric
+2 2 1
+2 1 1
+") {
+        Err(Error::InvalidEntry { line: 3 }) => {}
+      , other => panic!("unexpected result {:?}", other)
+    }
+    match read_str::<f64>("\
+%%MatrixMarket matrix coordinate real symmetric
+2 2 1
+1 2 1.0
+") {
+        Err(Error::NotStored { line: 3, row: 1, col: 2 }) => {}
+      , other => panic!("unexpected result {:?}", other)
+    }
+    match read_str::<i32>("\
+%%MatrixMarket matrix coordinate integer skew-symmetric
+2 2 1
+2 2 1
+") {
+        Err(Error::NotStored { line: 3, row: 2, col: 2 }) => {}
+      , other => panic!("unexpected result {:?}", other)
+    }
+}
+
+#[test]
+fn test_read_rejects_huge_sizes() {
+    // the number of elements overflows a `usize`
+    match read_str::<f64>("\
+%%MatrixMarket matrix array real general
+18446744073709551615 2
+") {
+        Err(Error::InvalidSize { line: 2 }) => {}
+      , other => panic!("unexpected result {:?}", other)
+    }
+    // the number of elements fits, but they could never be allocated
+    match read_str::<f64>("\
+%%MatrixMarket matrix coordinate real general
+4294967296 4294967295 0
+") {
+        Err(Error::InvalidSize { line: 2 }) => {}
+      , other => panic!("unexpected result {:?}", other)
+    }
+}
+
+#[test]
+fn test_conversion_shape_mismatch() {
+    let m = MatrixN::from_elem(2, 3, 0.0f64);
+    let err = Matrix3::try_from(m).unwrap_err();
+    assert_eq!(err.expected, (3, 3));
+    assert_eq!(err.found, (2, 3));
+}
+
+#[test]
+fn test_write_coordinate() {
+    let m = MatrixN::from_vec(2, 2, vec![0.0f64, 1.5, -2.0, 0.0]).unwrap();
+    let header = Header { format: Format::Coordinate
+                        , field: Field::Real
+                        , symmetry: Symmetry::General };
+    assert_eq!(write_string(&m, &header), "\
+%%MatrixMarket matrix coordinate real general
+2 2 2
+2 1 -2
+1 2 1.5
+");
+}
+
+#[test]
+fn test_write_symmetric_array_round_trip() {
+    let m = MatrixN::from_vec(3, 3, vec![ 1, 2, 3
+                                        , 2, 4, 5
+                                        , 3, 5, 6 ]).unwrap();
+    let header = Header { format: Format::Array
+                        , field: Field::Integer
+                        , symmetry: Symmetry::Symmetric };
+    let written = write_string(&m, &header);
+    assert_eq!(written, "\
+%%MatrixMarket matrix array integer symmetric
+3 3
+1
+2
+3
+4
+5
+6
+");
+    let (read_header, read_m) = read_with_header::<_, i64>(written.as_bytes())
+        .unwrap();
+    assert_eq!(read_header, header);
+    assert_eq!(read_m, m.map(i64::from));
+}
+
+#[test]
+fn test_write_skew_round_trip() {
+    let m = MatrixN::from_vec(2, 2, vec![0.0f32, -4.0, 4.0, 0.0]).unwrap();
+    let header = Header { format: Format::Coordinate
+                        , field: Field::Real
+                        , symmetry: Symmetry::SkewSymmetric };
+    let written = write_string(&m, &header);
+    assert_eq!(read_str::<f32>(&written).unwrap(), m);
+}
+
+#[test]
+fn test_write_errors() {
+    let m = MatrixN::from_vec(2, 2, vec![1, 2, 3, 4]).unwrap();
+    let mut header = Header { format: Format::Array
+                            , field: Field::Integer
+                            , symmetry: Symmetry::Symmetric };
+    match write(Vec::new(), &m, &header) {
+        Err(Error::NotSymmetric { row: 2, col: 1 }) => {}
+      , other => panic!("unexpected result {:?}", other)
+    }
+    header.symmetry = Symmetry::General;
+    header.field = Field::Real;
+    match write(Vec::new(), &m, &header) {
+        Err(Error::FieldMismatch { field: Field::Real }) => {}
+      , other => panic!("unexpected result {:?}", other)
+    }
+}
@@ -0,0 +1,542 @@
+//! Reading and writing NumPy `.npy` files and uncompressed `.npz` archives.
+//!
+//! Arrays of `f32`, `f64`, `i32` and `i64` elements may be read in either
+//! byte order and in either C (row-major) or Fortran (column-major) order.
+//! Two-dimensional arrays are read into a `MatrixN`, and arrays of shape
+//! `(n, D)` into a `Vec` of any vector type with `D` elements. Arrays are
+//! always written little-endian in C order.
+
+use matrix::MatrixN;
+use vector::Vector;
+use super::zip;
+
+use std::error;
+use std::fmt;
+use std::io::{self, Read, Seek, Write};
+use std::mem;
+
+#[cfg(test)] mod test;
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+
+/// An error encountered while reading or writing NumPy files.
+#[derive(Debug)]
+pub enum Error {
+    /// An I/O error occurred, or a `.npz` archive was malformed
+    Io(io::Error),
+    /// The file did not begin with the `.npy` magic string
+    InvalidMagic,
+    /// The file has a format version other than 1.0, 2.0 or 3.0
+    UnsupportedVersion { major: u8, minor: u8 },
+    /// The header dictionary could not be parsed
+    InvalidHeader,
+    /// The array's dtype is not one which can be read, such as a structured
+    /// or complex dtype
+    UnsupportedDtype { descr: String },
+    /// The array's dtype differs from the requested element type
+    WrongDtype { expected: &'static str, found: String },
+    /// The array's shape is not valid for the requested type
+    WrongShape { found: Vec<usize> },
+    /// A `.npz` archive did not contain the requested array
+    MissingArray { name: String },
+    /// A `.npz` entry was compressed, which is not supported
+    Compressed { name: String },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Io(ref err) => write!(f, "I/O error: {}", err)
+          , Error::InvalidMagic => write!(f, "not a .npy file")
+          , Error::UnsupportedVersion { major, minor } =>
+                write!(f, "unsupported .npy version {}.{}", major, minor)
+          , Error::InvalidHeader => write!(f, "invalid .npy header")
+          , Error::UnsupportedDtype { ref descr } =>
+                write!(f, "unsupported dtype `{}`", descr)
+          , Error::WrongDtype { expected, ref found } =>
+                write!(f, "expected dtype `{}`, found `{}`", expected, found)
+          , Error::WrongShape { ref found } =>
+                write!(f, "array has unexpected shape {:?}", found)
+          , Error::MissingArray { ref name } =>
+                write!(f, "archive has no array named `{}`", name)
+          , Error::Compressed { ref name } =>
+                write!(f, "array `{}` is compressed", name)
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Error::Io(ref err) => Some(err)
+          , _ => None
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    #[inline] fn from(err: io::Error) -> Error { Error::Io(err) }
+}
+
+/// An element type which can be stored in NumPy arrays.
+pub trait Element: Copy {
+    /// The kind and size of this type in a dtype descriptor, such as `"f8"`
+    const TYPE: &'static str;
+
+    /// Decode an element from little-endian bytes
+    fn from_le(bytes: &[u8]) -> Self;
+
+    /// Decode an element from big-endian bytes
+    fn from_be(bytes: &[u8]) -> Self;
+
+    /// Append the little-endian bytes of this element to `out`
+    fn put_le(self, out: &mut Vec<u8>);
+}
+
+macro_rules! impl_element {
+    ($($t: ty, $descr: expr);+) => { $(
+        impl Element for $t {
+            const TYPE: &'static str = $descr;
+
+            #[inline] fn from_le(bytes: &[u8]) -> $t {
+                let mut le = [0; mem::size_of::<$t>()];
+                le.copy_from_slice(bytes);
+                <$t>::from_le_bytes(le)
+            }
+
+            #[inline] fn from_be(bytes: &[u8]) -> $t {
+                let mut be = [0; mem::size_of::<$t>()];
+                be.copy_from_slice(bytes);
+                <$t>::from_be_bytes(be)
+            }
+
+            #[inline] fn put_le(self, out: &mut Vec<u8>) {
+                out.extend_from_slice(&self.to_le_bytes())
+            }
+        }
+    )+ };
+}
+
+impl_element! { f32, "f4"; f64, "f8"; i32, "i4"; i64, "i8" }
+
+/// The header of a `.npy` file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Header {
+    /// The dtype descriptor, such as `"<f8"`
+    pub descr: String,
+    /// Whether the data is stored in Fortran (column-major) order
+    pub fortran_order: bool,
+    /// The length of each dimension of the array
+    pub shape: Vec<usize>,
+}
+
+/// A value in the header dictionary.
+#[derive(Debug, PartialEq)]
+enum Value { Str(String)
+           , Bool(bool)
+           , Tuple(Vec<usize>)
+           }
+
+/// Parses the Python dictionary literal in a `.npy` header.
+struct DictParser<'a> { s: &'a str }
+
+impl<'a> DictParser<'a> {
+
+    #[inline]
+    fn skip_ws(&mut self) { self.s = self.s.trim_start() }
+
+    fn eat(&mut self, c: char) -> Result<(), Error> {
+        self.skip_ws();
+        if self.s.starts_with(c) {
+            self.s = &self.s[c.len_utf8()..];
+            Ok(())
+        } else {
+            Err(Error::InvalidHeader)
+        }
+    }
+
+    #[inline]
+    fn peek(&mut self) -> Option<char> {
+        self.skip_ws();
+        self.s.chars().next()
+    }
+
+    fn string(&mut self) -> Result<String, Error> {
+        let quote = match self.peek() {
+            Some(q @ '\'') | Some(q @ '"') => q
+          , _ => return Err(Error::InvalidHeader)
+        };
+        let end = self.s[1..].find(quote).ok_or(Error::InvalidHeader)?;
+        let string = self.s[1..end + 1].to_owned();
+        self.s = &self.s[end + 2..];
+        Ok(string)
+    }
+
+    fn value(&mut self) -> Result<Value, Error> {
+        match self.peek() {
+            Some('\'') | Some('"') => self.string().map(Value::Str)
+          , Some('(') => {
+                self.eat('(')?;
+                let mut dims = Vec::new();
+                while self.peek() != Some(')') {
+                    let len = self.s.find(|c: char| !c.is_ascii_digit())
+                                    .unwrap_or(self.s.len());
+                    let dim = self.s[..len].parse()
+                                  .map_err(|_| Error::InvalidHeader)?;
+                    dims.push(dim);
+                    self.s = &self.s[len..];
+                    if self.peek() == Some(',') { self.eat(',')? }
+                    else { break }
+                }
+                self.eat(')')?;
+                Ok(Value::Tuple(dims))
+            }
+          , _ if self.s.starts_with("True") => {
+                self.s = &self.s[4..];
+                Ok(Value::Bool(true))
+            }
+          , _ if self.s.starts_with("False") => {
+                self.s = &self.s[5..];
+                Ok(Value::Bool(false))
+            }
+          , _ => Err(Error::InvalidHeader)
+        }
+    }
+
+    fn dict(&mut self) -> Result<Vec<(String, Value)>, Error> {
+        let mut items = Vec::new();
+        self.eat('{')?;
+        while self.peek() != Some('}') {
+            let key = self.string()?;
+            self.eat(':')?;
+            items.push((key, self.value()?));
+            if self.peek() == Some(',') { self.eat(',')? }
+            else { break }
+        }
+        self.eat('}')?;
+        Ok(items)
+    }
+}
+
+impl Header {
+
+    fn parse(s: &str) -> Result<Header, Error> {
+        let (mut descr, mut fortran_order, mut shape) = (None, None, None);
+        let items = DictParser { s }.dict()?;
+        for (key, value) in items {
+            match (&key[..], value) {
+                ("descr", Value::Str(s)) => descr = Some(s)
+              , ("fortran_order", Value::Bool(b)) => fortran_order = Some(b)
+              , ("shape", Value::Tuple(t)) => shape = Some(t)
+              , _ => return Err(Error::InvalidHeader)
+            }
+        }
+        match (descr, fortran_order, shape) {
+            (Some(descr), Some(fortran_order), Some(shape))
+                if elem_count(&shape).is_some() =>
+                Ok(Header { descr, fortran_order, shape })
+          , _ => Err(Error::InvalidHeader)
+        }
+    }
+
+    /// Returns the number of elements in the array
+    #[inline]
+    pub fn len(&self) -> usize { self.shape.iter().product() }
+
+    /// Returns true if the array has no elements
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    fn to_dict(&self) -> String {
+        let shape = match self.shape.len() {
+            1 => format!("({},)", self.shape[0])
+          , _ => format!("({})", self.shape.iter()
+                                           .map(usize::to_string)
+                                           .collect::<Vec<_>>()
+                                           .join(", "))
+        };
+        format!( "{{'descr': '{}', 'fortran_order': {}, 'shape': {}, }}"
+               , self.descr
+               , if self.fortran_order { "True" } else { "False" }
+               , shape)
+    }
+}
+
+/// Returns the number of elements in an array of the given shape, or `None`
+/// if it overflows a `usize`.
+#[inline]
+fn elem_count(shape: &[usize]) -> Option<usize> {
+    shape.iter().try_fold(1usize, |n, &d| n.checked_mul(d))
+}
+
+/// Read the header of a `.npy` file, leaving `reader` at the start of the
+/// array data.
+pub fn read_header<R>(reader: &mut R) -> Result<Header, Error>
+where R: Read {
+    let mut prefix = [0; 8];
+    reader.read_exact(&mut prefix)?;
+    if &prefix[..6] != MAGIC { return Err(Error::InvalidMagic) }
+    let len = match (prefix[6], prefix[7]) {
+        (1, 0) => {
+            let mut len = [0; 2];
+            reader.read_exact(&mut len)?;
+            u16::from_le_bytes(len) as usize
+        }
+      , (2, 0) | (3, 0) => {
+            let mut len = [0; 4];
+            reader.read_exact(&mut len)?;
+            u32::from_le_bytes(len) as usize
+        }
+      , (major, minor) =>
+            return Err(Error::UnsupportedVersion { major, minor })
+    };
+    let mut header = vec![0; len];
+    reader.read_exact(&mut header)?;
+    let header = String::from_utf8(header).map_err(|_| Error::InvalidHeader)?;
+    Header::parse(&header)
+}
+
+/// Read the data of an array with the given header, in C order.
+pub fn read_data<R, N>(reader: &mut R, header: &Header)
+                      -> Result<Vec<N>, Error>
+where R: Read
+    , N: Element {
+    let descr = &header.descr;
+    let (order, ty) = descr.split_at(if descr.is_empty() { 0 } else { 1 });
+    let big_endian = match order {
+        "<" => false
+      , ">" => true
+      , "=" => cfg!(target_endian = "big")
+      , _ => return Err(Error::UnsupportedDtype { descr: descr.clone() })
+    };
+    if ty != N::TYPE {
+        return match ty {
+            "f4" | "f8" | "i4" | "i8" =>
+                Err(Error::WrongDtype { expected: N::TYPE
+                                      , found: descr.clone() })
+          , _ => Err(Error::UnsupportedDtype { descr: descr.clone() })
+        }
+    }
+
+    let size = mem::size_of::<N>();
+    let len = elem_count(&header.shape)
+        .and_then(|len| len.checked_mul(size))
+        .ok_or(Error::InvalidHeader)?;
+    // the header is untrusted, so the data is read without allocating all of
+    // it up front
+    let mut bytes = Vec::new();
+    reader.by_ref().take(len as u64).read_to_end(&mut bytes)?;
+    if bytes.len() != len {
+        return Err(Error::Io(io::ErrorKind::UnexpectedEof.into()))
+    }
+    let elems = bytes.chunks(size).map(|b| if big_endian { N::from_be(b) }
+                                           else { N::from_le(b) });
+    if !header.fortran_order || header.shape.len() < 2 {
+        return Ok(elems.collect())
+    }
+
+    // in Fortran order the first index varies fastest, so each element is
+    // found by splitting its C offset into indices and recombining them with
+    // Fortran strides.
+    let elems = elems.collect::<Vec<_>>();
+    let shape = &header.shape;
+    let mut f_strides = vec![1; shape.len()];
+    for d in 1..shape.len() {
+        f_strides[d] = f_strides[d - 1] * shape[d - 1];
+    }
+    Ok((0..elems.len()).map(|c| {
+        let (mut rest, mut offset) = (c, 0);
+        for d in (0..shape.len()).rev() {
+            offset += (rest % shape[d]) * f_strides[d];
+            rest /= shape[d];
+        }
+        elems[offset]
+    }).collect())
+}
+
+/// Read a two-dimensional array from a `.npy` file into a matrix.
+pub fn read_matrix<R, N>(mut reader: R) -> Result<MatrixN<N>, Error>
+where R: Read
+    , N: Element {
+    let header = read_header(&mut reader)?;
+    if header.shape.len() != 2 {
+        return Err(Error::WrongShape { found: header.shape })
+    }
+    let elems = read_data(&mut reader, &header)?;
+    Ok(MatrixN::from_vec(header.shape[0], header.shape[1], elems)
+               .expect("header shape matches number of elements read"))
+}
+
+/// Read an array of shape `(n, V::DIM)` from a `.npy` file into a list of
+/// `n` vectors.
+pub fn read_vectors<R, V, N>(mut reader: R) -> Result<Vec<V>, Error>
+where R: Read
+    , V: Vector<N>
+    , N: Element {
+    let header = read_header(&mut reader)?;
+    if header.shape.len() != 2 || header.shape[1] != V::DIM {
+        return Err(Error::WrongShape { found: header.shape })
+    }
+    let elems = read_data(&mut reader, &header)?;
+    Ok(elems.chunks(V::DIM)
+            .map(|chunk| V::from_slice(chunk)
+                           .expect("chunk has as many elements as vector"))
+            .collect())
+}
+
+/// Write an array with the given shape to a `.npy` file, little-endian and
+/// in C order.
+///
+/// # Panics
+///   - If `elems` does not contain as many elements as `shape` describes
+///
+pub fn write_array<W, N>(mut writer: W, shape: &[usize], elems: &[N])
+                        -> Result<(), Error>
+where W: Write
+    , N: Element {
+    let header = Header { descr: format!("<{}", N::TYPE)
+                        , fortran_order: false
+                        , shape: shape.to_vec() };
+    assert_eq!( header.len(), elems.len()
+              , "array shape does not match the number of elements");
+    let mut dict = header.to_dict();
+    // the header is padded with spaces and a newline so that the data is
+    // aligned to 64 bytes.
+    let version_1 = dict.len() + 11 <= 0xffff;
+    let prefix_len = if version_1 { 10 } else { 12 };
+    let padding = (64 - (prefix_len + dict.len() + 1) % 64) % 64;
+    dict.extend(::std::iter::repeat_n(' ', padding));
+    dict.push('\n');
+
+    let mut out = Vec::with_capacity( prefix_len + dict.len()
+                                    + mem::size_of_val(elems));
+    out.extend_from_slice(MAGIC);
+    if version_1 {
+        out.extend_from_slice(&[1, 0]);
+        out.extend_from_slice(&(dict.len() as u16).to_le_bytes());
+    } else {
+        out.extend_from_slice(&[2, 0]);
+        out.extend_from_slice(&(dict.len() as u32).to_le_bytes());
+    }
+    out.extend_from_slice(dict.as_bytes());
+    for &elem in elems { elem.put_le(&mut out) }
+    writer.write_all(&out)?;
+    Ok(())
+}
+
+/// Write a matrix to a `.npy` file.
+pub fn write_matrix<W, N>(writer: W, m: &MatrixN<N>) -> Result<(), Error>
+where W: Write
+    , N: Element {
+    write_array(writer, &[m.nrows(), m.ncols()], m.as_slice())
+}
+
+/// Write a list of vectors to a `.npy` file, as an array of shape
+/// `(n, V::DIM)`.
+pub fn write_vectors<W, V, N>(writer: W, vectors: &[V]) -> Result<(), Error>
+where W: Write
+    , V: Vector<N>
+    , N: Element {
+    let elems = vectors.iter()
+                       .flat_map(|v| v.as_slice().iter().cloned())
+                       .collect::<Vec<_>>();
+    write_array(writer, &[vectors.len(), V::DIM], &elems)
+}
+
+/// Reads arrays from an uncompressed `.npz` archive.
+pub struct NpzReader<R> { reader: R
+                        , entries: Vec<zip::Entry>
+                        }
+
+impl<R> NpzReader<R>
+where R: Read + Seek {
+
+    /// Open an archive, reading its table of contents.
+    pub fn new(mut reader: R) -> Result<Self, Error> {
+        let entries = zip::read_entries(&mut reader)?;
+        Ok(NpzReader { reader, entries })
+    }
+
+    /// Returns the names of the arrays in the archive
+    pub fn names(&self) -> Vec<&str> {
+        self.entries.iter()
+            .map(|e| e.name.trim_end_matches(".npy"))
+            .collect()
+    }
+
+    fn entry_data(&mut self, name: &str) -> Result<Vec<u8>, Error> {
+        let entry = self.entries.iter()
+            .find(|e| e.name.trim_end_matches(".npy") == name)
+            .ok_or_else(|| Error::MissingArray { name: name.to_owned() })?;
+        if entry.method != zip::STORED {
+            return Err(Error::Compressed { name: name.to_owned() })
+        }
+        Ok(zip::read_entry(&mut self.reader, entry)?)
+    }
+
+    /// Read the named two-dimensional array into a matrix.
+    pub fn read_matrix<N>(&mut self, name: &str) -> Result<MatrixN<N>, Error>
+    where N: Element {
+        read_matrix(&self.entry_data(name)?[..])
+    }
+
+    /// Read the named array of shape `(n, V::DIM)` into a list of vectors.
+    pub fn read_vectors<V, N>(&mut self, name: &str) -> Result<Vec<V>, Error>
+    where V: Vector<N>
+        , N: Element {
+        read_vectors(&self.entry_data(name)?[..])
+    }
+}
+
+/// Writes arrays to an uncompressed `.npz` archive.
+///
+/// The archive is incomplete until `finish` is called.
+pub struct NpzWriter<W> { writer: W
+                        , offset: u64
+                        , entries: Vec<zip::Entry>
+                        }
+
+impl<W> NpzWriter<W>
+where W: Write {
+
+    /// Begin writing an archive.
+    #[inline]
+    pub fn new(writer: W) -> Self {
+        NpzWriter { writer, offset: 0, entries: Vec::new() }
+    }
+
+    fn add_entry(&mut self, name: &str, data: &[u8]) -> Result<(), Error> {
+        let name = format!("{}.npy", name);
+        let entry =
+            zip::write_entry(&mut self.writer, self.offset, &name, data)?;
+        self.offset += zip::entry_len(&entry);
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    /// Add a matrix to the archive under the given name.
+    pub fn add_matrix<N>(&mut self, name: &str, m: &MatrixN<N>)
+                        -> Result<(), Error>
+    where N: Element {
+        let mut data = Vec::new();
+        write_matrix(&mut data, m)?;
+        self.add_entry(name, &data)
+    }
+
+    /// Add a list of vectors to the archive under the given name.
+    pub fn add_vectors<V, N>(&mut self, name: &str, vectors: &[V])
+                            -> Result<(), Error>
+    where V: Vector<N>
+        , N: Element {
+        let mut data = Vec::new();
+        write_vectors(&mut data, vectors)?;
+        self.add_entry(name, &data)
+    }
+
+    /// Write the archive's table of contents, returning the underlying
+    /// writer.
+    pub fn finish(mut self) -> Result<W, Error> {
+        zip::write_directory(&mut self.writer, self.offset, &self.entries)?;
+        Ok(self.writer)
+    }
+}
@@ -0,0 +1,206 @@
+use super::*;
+use vector::{Vector2, Vector3};
+
+use std::io::Cursor;
+
+/// Builds a version 1.0 `.npy` file from a header dictionary and raw data.
+fn npy_bytes(dict: &str, data: &[u8]) -> Vec<u8> {
+    let mut bytes = MAGIC.to_vec();
+    bytes.extend_from_slice(&[1, 0]);
+    bytes.extend_from_slice(&(dict.len() as u16 + 1).to_le_bytes());
+    bytes.extend_from_slice(dict.as_bytes());
+    bytes.push(b'\n');
+    bytes.extend_from_slice(data);
+    bytes
+}
+
+#[test]
+fn test_write_matrix_layout() {
+    let m = MatrixN::from_vec(2, 3, vec![1.0f64, 2.0, 3.0, 4.0, 5.0, 6.0])
+        .unwrap();
+    let mut bytes = Vec::new();
+    write_matrix(&mut bytes, &m).unwrap();
+    assert_eq!(bytes.len(), 128 + 6 * 8);
+    assert_eq!(&bytes[..10], b"\x93NUMPY\x01\x00\x76\x00");
+    let dict = "{'descr': '<f8', 'fortran_order': False, 'shape': (2, 3), }";
+    assert_eq!(&bytes[10..10 + dict.len()], dict.as_bytes());
+    assert_eq!(bytes[127], b'\n');
+    assert_eq!(&bytes[128..136], &1.0f64.to_le_bytes());
+    assert_eq!(read_matrix::<_, f64>(&bytes[..]).unwrap(), m);
+}
+
+#[test]
+fn test_read_big_endian_fortran_order() {
+    let data = [1i32, 4, 2, 5, 3, 6].iter()
+                                    .flat_map(|x| x.to_be_bytes().to_vec())
+                                    .collect::<Vec<_>>();
+    let bytes = npy_bytes( "{'descr': '>i4', 'fortran_order': True, \
+                             'shape': (2, 3), }"
+                         , &data);
+    let m = read_matrix::<_, i32>(&bytes[..]).unwrap();
+    assert_eq!(m.shape(), (2, 3));
+    assert_eq!(m.as_slice(), &[1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn test_vectors_round_trip() {
+    let points = vec![ Vector3 { x: 1.5f32, y: 2.0, z: -3.0 }
+                     , Vector3 { x: 0.0, y: 1.0e-3, z: 7.0 } ];
+    let mut bytes = Vec::new();
+    write_vectors(&mut bytes, &points).unwrap();
+    let read: Vec<Vector3<f32>> = read_vectors(&bytes[..]).unwrap();
+    assert_eq!(read, points);
+
+    match read_vectors::<_, Vector2<f32>, f32>(&bytes[..]) {
+        Err(Error::WrongShape { ref found }) if *found == [2, 3] => {}
+      , other => panic!("unexpected result {:?}", other)
+    }
+}
+
+/// Builds an archive holding one empty entry, `m.npy`, whose central
+/// directory entry claims a zip64 size of `size` bytes, and whose end of
+/// central directory record claims a directory of `dir_size` bytes.
+fn zip64_npz_bytes(size: u64, dir_size: Option<u32>) -> Vec<u8> {
+    let name = b"m.npy";
+    let mut bytes = 0x0403_4b50u32.to_le_bytes().to_vec();
+    bytes.extend_from_slice(&[0; 22]);
+    bytes.extend_from_slice(&(name.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(&[0; 2]);
+    bytes.extend_from_slice(name);
+
+    let dir_offset = bytes.len() as u32;
+    bytes.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+    bytes.extend_from_slice(&[0; 16]);
+    bytes.extend_from_slice(&[0xff; 8]);
+    bytes.extend_from_slice(&(name.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(&20u16.to_le_bytes());
+    bytes.extend_from_slice(&[0; 14]);
+    bytes.extend_from_slice(name);
+    bytes.extend_from_slice(&[1, 0, 16, 0]);
+    bytes.extend_from_slice(&size.to_le_bytes());
+    bytes.extend_from_slice(&size.to_le_bytes());
+
+    let actual_dir_size = bytes.len() as u32 - dir_offset;
+    bytes.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+    bytes.extend_from_slice(&[0, 0, 0, 0, 1, 0, 1, 0]);
+    bytes.extend_from_slice(&dir_size.unwrap_or(actual_dir_size)
+                                     .to_le_bytes());
+    bytes.extend_from_slice(&dir_offset.to_le_bytes());
+    bytes.extend_from_slice(&[0; 2]);
+    bytes
+}
+
+#[test]
+fn test_read_errors() {
+    let bytes = npy_bytes( "{'descr': '<f8', 'fortran_order': False, \
+                             'shape': (1,), }"
+                         , &[0; 8]);
+    match read_matrix::<_, f64>(&bytes[..]) {
+        Err(Error::WrongShape { ref found }) if *found == [1] => {}
+      , other => panic!("unexpected result {:?}", other)
+    }
+    match read_matrix::<_, f32>(&bytes[..]) {
+        Err(Error::WrongShape { .. }) => {}
+      , other => panic!("unexpected result {:?}", other)
+    }
+    let bytes = npy_bytes( "{'descr': '<f8', 'fortran_order': False, \
+                             'shape': (1, 1), }"
+                         , &[0; 8]);
+    match read_matrix::<_, i64>(&bytes[..]) {
+        Err(Error::WrongDtype { expected: "i8", ref found })
+            if found == "<f8" => {}
+      , other => panic!("unexpected result {:?}", other)
+    }
+    let bytes = npy_bytes( "{'descr': '<c16', 'fortran_order': False, \
+                             'shape': (1, 1), }"
+                         , &[0; 16]);
+    match read_matrix::<_, f64>(&bytes[..]) {
+        Err(Error::UnsupportedDtype { ref descr }) if descr == "<c16" => {}
+      , other => panic!("unexpected result {:?}", other)
+    }
+    match read_matrix::<_, f64>(&b"PK\x03\x04 not an npy file"[..]) {
+        Err(Error::InvalidMagic) => {}
+      , other => panic!("unexpected result {:?}", other)
+    }
+}
+
+#[test]
+fn test_read_rejects_huge_shapes() {
+    // the number of elements overflows a `usize`
+    let bytes = npy_bytes( "{'descr': '<f8', 'fortran_order': False, \
+                             'shape': (4294967296, 4294967296), }"
+                         , &[]);
+    match read_matrix::<_, f64>(&bytes[..]) {
+        Err(Error::InvalidHeader) => {}
+      , other => panic!("unexpected result {:?}", other)
+    }
+    // the number of bytes overflows a `usize`
+    let bytes = npy_bytes( "{'descr': '<f8', 'fortran_order': False, \
+                             'shape': (4294967296, 1073741824), }"
+                         , &[]);
+    match read_matrix::<_, f64>(&bytes[..]) {
+        Err(Error::InvalidHeader) => {}
+      , other => panic!("unexpected result {:?}", other)
+    }
+    // the data is much shorter than the header claims
+    let bytes = npy_bytes( "{'descr': '<f8', 'fortran_order': False, \
+                             'shape': (1000000000, 1000), }"
+                         , &[0; 8]);
+    match read_matrix::<_, f64>(&bytes[..]) {
+        Err(Error::Io(ref err))
+            if err.kind() == io::ErrorKind::UnexpectedEof => {}
+      , other => panic!("unexpected result {:?}", other)
+    }
+}
+
+#[test]
+fn test_read_numpy_style_npz() {
+    // written with Python's zipfile using `force_zip64`, as `numpy.savez`
+    // does.
+    let archive = &include_bytes!("fixtures/numpy_style.npz")[..];
+    let mut npz = NpzReader::new(Cursor::new(archive)).unwrap();
+    assert_eq!(npz.names(), vec!["m", "points"]);
+    let m = npz.read_matrix::<f64>("m").unwrap();
+    assert_eq!(m.as_slice(), &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let points: Vec<Vector3<i32>> = npz.read_vectors("points").unwrap();
+    assert_eq!(points, vec![ Vector3 { x: 1, y: 2, z: 3 }
+                           , Vector3 { x: -4, y: -5, z: -6 } ]);
+}
+
+#[test]
+fn test_read_rejects_huge_npz_entries() {
+    let bytes = zip64_npz_bytes(u64::MAX / 2, None);
+    let mut npz = NpzReader::new(Cursor::new(&bytes[..])).unwrap();
+    assert_eq!(npz.names(), vec!["m"]);
+    match npz.read_matrix::<f64>("m") {
+        Err(Error::Io(ref err))
+            if err.kind() == io::ErrorKind::InvalidData => {}
+      , other => panic!("unexpected result {:?}", other)
+    }
+    let bytes = zip64_npz_bytes(0, Some(u32::MAX));
+    match NpzReader::new(Cursor::new(&bytes[..])) {
+        Err(Error::Io(ref err))
+            if err.kind() == io::ErrorKind::InvalidData => {}
+      , Err(other) => panic!("unexpected error {:?}", other)
+      , Ok(_) => panic!("read a directory past the end of the archive")
+    }
+}
+
+#[test]
+fn test_npz_round_trip() {
+    let m = MatrixN::from_fn(3, 2, |x, y| (x * 10 + y) as i64);
+    let points = vec![Vector2 { x: 0.5f64, y: -0.25 }];
+    let mut npz = NpzWriter::new(Vec::new());
+    npz.add_matrix("m", &m).unwrap();
+    npz.add_vectors("points", &points).unwrap();
+    let archive = npz.finish().unwrap();
+
+    let mut npz = NpzReader::new(Cursor::new(archive)).unwrap();
+    assert_eq!(npz.read_matrix::<i64>("m").unwrap(), m);
+    let read: Vec<Vector2<f64>> = npz.read_vectors("points").unwrap();
+    assert_eq!(read, points);
+    match npz.read_matrix::<i64>("missing") {
+        Err(Error::MissingArray { ref name }) if name == "missing" => {}
+      , other => panic!("unexpected result {:?}", other)
+    }
+}
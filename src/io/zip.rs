@@ -0,0 +1,269 @@
+//! A minimal reader and writer for uncompressed zip archives.
+//!
+//! This supports only what is needed for `.npz` files: entries which are
+//! stored without compression, in archives small enough not to need the
+//! zip64 end of central directory record. Zip64 extra fields on individual
+//! entries, which NumPy always writes, are understood.
+
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+const LOCAL_HEADER: u32 = 0x0403_4b50;
+const CENTRAL_HEADER: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIRECTORY: u32 = 0x0605_4b50;
+const ZIP64_EXTRA: u16 = 0x0001;
+
+/// The compression method for entries stored without compression
+pub const STORED: u16 = 0;
+
+/// An entry in the central directory of an archive.
+#[derive(Clone, Debug)]
+pub struct Entry { pub name: String
+                 , pub method: u16
+                 , pub crc: u32
+                 , pub size: u64
+                 , pub offset: u64
+                 }
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new( io::ErrorKind::InvalidData
+                  , format!("malformed zip archive: {}", message))
+}
+
+#[inline]
+fn u16_at(bytes: &[u8], i: usize) -> u16 {
+    u16::from(bytes[i]) | u16::from(bytes[i + 1]) << 8
+}
+
+#[inline]
+fn u32_at(bytes: &[u8], i: usize) -> u32 {
+    u32::from(u16_at(bytes, i)) | u32::from(u16_at(bytes, i + 2)) << 16
+}
+
+#[inline]
+fn u64_at(bytes: &[u8], i: usize) -> u64 {
+    u64::from(u32_at(bytes, i)) | u64::from(u32_at(bytes, i + 4)) << 32
+}
+
+/// Computes the CRC-32 checksum used by zip archives.
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 }
+                  else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+/// Reads exactly `len` bytes, without trusting `len` enough to allocate it
+/// all up front.
+fn read_bytes<R>(reader: &mut R, len: u64) -> io::Result<Vec<u8>>
+where R: Read {
+    let mut bytes = Vec::new();
+    reader.by_ref().take(len).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 != len {
+        return Err(io::ErrorKind::UnexpectedEof.into())
+    }
+    Ok(bytes)
+}
+
+/// Reads the central directory of an archive.
+pub fn read_entries<R>(reader: &mut R) -> io::Result<Vec<Entry>>
+where R: Read + Seek {
+    // the end of central directory record is 22 bytes long, followed by a
+    // comment of at most 65535 bytes.
+    let len = reader.seek(SeekFrom::End(0))?;
+    let tail_len = ::std::cmp::min(len, 22 + 0xffff);
+    reader.seek(SeekFrom::Start(len - tail_len))?;
+    let mut tail = vec![0; tail_len as usize];
+    reader.read_exact(&mut tail)?;
+    let end = (0..tail.len().saturating_sub(21)).rev()
+        .find(|&i| u32_at(&tail, i) == END_OF_CENTRAL_DIRECTORY)
+        .ok_or_else(|| invalid("no end of central directory record"))?;
+    let count = u16_at(&tail, end + 10) as usize;
+    let dir_size = u64::from(u32_at(&tail, end + 12));
+    let dir_offset = u64::from(u32_at(&tail, end + 16));
+    if dir_offset > len || dir_size > len - dir_offset {
+        return Err(invalid("central directory past the end of the archive"))
+    }
+
+    reader.seek(SeekFrom::Start(dir_offset))?;
+    let dir = read_bytes(reader, dir_size)?;
+
+    // each entry takes at least 46 bytes of the directory
+    let capacity = ::std::cmp::min(count, dir.len() / 46);
+    let mut entries = Vec::with_capacity(capacity);
+    let mut i = 0;
+    for _ in 0..count {
+        if i + 46 > dir.len() || u32_at(&dir, i) != CENTRAL_HEADER {
+            return Err(invalid("bad central directory entry"))
+        }
+        let name_len = u16_at(&dir, i + 28) as usize;
+        let extra_len = u16_at(&dir, i + 30) as usize;
+        let comment_len = u16_at(&dir, i + 32) as usize;
+        let name_start = i + 46;
+        let extra_start = name_start + name_len;
+        let next = extra_start + extra_len + comment_len;
+        if next > dir.len() {
+            return Err(invalid("bad central directory entry"))
+        }
+        let name = String::from_utf8(dir[name_start..extra_start].to_vec())
+            .map_err(|_| invalid("entry name is not UTF-8"))?;
+
+        let mut size = u64::from(u32_at(&dir, i + 20));
+        let mut uncompressed = u64::from(u32_at(&dir, i + 24));
+        let mut offset = u64::from(u32_at(&dir, i + 42));
+        // values which do not fit in 32 bits are replaced by 0xffffffff,
+        // and stored in the zip64 extra field in this order.
+        let extra = &dir[extra_start..extra_start + extra_len];
+        let mut j = 0;
+        while j + 4 <= extra.len() {
+            let (id, len) = (u16_at(extra, j), u16_at(extra, j + 2) as usize);
+            if j + 4 + len > extra.len() { break }
+            if id == ZIP64_EXTRA {
+                let mut k = j + 4;
+                for value in &mut [&mut uncompressed, &mut size, &mut offset] {
+                    if **value == 0xffff_ffff && k + 8 <= j + 4 + len {
+                        **value = u64_at(extra, k);
+                        k += 8;
+                    }
+                }
+            }
+            j += 4 + len;
+        }
+
+        entries.push(Entry { name
+                           , method: u16_at(&dir, i + 10)
+                           , crc: u32_at(&dir, i + 16)
+                           , size
+                           , offset
+                           });
+        i = next;
+    }
+    Ok(entries)
+}
+
+/// Reads the data of an entry, checking its checksum.
+///
+/// The entry is returned exactly as it is stored, so this should only be
+/// called for entries whose method is `STORED`.
+pub fn read_entry<R>(reader: &mut R, entry: &Entry) -> io::Result<Vec<u8>>
+where R: Read + Seek {
+    let mut header = [0; 30];
+    reader.seek(SeekFrom::Start(entry.offset))?;
+    reader.read_exact(&mut header)?;
+    if u32_at(&header, 0) != LOCAL_HEADER {
+        return Err(invalid("bad local file header"))
+    }
+    let skip = u16_at(&header, 26) as i64 + u16_at(&header, 28) as i64;
+    let start = reader.seek(SeekFrom::Current(skip))?;
+    // the size comes from the archive, so it is checked against what is
+    // actually there before anything is allocated.
+    let len = reader.seek(SeekFrom::End(0))?;
+    if start > len || entry.size > len - start {
+        return Err(invalid(&format!("`{}` extends past the end of the archive"
+                                   , entry.name)))
+    }
+    reader.seek(SeekFrom::Start(start))?;
+    let data = read_bytes(reader, entry.size)?;
+    if crc32(&data) != entry.crc {
+        return Err(invalid(&format!("checksum mismatch in `{}`", entry.name)))
+    }
+    Ok(data)
+}
+
+/// Writes a stored entry at `offset` bytes into the archive.
+///
+/// # Returns:
+///   - The central directory entry describing what was written, whose
+///     `size` together with the header length gives the offset of the next
+///     entry
+///
+pub fn write_entry<W>(writer: &mut W, offset: u64, name: &str, data: &[u8])
+                     -> io::Result<Entry>
+where W: Write {
+    if offset > 0xffff_ffff || data.len() as u64 > 0xffff_ffff {
+        return Err(io::Error::new( io::ErrorKind::InvalidInput
+                                 , "archive too large without zip64"))
+    }
+    let entry = Entry { name: name.to_owned()
+                      , method: STORED
+                      , crc: crc32(data)
+                      , size: data.len() as u64
+                      , offset
+                      };
+    let mut header = Vec::with_capacity(30 + name.len());
+    put_u32(&mut header, LOCAL_HEADER);
+    put_common(&mut header, &entry);
+    put_u16(&mut header, 0); // extra field length
+    header.extend_from_slice(name.as_bytes());
+    writer.write_all(&header)?;
+    writer.write_all(data)?;
+    Ok(entry)
+}
+
+/// Returns the number of bytes written by `write_entry`.
+#[inline]
+pub fn entry_len(entry: &Entry) -> u64 {
+    30 + entry.name.len() as u64 + entry.size
+}
+
+/// Writes the central directory, which begins `offset` bytes into the
+/// archive, and the end of central directory record.
+pub fn write_directory<W>(writer: &mut W, offset: u64, entries: &[Entry])
+                         -> io::Result<()>
+where W: Write {
+    let mut dir = Vec::new();
+    for entry in entries {
+        put_u32(&mut dir, CENTRAL_HEADER);
+        put_u16(&mut dir, 20); // version made by
+        put_common(&mut dir, entry);
+        put_u16(&mut dir, 0); // extra field length
+        put_u16(&mut dir, 0); // comment length
+        put_u16(&mut dir, 0); // disk number
+        put_u16(&mut dir, 0); // internal attributes
+        put_u32(&mut dir, 0); // external attributes
+        put_u32(&mut dir, entry.offset as u32);
+        dir.extend_from_slice(entry.name.as_bytes());
+    }
+    if entries.len() > 0xffff || offset > 0xffff_ffff {
+        return Err(io::Error::new( io::ErrorKind::InvalidInput
+                                 , "archive too large without zip64"))
+    }
+    let dir_len = dir.len() as u32;
+    put_u32(&mut dir, END_OF_CENTRAL_DIRECTORY);
+    put_u16(&mut dir, 0); // this disk
+    put_u16(&mut dir, 0); // disk containing the central directory
+    put_u16(&mut dir, entries.len() as u16);
+    put_u16(&mut dir, entries.len() as u16);
+    put_u32(&mut dir, dir_len);
+    put_u32(&mut dir, offset as u32);
+    put_u16(&mut dir, 0); // comment length
+    writer.write_all(&dir)
+}
+
+/// Writes the fields shared by local and central headers, from the version
+/// needed to extract up to the file name length.
+fn put_common(out: &mut Vec<u8>, entry: &Entry) {
+    put_u16(out, 20); // version needed to extract
+    put_u16(out, 0); // flags
+    put_u16(out, entry.method);
+    put_u16(out, 0); // modification time
+    put_u16(out, 0x21); // modification date: 1980-01-01
+    put_u32(out, entry.crc);
+    put_u32(out, entry.size as u32);
+    put_u32(out, entry.size as u32);
+    put_u16(out, entry.name.len() as u16);
+}
+
+#[inline]
+fn put_u16(out: &mut Vec<u8>, value: u16) {
+    out.extend_from_slice(&value.to_le_bytes())
+}
+
+#[inline]
+fn put_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_le_bytes())
+}
@@ -0,0 +1,82 @@
+//! Support for property tests checking that arithmetic on vectors and
+//! matrices obeys the usual algebraic laws.
+//!
+//! The laws themselves are generated by `additive_laws!` and
+//! `product_laws!`, each of which expands to a module of `quickcheck` tests
+//! for a single type and element type, so the same laws can be checked over
+//! integers and floats alike, and for types defined in other crates with
+//! `make_vector!` or `make_matrix!`:
+//!
+//! ```ignore
+//! additive_laws! { v3_f64, Vector3, f64 }
+//! product_laws! { m4_i64, Matrix4, i64, 4 }
+//! ```
+//!
+//! Arbitrary elements are first mapped into a small range with
+//! `Element::tame`, so that integer arithmetic cannot overflow and float
+//! arithmetic stays finite. Floats are then compared with a tolerance
+//! proportional to the magnitude of the values involved.
+
+use std::fmt::Debug;
+use std::ops::{Add, Sub, Mul};
+
+use quickcheck::Arbitrary;
+
+/// An element type over which the laws can be checked.
+pub trait Element: Arbitrary + Copy + Debug
+                 + Add<Output = Self>
+                 + Sub<Output = Self>
+                 + Mul<Output = Self> {
+
+    /// Maps an arbitrary value into the range the laws are checked over
+    fn tame(self) -> Self;
+
+    /// Returns the absolute value, as an `f64`
+    fn magnitude(self) -> f64;
+
+    /// Returns true if `a` and `b` are equal, allowing for rounding errors
+    /// in computations whose terms have magnitude up to `scale`
+    fn close(a: Self, b: Self, scale: f64) -> bool;
+}
+
+macro_rules! impl_element_int {
+    ($($t: ty),+) => { $(
+        impl Element for $t {
+            // small enough that the determinant of a product of two 4x4
+            // matrices fits in an `i32`.
+            #[inline] fn tame(self) -> $t { self % 5 }
+            #[inline] fn magnitude(self) -> f64 { (self as f64).abs() }
+            #[inline] fn close(a: $t, b: $t, _: f64) -> bool { a == b }
+        }
+    )+ }
+}
+
+macro_rules! impl_element_float {
+    ($($t: ident),+) => { $(
+        impl Element for $t {
+            #[inline] fn tame(self) -> $t {
+                if self.is_finite() { self % 10.0 } else { 0.0 }
+            }
+            #[inline] fn magnitude(self) -> f64 { (self as f64).abs() }
+            #[inline] fn close(a: $t, b: $t, scale: f64) -> bool {
+                let tolerance = $t::EPSILON as f64 * 256.0 * (scale + 1.0);
+                (a - b).magnitude() <= tolerance
+            }
+        }
+    )+ }
+}
+
+impl_element_int! { i32, i64 }
+impl_element_float! { f32, f64 }
+
+/// Returns the largest magnitude of any element.
+pub fn norm<N>(elems: &[N]) -> f64
+where N: Element {
+    elems.iter().map(|e| e.magnitude()).fold(0.0, f64::max)
+}
+
+/// Returns true if each pair of corresponding elements is `close`.
+pub fn all_close<N>(a: &[N], b: &[N], scale: f64) -> bool
+where N: Element {
+    a.iter().zip(b).all(|(&a, &b)| N::close(a, b, scale))
+}
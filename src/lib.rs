@@ -1,40 +1,66 @@
 #![crate_name = "lin"]
 #![crate_type = "lib"]
 
-#![cfg_attr(features = "unstable", feature(zero_one))]
-#![cfg_attr(features = "unstable", feature(const_fn))]
-#![cfg_attr(test, feature(plugin))]
-#![cfg_attr(test, plugin(quickcheck_macros))]
-#![feature(test)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
-#[cfg(test)] extern crate test;
-#[cfg(test)] extern crate quickcheck;
-#[cfg(features = "simd")] extern crate simd;
-#[cfg(features = "rand")] extern crate rand;
+// without `std`, its paths refer to `core`, which provides everything this
+// crate needs apart from allocation and the float functions from `libm`.
+#[cfg(not(feature = "std"))] extern crate core as std;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+#[macro_use] extern crate alloc;
+#[cfg(feature = "std")] extern crate std as alloc;
+#[cfg(all(feature = "libm", not(feature = "std")))] extern crate libm;
+// public so that the exported macros can name `core` from other crates,
+// including `no_std` ones
+#[doc(hidden)] pub extern crate core as __core;
 
+// public so that `impl_arbitrary!` can name them from other crates
+#[cfg(any(test, feature = "quickcheck"))]
+#[doc(hidden)] pub extern crate quickcheck;
+#[cfg(any(test, feature = "quickcheck"))]
+#[doc(hidden)] pub extern crate alloc as __alloc;
+#[cfg(test)] #[macro_use] extern crate quickcheck_macros;
+#[cfg(all(test, feature = "serde"))] extern crate serde_test;
+// public so that `impl_rand!` can name it from other crates
+#[cfg(feature = "rand")] #[doc(hidden)] pub extern crate rand;
+#[cfg(feature = "serde")] extern crate serde;
+#[cfg(feature = "bytemuck")] extern crate bytemuck;
+#[cfg(feature = "mint")] extern crate mint;
+
+#[macro_use] mod macros;
+
+#[macro_use] pub mod approx;
+// public so that the law suites can be used from other crates
+#[cfg(any(test, feature = "quickcheck"))] #[doc(hidden)] pub mod laws;
+#[cfg(all(feature = "alloc", any(feature = "std", feature = "libm")))]
+pub mod backend;
+#[cfg(feature = "alloc")]
+pub mod blas;
+pub mod cast;
+#[doc(hidden)]
+pub mod display;
+pub mod error;
+pub mod gpu;
+#[cfg(feature = "mint")]
+mod interop;
+#[cfg(feature = "std")]
+pub mod io;
+#[cfg(feature = "alloc")]
+pub mod parse;
+pub mod pod;
+#[cfg(feature = "serde")]
+pub mod serialization;
+pub mod strided;
+#[cfg(feature = "threads")]
+pub mod threads;
 pub mod vector;
 pub mod matrix;
 
-#[cfg(features = "parallel")]
-mod parallel;
-
 use std::ops::{Add, Sub, Mul, Div, Rem};
+#[cfg(any(feature = "std", feature = "libm"))]
+use std::ops::Neg;
+use std::marker::PhantomData;
 
-#[cfg(features = "unstable")]
-use std::num::Zero;
-
-#[cfg(features = "unstable")]
-pub trait Numeric: PartialEq + PartialOrd
-                             + Add<Self>
-                             + Sub<Self>
-                             + Mul<Self>
-                             + Div<Self>
-                             + Rem<Self>
-                             + Zero
-                             + One
-                             + Sized {}
-
-#[cfg(not(features = "unstable"))]
 pub trait Numeric: PartialEq + PartialOrd
                              + Add<Self>
                              + Sub<Self>
@@ -52,28 +78,156 @@ make_numeric!( u8, u16, u32, u64, usize
              , f32, f64
              );
 
+/// A primitive integer type, supporting arithmetic which handles overflow
+/// explicitly.
+///
+/// This exposes the inherent `checked_*`, `wrapping_*` and `saturating_*`
+/// methods of the primitive integer types, so that vectors and matrices can
+/// provide the same operations for any integer element type.
+pub trait Integer: Numeric + Copy {
+
+    /// Addition returning `None` on overflow
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    /// Subtraction returning `None` on overflow
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    /// Multiplication returning `None` on overflow
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    /// Division returning `None` on overflow or division by zero
+    fn checked_div(self, rhs: Self) -> Option<Self>;
+
+    /// Addition wrapping around at the bounds of the type
+    fn wrapping_add(self, rhs: Self) -> Self;
+    /// Subtraction wrapping around at the bounds of the type
+    fn wrapping_sub(self, rhs: Self) -> Self;
+    /// Multiplication wrapping around at the bounds of the type
+    fn wrapping_mul(self, rhs: Self) -> Self;
+
+    /// Addition clamped to the bounds of the type
+    fn saturating_add(self, rhs: Self) -> Self;
+    /// Subtraction clamped to the bounds of the type
+    fn saturating_sub(self, rhs: Self) -> Self;
+    /// Multiplication clamped to the bounds of the type
+    fn saturating_mul(self, rhs: Self) -> Self;
+}
+
+macro_rules! make_integer {
+    ($($t:ty),*) => { $(
+        impl Integer for $t {
+            #[inline] fn checked_add(self, rhs: $t) -> Option<$t> {
+                <$t>::checked_add(self, rhs)
+            }
+            #[inline] fn checked_sub(self, rhs: $t) -> Option<$t> {
+                <$t>::checked_sub(self, rhs)
+            }
+            #[inline] fn checked_mul(self, rhs: $t) -> Option<$t> {
+                <$t>::checked_mul(self, rhs)
+            }
+            #[inline] fn checked_div(self, rhs: $t) -> Option<$t> {
+                <$t>::checked_div(self, rhs)
+            }
+            #[inline] fn wrapping_add(self, rhs: $t) -> $t {
+                <$t>::wrapping_add(self, rhs)
+            }
+            #[inline] fn wrapping_sub(self, rhs: $t) -> $t {
+                <$t>::wrapping_sub(self, rhs)
+            }
+            #[inline] fn wrapping_mul(self, rhs: $t) -> $t {
+                <$t>::wrapping_mul(self, rhs)
+            }
+            #[inline] fn saturating_add(self, rhs: $t) -> $t {
+                <$t>::saturating_add(self, rhs)
+            }
+            #[inline] fn saturating_sub(self, rhs: $t) -> $t {
+                <$t>::saturating_sub(self, rhs)
+            }
+            #[inline] fn saturating_mul(self, rhs: $t) -> $t {
+                <$t>::saturating_mul(self, rhs)
+            }
+        }
+    )* };
+}
+
+make_integer!( u8, u16, u32, u64, usize
+             , i8, i16, i32, i64, isize
+             );
+
+/// A primitive floating-point type.
+///
+/// Functions such as `sqrt` come from `std`, or from `libm` when building
+/// without `std`, so this trait is only available when one of the `std` or
+/// `libm` features is enabled.
+#[cfg(any(feature = "std", feature = "libm"))]
+pub trait Real: Numeric + Copy
+                          + Add<Output = Self>
+                          + Sub<Output = Self>
+                          + Mul<Output = Self>
+                          + Div<Output = Self>
+                          + Neg<Output = Self> {
+
+    /// Returns the square root, or NaN if `self` is negative
+    fn sqrt(self) -> Self;
+    /// Returns the absolute value
+    fn abs(self) -> Self;
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+macro_rules! make_real {
+    ($($t:ty, $sqrt:ident),*) => { $(
+        impl Real for $t {
+            #[cfg(feature = "std")]
+            #[inline] fn sqrt(self) -> $t { <$t>::sqrt(self) }
+            #[cfg(not(feature = "std"))]
+            #[inline] fn sqrt(self) -> $t { ::libm::$sqrt(self) }
+            #[inline] fn abs(self) -> $t { <$t>::abs(self) }
+        }
+    )* };
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+make_real!(f32, sqrtf, f64, sqrt);
+
 /// Something which can be indexed by column.
+///
+/// Columns need not be stored contiguously: a type may hand out borrowed
+/// columns as references (`&Self::Column`), or as views such as
+/// `strided::Strided` when the elements of a column are spread out in
+/// memory.
 pub trait Columnar: Sized {
+    /// The owned type of a single column
     type Column;
 
+    /// The type of a borrowed column
+    type ColumnRef<'a> where Self: 'a;
+
+    /// The type of a mutably borrowed column
+    type ColumnMut<'a> where Self: 'a;
+
     /// Return the number of columns
     fn ncols(&self) -> usize;
 
     /// Select a column by index
     ///
     /// # Returns:
-    ///   - `Some(&Column)` if a column exists for the given index
+    ///   - `Some(ColumnRef)` if a column exists for the given index
     ///   - `None` if the index is out of bounds
     ///
-    fn column(&self, i: usize) -> Option<&Self::Column>;
+    fn column<'a>(&'a self, i: usize) -> Option<Self::ColumnRef<'a>>;
 
     /// Index a column mutably.
     ///
     /// # Returns:
-    ///   - `Some(&mut Column)` if a column exists for the given index
+    ///   - `Some(ColumnMut)` if a column exists for the given index
     ///   - `None` if the index is out of bounds
     ///
-    fn column_mut(&mut self, i: usize) -> Option<&mut Self::Column>;
+    fn column_mut<'a>(&'a mut self, i: usize) -> Option<Self::ColumnMut<'a>>;
+
+    /// Copy a column out by index.
+    ///
+    /// # Returns:
+    ///   - `Some(Column)` if a column exists for the given index
+    ///   - `None` if the index is out of bounds
+    ///
+    fn column_owned(&self, i: usize) -> Option<Self::Column>;
 
     fn cols_iter<'a>(&'a self) -> ColsIterator<'a, Self> {
         ColsIterator { target: self
@@ -81,13 +235,43 @@ pub trait Columnar: Sized {
                      , max: self.ncols()
         }
     }
+}
+
+/// Something whose columns may all be borrowed mutably at the same time.
+///
+/// Every column is built from a single raw handle, such as a pointer to the
+/// first element, so that handing out one column never reborrows the whole
+/// value and so never invalidates the columns handed out before it.
+///
+/// # Safety
+///   Implementors must guarantee that `column_from_raw` never returns
+///   overlapping references for two different indices. `ColsIterMut` relies
+///   on this to hand out every column at once.
+pub unsafe trait ColumnarMut: Columnar {
+    /// A raw handle from which any column may be built
+    type ColumnsRaw: Copy;
+
+    /// Returns the raw handle from which the columns of `self` are built.
+    fn columns_raw(&mut self) -> Self::ColumnsRaw;
+
+    /// Builds column `i` from a handle returned by `columns_raw`.
+    ///
+    /// # Safety
+    ///   `raw` must have been returned by `columns_raw` on a value which
+    ///   stays mutably borrowed for `'a`, `i` must be less than its number
+    ///   of columns, and no other reference to column `i` may be used for
+    ///   `'a`.
+    unsafe fn column_from_raw<'a>(raw: Self::ColumnsRaw, i: usize)
+                                 -> Self::ColumnMut<'a>
+    where Self: 'a;
 
-    // fn cols_iter_mut<'a>(&'a mut self) -> ColsIterMut<'a, Self> {
-    //     ColsIterMut { target: self
-    //                 , i: 0
-    //                 , max: self.ncols()
-    //     }
-    // }
+    fn cols_iter_mut<'a>(&'a mut self) -> ColsIterMut<'a, Self> {
+        ColsIterMut { max: self.ncols()
+                    , raw: self.columns_raw()
+                    , i: 0
+                    , _lifetime: PhantomData
+        }
+    }
 }
 
 pub struct ColsIterator<'a, C>
@@ -100,7 +284,7 @@ where C: Columnar
 impl<'a, C> Iterator for ColsIterator<'a, C>
 where C: Columnar {
 
-    type Item = &'a C::Column;
+    type Item = C::ColumnRef<'a>;
     fn next(&mut self) -> Option<Self::Item> {
         match self.i {
             col if col < self.max => {
@@ -111,36 +295,83 @@ where C: Columnar {
         }
     }
 
+    #[inline] fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.max - self.i;
+        (len, Some(len))
+    }
+
+}
+
+impl<'a, C> DoubleEndedIterator for ColsIterator<'a, C>
+where C: Columnar {
+
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.max {
+            col if col > self.i => {
+                self.max -= 1;
+                self.target.column(col - 1)
+            }
+          , _ => None
+        }
+    }
+}
+
+impl<'a, C> ExactSizeIterator for ColsIterator<'a, C>
+where C: Columnar {}
+
+pub struct ColsIterMut<'a, C>
+where C: ColumnarMut
+    , C: 'a { raw: C::ColumnsRaw
+            , i: usize
+            , max: usize
+            , _lifetime: PhantomData<&'a mut C>
+            }
+
+impl<'a, C> Iterator for ColsIterMut<'a, C>
+where C: ColumnarMut {
+
+    type Item = C::ColumnMut<'a>;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.i {
+            col if col < self.max => {
+                self.i += 1;
+                // this is safe because `ColumnarMut` guarantees that each
+                // index refers to a distinct column, and each index is only
+                // ever yielded once.
+                Some(unsafe { C::column_from_raw(self.raw, col) })
+            }
+          , _ => None
+        }
+    }
+
+    #[inline] fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.max - self.i;
+        (len, Some(len))
+    }
+
+}
+
+impl<'a, C> DoubleEndedIterator for ColsIterMut<'a, C>
+where C: ColumnarMut {
+
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.max {
+            col if col > self.i => {
+                self.max -= 1;
+                Some(unsafe { C::column_from_raw(self.raw, col - 1) })
+            }
+          , _ => None
+        }
+    }
 }
-//
-//pub struct ColsIterMut<'a, 'b, C>
-//where C: Columnar
-//    , C: 'a
-//    , 'b: 'a
-//    { target: &'b mut C
-//            , i: usize
-//            , max: usize
-//            }
-//
-//impl<'a, 'b, C> Iterator for ColsIterMut<'a, 'b, C>
-//where C: Columnar {
-//
-//    type Item = &'a mut C::Column;
-//    fn next(&'b mut self) -> Option<Self::Item> {
-//        match self.i {
-//            col if col < self.max => {
-//                self.i += 1;
-//                self.target.column_mut(col)
-//            }
-//          , _ => None
-//        }
-//    }
-//
-//}
+
+impl<'a, C> ExactSizeIterator for ColsIterMut<'a, C>
+where C: ColumnarMut {}
 
 /// Something which can be indexed by row.
 pub trait Tabular: Sized {
-    type Row;
+    /// The type of a single row, which may be unsized (such as a slice)
+    type Row: ?Sized;
 
     /// Returns the number of rows
     fn nrows(&self) -> usize;
@@ -169,6 +400,41 @@ pub trait Tabular: Sized {
     }
 }
 
+/// Something whose rows may all be borrowed mutably at the same time.
+///
+/// As with `ColumnarMut`, every row is built from a single raw handle, so
+/// that handing out one row never invalidates the others.
+///
+/// # Safety
+///   Implementors must guarantee that `row_from_raw` never returns
+///   overlapping references for two different indices. `RowsIterMut` relies
+///   on this to hand out every row at once.
+pub unsafe trait TabularMut: Tabular {
+    /// A raw handle from which any row may be built
+    type RowsRaw: Copy;
+
+    /// Returns the raw handle from which the rows of `self` are built.
+    fn rows_raw(&mut self) -> Self::RowsRaw;
+
+    /// Builds row `i` from a handle returned by `rows_raw`.
+    ///
+    /// # Safety
+    ///   `raw` must have been returned by `rows_raw` on a value which stays
+    ///   mutably borrowed for `'a`, `i` must be less than its number of
+    ///   rows, and no other reference to row `i` may be used for `'a`.
+    unsafe fn row_from_raw<'a>(raw: Self::RowsRaw, i: usize)
+                              -> &'a mut Self::Row
+    where Self: 'a;
+
+    fn rows_iter_mut<'a>(&'a mut self) -> RowsIterMut<'a, Self> {
+        RowsIterMut { max: self.nrows()
+                    , raw: self.rows_raw()
+                    , i: 0
+                    , _lifetime: PhantomData
+        }
+    }
+}
+
 pub struct RowsIterator<'a, R>
 where R: Tabular
     , R: 'a { target: &'a R
@@ -190,4 +456,75 @@ where R: Tabular {
         }
     }
 
+    #[inline] fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.max - self.i;
+        (len, Some(len))
+    }
+
+}
+
+impl<'a, R> DoubleEndedIterator for RowsIterator<'a, R>
+where R: Tabular {
+
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.max {
+            row if row > self.i => {
+                self.max -= 1;
+                self.target.row(row - 1)
+            }
+          , _ => None
+        }
+    }
+}
+
+impl<'a, R> ExactSizeIterator for RowsIterator<'a, R>
+where R: Tabular {}
+
+pub struct RowsIterMut<'a, R>
+where R: TabularMut
+    , R: 'a { raw: R::RowsRaw
+            , i: usize
+            , max: usize
+            , _lifetime: PhantomData<&'a mut R>
+            }
+
+impl<'a, R> Iterator for RowsIterMut<'a, R>
+where R: TabularMut {
+
+    type Item = &'a mut R::Row;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.i {
+            row if row < self.max => {
+                self.i += 1;
+                // this is safe because `TabularMut` guarantees that each
+                // index refers to a distinct row, and each index is only
+                // ever yielded once.
+                Some(unsafe { R::row_from_raw(self.raw, row) })
+            }
+          , _ => None
+        }
+    }
+
+    #[inline] fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.max - self.i;
+        (len, Some(len))
+    }
+
 }
+
+impl<'a, R> DoubleEndedIterator for RowsIterMut<'a, R>
+where R: TabularMut {
+
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.max {
+            row if row > self.i => {
+                self.max -= 1;
+                Some(unsafe { R::row_from_raw(self.raw, row - 1) })
+            }
+          , _ => None
+        }
+    }
+}
+
+impl<'a, R> ExactSizeIterator for RowsIterMut<'a, R>
+where R: TabularMut {}
@@ -0,0 +1,857 @@
+//! Macros shared between the vector and matrix types.
+//!
+//! `make_vector!` and `make_matrix!` are exported for use in other crates,
+//! so every macro they expand to is exported too, hidden from the
+//! documentation, and invoked through `$crate`.
+
+/// Expands to an expression, so that an operator passed as a token tree can
+/// be applied to its operands.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __lin_expr { ($e:expr) => { $e } }
+
+/// Expands to the sum of one or more expressions.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __lin_sum {
+    ($x:expr) => { $x };
+    ($x:expr, $($y:expr),+) => { $x + $crate::__lin_sum!($($y),+) }
+}
+
+/// Implements iteration over the elements of a fixed-size type.
+///
+/// This provides `iter()`, `iter_mut()`, `map()`, `zip_map()` and
+/// `try_from_iter()`, as well as `IntoIterator`, `FromIterator`, `Sum` and
+/// `Product`.
+/// Elements are always visited in the order in which the subscripts are
+/// declared, which for matrices is row-major order.
+///
+/// # Arguments
+///    - `$ty`: The name of the type to implement iteration for
+///    - `$len`: The total number of elements in the type
+///    - `$sub`: The name of each subscript or element of the type
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_iter {
+    ($ty: ident, $len: expr, $($sub: ident),+) => {
+        impl<N> $ty<N> {
+
+            /// Returns an iterator over references to the elements.
+            #[inline]
+            pub fn iter(&self) -> $crate::__core::slice::Iter<'_, N> {
+                self.as_elems().iter()
+            }
+
+            /// Returns an iterator over mutable references to the elements.
+            #[inline]
+            pub fn iter_mut(&mut self)
+                           -> $crate::__core::slice::IterMut<'_, N> {
+                self.as_elems_mut().iter_mut()
+            }
+
+            /// Applies `f` to each element, returning a new value of the same
+            /// shape containing the results.
+            #[inline]
+            pub fn map<M, F>(self, mut f: F) -> $ty<M>
+            where F: FnMut(N) -> M {
+                $ty { $($sub: f(self.$sub)),+ }
+            }
+
+            /// Applies `f` to each pair of corresponding elements in `self`
+            /// and `other`, returning a new value containing the results.
+            #[inline]
+            pub fn zip_map<M, O, F>(self, other: $ty<M>, mut f: F) -> $ty<O>
+            where F: FnMut(N, M) -> O {
+                $ty { $($sub: f(self.$sub, other.$sub)),+ }
+            }
+
+            /// Collects an iterator into a new value.
+            ///
+            /// # Returns:
+            ///   - `Some(Self)` if the iterator yielded exactly as many
+            ///     elements as `Self` has
+            ///   - `None` if the iterator yielded too few or too many elements
+            ///
+            pub fn try_from_iter<I>(iter: I) -> Option<Self>
+            where I: IntoIterator<Item = N> {
+                let mut iter = iter.into_iter();
+                let result = $ty { $($sub: iter.next()?),+ };
+                if iter.next().is_some() { None } else { Some(result) }
+            }
+        }
+
+        impl<N> $crate::__core::iter::IntoIterator for $ty<N> {
+            type Item = N;
+            type IntoIter = $crate::__core::array::IntoIter<N, { $len }>;
+
+            #[inline] fn into_iter(self) -> Self::IntoIter {
+                $crate::__core::iter::IntoIterator::into_iter([$(self.$sub),+])
+            }
+        }
+
+        impl<'a, N> $crate::__core::iter::IntoIterator for &'a $ty<N> {
+            type Item = &'a N;
+            type IntoIter = $crate::__core::slice::Iter<'a, N>;
+
+            #[inline] fn into_iter(self) -> Self::IntoIter { self.iter() }
+        }
+
+        impl<'a, N> $crate::__core::iter::IntoIterator for &'a mut $ty<N> {
+            type Item = &'a mut N;
+            type IntoIter = $crate::__core::slice::IterMut<'a, N>;
+
+            #[inline] fn into_iter(self) -> Self::IntoIter { self.iter_mut() }
+        }
+
+        /// # Panics
+        /// If the iterator does not yield exactly as many elements as
+        /// `Self` has. Use `try_from_iter` to handle this case instead.
+        impl<N> $crate::__core::iter::FromIterator<N> for $ty<N> {
+            fn from_iter<I>(iter: I) -> Self
+            where I: IntoIterator<Item = N> {
+                $ty::try_from_iter(iter)
+                    .expect(concat!( "iterator length did not match the "
+                                   , "number of elements in "
+                                   , stringify!($ty)))
+            }
+        }
+
+        impl<N> $crate::__core::iter::Sum for $ty<N>
+        where N: $crate::__core::ops::Add<Output = N>
+            , N: Default {
+
+            fn sum<I>(iter: I) -> Self
+            where I: Iterator<Item = Self> {
+                iter.fold( $ty::default()
+                         , |acc, x| $ty { $($sub: acc.$sub + x.$sub),+ })
+            }
+        }
+
+        impl<'a, N> $crate::__core::iter::Sum<&'a $ty<N>> for $ty<N>
+        where N: $crate::__core::ops::Add<Output = N>
+            , N: Default
+            , N: Copy {
+
+            fn sum<I>(iter: I) -> Self
+            where I: Iterator<Item = &'a $ty<N>> {
+                iter.fold( $ty::default()
+                         , |acc, x| $ty { $($sub: acc.$sub + x.$sub),+ })
+            }
+        }
+
+        /// The product of no values has every element equal to the product
+        /// of no elements, which is one for the primitive types.
+        impl<N> $crate::__core::iter::Product for $ty<N>
+        where N: $crate::__core::ops::Mul<Output = N>
+            , N: $crate::__core::iter::Product {
+
+            fn product<I>(iter: I) -> Self
+            where I: Iterator<Item = Self> {
+                let one = || N::product($crate::__core::iter::empty::<N>());
+                iter.fold( $ty { $($sub: one()),+ }
+                         , |acc, x| $ty { $($sub: acc.$sub * x.$sub),+ })
+            }
+        }
+
+        impl<'a, N> $crate::__core::iter::Product<&'a $ty<N>> for $ty<N>
+        where N: $crate::__core::ops::Mul<Output = N>
+            , N: $crate::__core::iter::Product
+            , N: Copy {
+
+            fn product<I>(iter: I) -> Self
+            where I: Iterator<Item = &'a $ty<N>> {
+                let one = || N::product($crate::__core::iter::empty::<N>());
+                iter.fold( $ty { $($sub: one()),+ }
+                         , |acc, x| $ty { $($sub: acc.$sub * x.$sub),+ })
+            }
+        }
+    }
+}
+
+/// Implements `ApproxEq` for a fixed-size type by comparing each element.
+///
+/// Two values are approximately equal if every pair of corresponding
+/// elements is approximately equal, using the same tolerances.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_approx_eq {
+    ($ty: ident, $($sub: ident),+) => {
+        impl<N> $crate::approx::ApproxEq for $ty<N>
+        where N: $crate::approx::ApproxEq {
+            type Epsilon = N::Epsilon;
+
+            #[inline] fn default_epsilon() -> N::Epsilon {
+                N::default_epsilon()
+            }
+
+            #[inline] fn default_max_relative() -> N::Epsilon {
+                N::default_max_relative()
+            }
+
+            #[inline] fn default_max_ulps() -> u32 { N::default_max_ulps() }
+
+            #[inline]
+            fn abs_diff_eq(&self, other: &Self, epsilon: N::Epsilon) -> bool {
+                $(self.$sub.abs_diff_eq(&other.$sub, epsilon))&&+
+            }
+
+            #[inline]
+            fn relative_eq( &self, other: &Self
+                          , epsilon: N::Epsilon
+                          , max_relative: N::Epsilon) -> bool {
+                $(self.$sub.relative_eq(&other.$sub, epsilon, max_relative))&&+
+            }
+
+            #[inline]
+            fn ulps_eq( &self, other: &Self
+                      , epsilon: N::Epsilon
+                      , max_ulps: u32) -> bool {
+                $(self.$sub.ulps_eq(&other.$sub, epsilon, max_ulps))&&+
+            }
+        }
+    }
+}
+
+/// Implements overflow-aware element-wise arithmetic for a fixed-size type
+/// with integer elements.
+///
+/// For each of `checked`, `wrapping` and `saturating`, this provides
+/// `_add` and `_sub` of two values of the type, and `_mul` by a scalar.
+/// `checked_div` by a scalar is also provided.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_int_arith {
+    ($ty: ident, $($sub: ident),+) => {
+        impl<N> $ty<N>
+        where N: $crate::Integer {
+
+            /// Element-wise addition, returning `None` if any element
+            /// overflows.
+            #[inline]
+            pub fn checked_add(self, rhs: Self) -> Option<Self> {
+                Some($ty { $($sub: $crate::Integer::checked_add( self.$sub
+                                                               , rhs.$sub)?),+
+                         })
+            }
+
+            /// Element-wise subtraction, returning `None` if any element
+            /// overflows.
+            #[inline]
+            pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+                Some($ty { $($sub: $crate::Integer::checked_sub( self.$sub
+                                                               , rhs.$sub)?),+
+                         })
+            }
+
+            /// Multiplication by a scalar, returning `None` if any element
+            /// overflows.
+            #[inline]
+            pub fn checked_mul(self, rhs: N) -> Option<Self> {
+                Some($ty { $($sub: $crate::Integer::checked_mul( self.$sub
+                                                               , rhs)?),+
+                         })
+            }
+
+            /// Division by a scalar, returning `None` if `rhs` is zero or any
+            /// element overflows.
+            #[inline]
+            pub fn checked_div(self, rhs: N) -> Option<Self> {
+                Some($ty { $($sub: $crate::Integer::checked_div( self.$sub
+                                                               , rhs)?),+
+                         })
+            }
+
+            /// Element-wise addition, wrapping around on overflow.
+            #[inline]
+            pub fn wrapping_add(self, rhs: Self) -> Self {
+                $ty { $($sub: $crate::Integer::wrapping_add( self.$sub
+                                                           , rhs.$sub)),+ }
+            }
+
+            /// Element-wise subtraction, wrapping around on overflow.
+            #[inline]
+            pub fn wrapping_sub(self, rhs: Self) -> Self {
+                $ty { $($sub: $crate::Integer::wrapping_sub( self.$sub
+                                                           , rhs.$sub)),+ }
+            }
+
+            /// Multiplication by a scalar, wrapping around on overflow.
+            #[inline]
+            pub fn wrapping_mul(self, rhs: N) -> Self {
+                $ty { $($sub: $crate::Integer::wrapping_mul(self.$sub, rhs)),+ }
+            }
+
+            /// Element-wise addition, clamping each element to the bounds of
+            /// `N`.
+            #[inline]
+            pub fn saturating_add(self, rhs: Self) -> Self {
+                $ty { $($sub: $crate::Integer::saturating_add( self.$sub
+                                                             , rhs.$sub)),+ }
+            }
+
+            /// Element-wise subtraction, clamping each element to the bounds
+            /// of `N`.
+            #[inline]
+            pub fn saturating_sub(self, rhs: Self) -> Self {
+                $ty { $($sub: $crate::Integer::saturating_sub( self.$sub
+                                                             , rhs.$sub)),+ }
+            }
+
+            /// Multiplication by a scalar, clamping each element to the
+            /// bounds of `N`.
+            #[inline]
+            pub fn saturating_mul(self, rhs: N) -> Self {
+                $ty { $($sub: $crate::Integer::saturating_mul( self.$sub
+                                                             , rhs)),+ }
+            }
+        }
+    }
+}
+
+/// Implements element type conversions for a fixed-size type.
+///
+/// This provides `cast()` and `try_cast()`, as well as `From` impls for
+/// every conversion between primitive types which `From` already provides,
+/// such as `$ty<f32>` to `$ty<f64>` or `$ty<i16>` to `$ty<i32>`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_cast {
+    (@pair $ty: ident, ($($sub: ident),+), $from: ty, $to: ty) => {
+        impl From<$ty<$from>> for $ty<$to> {
+            #[inline] fn from(v: $ty<$from>) -> Self {
+                $ty { $($sub: <$to>::from(v.$sub)),+ }
+            }
+        }
+    };
+    (@from $ty: ident, $subs: tt, $from: ty; $($to: ty),+) => {
+        $( $crate::impl_cast! { @pair $ty, $subs, $from, $to } )+
+    };
+    ($ty: ident, $($sub: ident),+) => {
+        impl<N> $ty<N> {
+
+            /// Converts each element to `M` with the semantics of `as`.
+            #[inline]
+            pub fn cast<M>(self) -> $ty<M>
+            where N: $crate::cast::LossyCast<M> {
+                $ty { $($sub: $crate::cast::LossyCast::lossy_cast(self.$sub)),+ }
+            }
+
+            /// Converts each element to `M`, checking that it is in range.
+            ///
+            /// # Returns:
+            ///   - `Some($ty<M>)` if every element lies within the range of
+            ///     `M`
+            ///   - `None` if any element is out of range
+            ///
+            #[inline]
+            pub fn try_cast<M>(self) -> Option<$ty<M>>
+            where N: $crate::cast::CheckedCast<M> {
+                Some($ty { $($sub: $crate::cast::CheckedCast::checked_cast(
+                                        self.$sub)?),+
+                         })
+            }
+        }
+
+        $crate::impl_cast! { @from $ty, ($($sub),+), u8; u16, u32, u64, usize
+                                               , i16, i32, i64, isize
+                                               , f32, f64 }
+        $crate::impl_cast! { @from $ty, ($($sub),+), u16; u32, u64, usize
+                                                , i32, i64, f32, f64 }
+        $crate::impl_cast! { @from $ty, ($($sub),+), u32; u64, i64, f64 }
+        $crate::impl_cast! { @from $ty, ($($sub),+), i8; i16, i32, i64, isize
+                                               , f32, f64 }
+        $crate::impl_cast! { @from $ty, ($($sub),+), i16; i32, i64, isize
+                                                , f32, f64 }
+        $crate::impl_cast! { @from $ty, ($($sub),+), i32; i64, f64 }
+        $crate::impl_cast! { @from $ty, ($($sub),+), f32; f64 }
+    }
+}
+
+/// Implements conversions which rely on a fixed-size type having the same
+/// layout as an array of its elements.
+///
+/// Every such conversion refers to `ARRAY_LAYOUT`, which fails to compile if
+/// `$ty<N>` does not have the same size and alignment as `[N; $len]`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_layout {
+    ($ty: ident, $len: expr) => {
+        impl<N> $ty<N> {
+
+            /// Asserts that `$ty<N>` may be reinterpreted as `[N; $len]`.
+            const ARRAY_LAYOUT: () = assert!(
+                $crate::__core::mem::size_of::<$ty<N>>()
+                    == $len * $crate::__core::mem::size_of::<N>()
+                && $crate::__core::mem::align_of::<$ty<N>>()
+                    == $crate::__core::mem::align_of::<N>()
+              , concat!(stringify!($ty), " does not have the layout of an array")
+            );
+
+            /// Copies the elements of `slice` into a new value.
+            ///
+            /// # Returns:
+            ///   - `Ok(Self)` if `slice` contains exactly as many elements as
+            ///     `Self`
+            ///   - `Err(LengthError)` otherwise
+            ///
+            pub fn from_slice(slice: &[N])
+                             -> Result<Self, $crate::error::LengthError>
+            where N: Copy {
+                if slice.len() != $len {
+                    return Err($crate::error::LengthError::Exact {
+                        expected: $len, found: slice.len()
+                    })
+                }
+                let () = Self::ARRAY_LAYOUT;
+                let ptr = slice.as_ptr() as *const Self;
+                Ok(unsafe { $crate::__core::ptr::read(ptr) })
+            }
+
+            /// Copies the elements of `self` into `slice`.
+            ///
+            /// # Returns:
+            ///   - `Ok(())` if `slice` has room for exactly as many elements
+            ///     as `Self`
+            ///   - `Err(LengthError)` otherwise, in which case `slice` is left
+            ///     unmodified
+            ///
+            pub fn copy_to_slice(&self, slice: &mut [N])
+                                -> Result<(), $crate::error::LengthError>
+            where N: Copy {
+                if slice.len() != $len {
+                    return Err($crate::error::LengthError::Exact {
+                        expected: $len, found: slice.len()
+                    })
+                }
+                slice.copy_from_slice(self.as_elems());
+                Ok(())
+            }
+
+            /// Reinterprets a slice of values as a slice of their elements.
+            ///
+            /// For example, a `&[Vector3<f32>]` of length `n` becomes a
+            /// `&[f32]` of length `3 * n`.
+            #[inline]
+            pub fn as_slice_of(values: &[Self]) -> &[N] {
+                let () = Self::ARRAY_LAYOUT;
+                unsafe {
+                    $crate::__core::slice::from_raw_parts(
+                        values.as_ptr() as *const N, values.len() * $len)
+                }
+            }
+
+            /// Reinterprets a mutable slice of values as a mutable slice of
+            /// their elements.
+            #[inline]
+            pub fn as_slice_of_mut(values: &mut [Self]) -> &mut [N] {
+                let () = Self::ARRAY_LAYOUT;
+                unsafe {
+                    $crate::__core::slice::from_raw_parts_mut(
+                        values.as_mut_ptr() as *mut N, values.len() * $len)
+                }
+            }
+
+            /// Reinterprets a slice of elements as a slice of values.
+            ///
+            /// # Returns:
+            ///   - `Ok(&[Self])` if the length of `elems` is a multiple of
+            ///     the number of elements in `Self`
+            ///   - `Err(LengthError)` otherwise
+            ///
+            #[inline]
+            pub fn from_slice_of(elems: &[N])
+                                -> Result<&[Self], $crate::error::LengthError> {
+                if elems.len() % $len != 0 {
+                    return Err($crate::error::LengthError::Multiple {
+                        of: $len, found: elems.len()
+                    })
+                }
+                let () = Self::ARRAY_LAYOUT;
+                Ok(unsafe {
+                    $crate::__core::slice::from_raw_parts(
+                        elems.as_ptr() as *const Self, elems.len() / $len)
+                })
+            }
+
+            /// Reinterprets a mutable slice of elements as a mutable slice of
+            /// values.
+            ///
+            /// # Returns:
+            ///   - `Ok(&mut [Self])` if the length of `elems` is a multiple of
+            ///     the number of elements in `Self`
+            ///   - `Err(LengthError)` otherwise
+            ///
+            #[inline]
+            pub fn from_slice_of_mut(elems: &mut [N])
+                                    -> Result<&mut [Self]
+                                             , $crate::error::LengthError> {
+                if elems.len() % $len != 0 {
+                    return Err($crate::error::LengthError::Multiple {
+                        of: $len, found: elems.len()
+                    })
+                }
+                let () = Self::ARRAY_LAYOUT;
+                Ok(unsafe {
+                    $crate::__core::slice::from_raw_parts_mut(
+                        elems.as_mut_ptr() as *mut Self, elems.len() / $len)
+                })
+            }
+
+            /// Borrows the elements of `self` as a flat array.
+            #[inline]
+            pub fn as_elems(&self) -> &[N; $len] {
+                let () = Self::ARRAY_LAYOUT;
+                unsafe { &*(self as *const Self as *const [N; $len]) }
+            }
+
+            /// Mutably borrows the elements of `self` as a flat array.
+            #[inline]
+            pub fn as_elems_mut(&mut self) -> &mut [N; $len] {
+                let () = Self::ARRAY_LAYOUT;
+                unsafe { &mut *(self as *mut Self as *mut [N; $len]) }
+            }
+        }
+    }
+}
+
+/// Implements `Serialize` and `Deserialize` for a fixed-size type, as a
+/// tuple of its elements, and `SerializeNamed` and `DeserializeNamed` as a
+/// struct keyed by its subscript names.
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_serde {
+    (@visit_seq $ty: ident, $seq: ident, $exp: expr, $($sub: ident),+) => {{
+        let mut _i = 0;
+        Ok($ty { $($sub: {
+            let elem = $seq.next_element()?
+                           .ok_or_else(|| Error::invalid_length(_i, $exp))?;
+            _i += 1;
+            elem
+        }),+ })
+    }};
+    ($ty: ident, $len: expr, $($sub: ident),+) => {
+        impl<N> $crate::serialization::Serialize for $ty<N>
+        where N: $crate::serialization::Serialize {
+
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where S: $crate::serialization::Serializer {
+                use $crate::serialization::SerializeTuple;
+                let mut tuple = serializer.serialize_tuple($len)?;
+                $( tuple.serialize_element(&self.$sub)?; )+
+                tuple.end()
+            }
+        }
+
+        impl<'de, N> $crate::serialization::Deserialize<'de> for $ty<N>
+        where N: $crate::serialization::Deserialize<'de> {
+
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where D: $crate::serialization::Deserializer<'de> {
+                use $crate::__core::marker::PhantomData;
+                use $crate::serialization::{ Deserialize, Error, SeqAccess
+                                           , Visitor };
+
+                struct TupleVisitor<N>(PhantomData<N>);
+
+                impl<'de, N> Visitor<'de> for TupleVisitor<N>
+                where N: Deserialize<'de> {
+                    type Value = $ty<N>;
+
+                    fn expecting(&self, f: &mut $crate::__core::fmt::Formatter)
+                                -> $crate::__core::fmt::Result {
+                        write!(f, "a tuple of {} elements", $len)
+                    }
+
+                    fn visit_seq<A>(self, mut seq: A) -> Result<$ty<N>, A::Error>
+                    where A: SeqAccess<'de> {
+                        $crate::impl_serde!(@visit_seq $ty, seq, &self
+                                                      , $($sub),+)
+                    }
+                }
+
+                deserializer.deserialize_tuple($len, TupleVisitor(PhantomData))
+            }
+        }
+
+        impl<N> $crate::serialization::SerializeNamed for $ty<N>
+        where N: $crate::serialization::Serialize {
+
+            fn serialize_named<S>(&self, serializer: S)
+                                 -> Result<S::Ok, S::Error>
+            where S: $crate::serialization::Serializer {
+                use $crate::serialization::SerializeStruct;
+                let mut st = serializer.serialize_struct( stringify!($ty)
+                                                        , $len)?;
+                $( st.serialize_field(stringify!($sub), &self.$sub)?; )+
+                st.end()
+            }
+        }
+
+        impl<'de, N> $crate::serialization::DeserializeNamed<'de> for $ty<N>
+        where N: $crate::serialization::Deserialize<'de> {
+
+            fn deserialize_named<D>(deserializer: D) -> Result<Self, D::Error>
+            where D: $crate::serialization::Deserializer<'de> {
+                use $crate::__core::marker::PhantomData;
+                use $crate::serialization::{ Deserialize, Error, FieldIndex
+                                           , MapAccess, SeqAccess, Visitor };
+
+                const FIELDS: &'static [&'static str] =
+                    &[$(stringify!($sub)),+];
+
+                struct StructVisitor<N>(PhantomData<N>);
+
+                impl<'de, N> Visitor<'de> for StructVisitor<N>
+                where N: Deserialize<'de> {
+                    type Value = $ty<N>;
+
+                    fn expecting(&self, f: &mut $crate::__core::fmt::Formatter)
+                                -> $crate::__core::fmt::Result {
+                        write!(f, "struct {}", stringify!($ty))
+                    }
+
+                    fn visit_seq<A>(self, mut seq: A) -> Result<$ty<N>, A::Error>
+                    where A: SeqAccess<'de> {
+                        $crate::impl_serde!(@visit_seq $ty, seq, &self
+                                                      , $($sub),+)
+                    }
+
+                    fn visit_map<A>(self, mut map: A) -> Result<$ty<N>, A::Error>
+                    where A: MapAccess<'de> {
+                        let mut slots: [Option<N>; $len] =
+                            $crate::__core::array::from_fn(|_| None);
+                        while let Some(i) =
+                                map.next_key_seed(FieldIndex(FIELDS))? {
+                            if slots[i].is_some() {
+                                return Err(Error::duplicate_field(FIELDS[i]))
+                            }
+                            slots[i] = Some(map.next_value()?);
+                        }
+                        let mut slots = IntoIterator::into_iter(slots);
+                        Ok($ty { $($sub: slots.next().and_then(|s| s)
+                            .ok_or_else(|| {
+                                Error::missing_field(stringify!($sub))
+                            })?),+
+                        })
+                    }
+                }
+
+                deserializer.deserialize_struct( stringify!($ty), FIELDS
+                                               , StructVisitor(PhantomData))
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_serde {
+    ($ty: ident, $len: expr, $($sub: ident),+) => { }
+}
+
+/// Implements `Pod` for a vector or matrix type, and the `bytemuck` traits
+/// when that feature is enabled.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_pod {
+    ($ty: ident) => {
+        // `$ty` is a `repr(C)` struct whose fields all have type `N`, so it
+        // contains no padding bytes when `N` doesn't.
+        unsafe impl<N> $crate::pod::Pod for $ty<N>
+        where N: $crate::pod::Pod {}
+
+        $crate::impl_bytemuck! { $ty }
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_bytemuck {
+    ($ty: ident) => {
+        unsafe impl<N> $crate::pod::Zeroable for $ty<N>
+        where N: $crate::pod::Zeroable {}
+
+        unsafe impl<N> $crate::pod::BytemuckPod for $ty<N>
+        where N: $crate::pod::BytemuckPod {}
+    }
+}
+
+#[cfg(not(feature = "bytemuck"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_bytemuck {
+    ($ty: ident) => { }
+}
+
+/// Implements `FromStr` for a vector or matrix type, using one of the
+/// parsers in the `parse` module, when an allocator is available.
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_from_str {
+    ($ty: ident, $parse: ident, $($dim: expr),+) => {
+        impl<N> $crate::__core::str::FromStr for $ty<N>
+        where N: $crate::__core::str::FromStr {
+
+            type Err = $crate::parse::ParseError;
+
+            fn from_str(s: &str) -> Result<$ty<N>, Self::Err> {
+                let elems = $crate::parse::$parse(s, $($dim),+)?;
+                Ok($ty::try_from_iter(elems)
+                       .expect("parsed as many elements as the type holds"))
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_from_str {
+    ($ty: ident, $parse: ident, $($dim: expr),+) => { }
+}
+
+/// Implements `quickcheck::Arbitrary` for a vector or matrix type, by
+/// generating each element independently and shrinking one element at a
+/// time.
+#[cfg(any(test, feature = "quickcheck"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_arbitrary {
+    ($ty: ident, $($sub: ident),+) => {
+        impl<N> $crate::quickcheck::Arbitrary for $ty<N>
+        where N: $crate::quickcheck::Arbitrary {
+
+            fn arbitrary(g: &mut $crate::quickcheck::Gen) -> $ty<N> {
+                $ty { $($sub: N::arbitrary(g)),+ }
+            }
+
+            fn shrink(&self)
+                -> $crate::__alloc::boxed::Box<dyn Iterator<Item = Self>> {
+                let shrunk = $crate::__core::iter::empty();
+                $( let base = self.clone();
+                   let shrunk = shrunk.chain(
+                       self.$sub.shrink().map(move |elem| {
+                           let mut shrunk = base.clone();
+                           shrunk.$sub = elem;
+                           shrunk
+                       })); )+
+                $crate::__alloc::boxed::Box::new(shrunk)
+            }
+        }
+    }
+}
+
+#[cfg(not(any(test, feature = "quickcheck")))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_arbitrary {
+    ($ty: ident, $($sub: ident),+) => { }
+}
+
+/// Generates a module of tests checking that addition on `$ty<$n>` is
+/// associative and commutative, and that multiplication by a scalar
+/// distributes over addition.
+#[cfg(any(test, feature = "quickcheck"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! additive_laws {
+    ($name: ident, $ty: ident, $n: ty) => {
+        mod $name {
+            use super::*;
+            use $crate::laws::{Element, norm, all_close};
+
+            #[test]
+            fn add_associative() {
+                fn prop(a: $ty<$n>, b: $ty<$n>, c: $ty<$n>) -> bool {
+                    let (a, b, c) = ( a.map(Element::tame)
+                                    , b.map(Element::tame)
+                                    , c.map(Element::tame));
+                    let scale = norm(a.as_elems()) + norm(b.as_elems())
+                              + norm(c.as_elems());
+                    all_close( (a + b + c).as_elems()
+                             , (a + (b + c)).as_elems()
+                             , scale)
+                }
+                $crate::quickcheck::quickcheck(
+                    prop as fn($ty<$n>, $ty<$n>, $ty<$n>) -> bool);
+            }
+
+            #[test]
+            fn add_commutative() {
+                fn prop(a: $ty<$n>, b: $ty<$n>) -> bool {
+                    let (a, b) = (a.map(Element::tame), b.map(Element::tame));
+                    let scale = norm(a.as_elems()) + norm(b.as_elems());
+                    all_close((a + b).as_elems(), (b + a).as_elems(), scale)
+                }
+                $crate::quickcheck::quickcheck(
+                    prop as fn($ty<$n>, $ty<$n>) -> bool);
+            }
+
+            #[test]
+            fn scalar_mul_distributive() {
+                fn prop(a: $ty<$n>, b: $ty<$n>, k: $n) -> bool {
+                    let (a, b) = (a.map(Element::tame), b.map(Element::tame));
+                    let k = k.tame();
+                    let scale = (norm(a.as_elems()) + norm(b.as_elems()))
+                              * k.magnitude();
+                    all_close( ((a + b) * k).as_elems()
+                             , (a * k + b * k).as_elems()
+                             , scale)
+                }
+                $crate::quickcheck::quickcheck(
+                    prop as fn($ty<$n>, $ty<$n>, $n) -> bool);
+            }
+        }
+    }
+}
+
+/// Generates a module of tests checking that the transpose of a product of
+/// `$ty<$n>`s is the product of the transposes in reverse order, and that
+/// the determinant is multiplicative, for a square matrix type with
+/// `$dim` rows which has a `determinant`.
+#[cfg(any(test, feature = "quickcheck"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! product_laws {
+    ($name: ident, $ty: ident, $n: ty, $dim: expr) => {
+        mod $name {
+            use super::*;
+            use $crate::laws::{Element, norm, all_close};
+
+            #[test]
+            fn transpose_of_product() {
+                fn prop(a: $ty<$n>, b: $ty<$n>) -> bool {
+                    let (a, b) = (a.map(Element::tame), b.map(Element::tame));
+                    let scale = $dim as f64 * norm(a.as_elems())
+                              * norm(b.as_elems());
+                    all_close( (a * b).transpose().as_elems()
+                             , (b.transpose() * a.transpose()).as_elems()
+                             , scale)
+                }
+                $crate::quickcheck::quickcheck(
+                    prop as fn($ty<$n>, $ty<$n>) -> bool);
+            }
+
+            #[test]
+            fn determinant_of_product() {
+                fn prop(a: $ty<$n>, b: $ty<$n>) -> bool {
+                    let (a, b) = (a.map(Element::tame), b.map(Element::tame));
+                    // each term of either side is bounded by the `$dim`th
+                    // power of the largest possible row sum of `a * b`.
+                    let row = $dim as f64 * norm(a.as_elems())
+                            * norm(b.as_elems());
+                    let scale = row.powi($dim);
+                    <$n as Element>::close( (a * b).determinant()
+                                          , a.determinant() * b.determinant()
+                                          , scale)
+                }
+                $crate::quickcheck::quickcheck(
+                    prop as fn($ty<$n>, $ty<$n>) -> bool);
+            }
+        }
+    }
+}
@@ -0,0 +1,316 @@
+//! Dense matrices whose dimensions are only known at runtime.
+
+use super::{Matrix, MatrixView, MatrixViewMut};
+use {Columnar, ColumnarMut, Tabular, TabularMut};
+use error::LengthError;
+use strided::{Strided, StridedMut};
+
+use alloc::vec::{self, Vec};
+
+use std::ops;
+use std::fmt;
+use std::slice;
+
+/// Returns the number of elements in an `nrows` by `ncols` matrix.
+#[inline]
+fn elem_count(nrows: usize, ncols: usize) -> usize {
+    nrows.checked_mul(ncols).expect("matrix size overflows a `usize`")
+}
+
+/// A dense matrix of any size, stored in row-major order.
+///
+/// Like the fixed-size matrices, a `MatrixN` is indexed by `(x, y)`, where
+/// `x` is the column and `y` is the row.
+#[derive(Clone, PartialEq, Eq, Debug, Default, Hash)]
+pub struct MatrixN<N> { nrows: usize
+                      , ncols: usize
+                      , elems: Vec<N>
+                      }
+
+impl<N> MatrixN<N> {
+
+    /// Construct a new matrix from a vector of elements in row-major order.
+    ///
+    /// # Returns:
+    ///   - `Ok(MatrixN)` if `elems` contains `nrows * ncols` elements
+    ///   - `Err(LengthError)` otherwise, including if `nrows * ncols`
+    ///     overflows a `usize`
+    ///
+    pub fn from_vec(nrows: usize, ncols: usize, elems: Vec<N>)
+                   -> Result<Self, LengthError> {
+        match nrows.checked_mul(ncols) {
+            Some(len) if len == elems.len() =>
+                Ok(MatrixN { nrows, ncols, elems })
+          , len => Err(LengthError::Exact {
+                // no vector can hold more than `usize::MAX` elements
+                expected: len.unwrap_or(usize::MAX)
+              , found: elems.len()
+            })
+        }
+    }
+
+    /// Construct a new matrix by calling `f(x, y)` for each element, in
+    /// row-major order.
+    ///
+    /// # Panics
+    ///   - if `nrows * ncols` overflows a `usize`
+    ///
+    pub fn from_fn<F>(nrows: usize, ncols: usize, mut f: F) -> Self
+    where F: FnMut(usize, usize) -> N {
+        let mut elems = Vec::with_capacity(elem_count(nrows, ncols));
+        for y in 0..nrows {
+            for x in 0..ncols { elems.push(f(x, y)) }
+        }
+        MatrixN { nrows, ncols, elems }
+    }
+
+    /// Construct a new matrix with every element set to `elem`.
+    ///
+    /// # Panics
+    ///   - if `nrows * ncols` overflows a `usize`
+    ///
+    pub fn from_elem(nrows: usize, ncols: usize, elem: N) -> Self
+    where N: Clone {
+        MatrixN { nrows, ncols, elems: vec![elem; elem_count(nrows, ncols)] }
+    }
+
+    /// Returns the number of rows
+    #[inline] pub fn nrows(&self) -> usize { self.nrows }
+
+    /// Returns the number of columns
+    #[inline] pub fn ncols(&self) -> usize { self.ncols }
+
+    /// Returns the number of rows and columns, as `(rows, columns)`
+    #[inline]
+    pub fn shape(&self) -> (usize, usize) { (self.nrows, self.ncols) }
+
+    /// Returns true if this matrix has as many rows as columns
+    #[inline] pub fn is_square(&self) -> bool { self.nrows == self.ncols }
+
+    /// Borrow the elements of this matrix in row-major order
+    #[inline] pub fn as_slice(&self) -> &[N] { &self.elems }
+
+    /// Mutably borrow the elements of this matrix in row-major order
+    #[inline] pub fn as_mut_slice(&mut self) -> &mut [N] { &mut self.elems }
+
+    /// Consume this matrix, returning its elements in row-major order
+    #[inline] pub fn into_vec(self) -> Vec<N> { self.elems }
+
+    /// Select an element by column and row
+    ///
+    /// # Returns:
+    ///   - `Some(&N)` if an element exists at the given position
+    ///   - `None` if the position is out of bounds
+    ///
+    #[inline]
+    pub fn get(&self, x: usize, y: usize) -> Option<&N> {
+        if x < self.ncols && y < self.nrows {
+            Some(&self.elems[x + y * self.ncols])
+        } else {
+            None
+        }
+    }
+
+    /// Mutably select an element by column and row
+    ///
+    /// # Returns:
+    ///   - `Some(&mut N)` if an element exists at the given position
+    ///   - `None` if the position is out of bounds
+    ///
+    #[inline]
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut N> {
+        if x < self.ncols && y < self.nrows {
+            Some(&mut self.elems[x + y * self.ncols])
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over the elements of this matrix in row-major
+    /// order
+    #[inline] pub fn iter(&self) -> slice::Iter<'_, N> { self.elems.iter() }
+
+    /// Returns an iterator over mutable references to the elements of this
+    /// matrix in row-major order
+    #[inline]
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, N> {
+        self.elems.iter_mut()
+    }
+
+    /// Borrow this matrix as a view
+    #[inline]
+    pub fn view(&self) -> MatrixView<'_, N> {
+        MatrixView::from_row_major(&self.elems, self.nrows, self.ncols)
+            .expect("matrix has as many elements as its shape")
+    }
+
+    /// Mutably borrow this matrix as a view
+    #[inline]
+    pub fn view_mut(&mut self) -> MatrixViewMut<'_, N> {
+        MatrixViewMut::from_row_major(&mut self.elems, self.nrows, self.ncols)
+            .expect("matrix has as many elements as its shape")
+    }
+
+    /// Returns the transpose of this matrix
+    pub fn transpose(&self) -> Self
+    where N: Clone {
+        MatrixN::from_fn(self.ncols, self.nrows, |x, y| self[(y, x)].clone())
+    }
+
+    /// Apply `f` to each element of this matrix, returning a new matrix of
+    /// the same shape.
+    pub fn map<M, F>(self, f: F) -> MatrixN<M>
+    where F: FnMut(N) -> M {
+        MatrixN { nrows: self.nrows
+                , ncols: self.ncols
+                , elems: self.elems.into_iter().map(f).collect()
+                }
+    }
+}
+
+impl<N> Matrix<N> for MatrixN<N> {
+    #[inline] fn nrows(&self) -> usize { self.nrows }
+    #[inline] fn ncols(&self) -> usize { self.ncols }
+}
+
+impl<N> ops::Index<(usize, usize)> for MatrixN<N> {
+
+    type Output = N;
+    #[inline] fn index(&self, (x, y): (usize, usize)) -> &N {
+        self.get(x, y).expect("matrix index out of bounds")
+    }
+}
+
+impl<N> ops::IndexMut<(usize, usize)> for MatrixN<N> {
+
+    #[inline] fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut N {
+        self.get_mut(x, y).expect("matrix index out of bounds")
+    }
+}
+
+impl<N> Tabular for MatrixN<N> {
+
+    type Row = [N];
+    #[inline] fn nrows(&self) -> usize { self.nrows }
+
+    #[inline]
+    fn row(&self, i: usize) -> Option<&[N]> {
+        if i < self.nrows {
+            Some(&self.elems[i * self.ncols..(i + 1) * self.ncols])
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn row_mut(&mut self, i: usize) -> Option<&mut [N]> {
+        if i < self.nrows {
+            Some(&mut self.elems[i * self.ncols..(i + 1) * self.ncols])
+        } else {
+            None
+        }
+    }
+}
+
+// each row is a distinct, contiguous range of elements.
+unsafe impl<N> TabularMut for MatrixN<N> {
+    /// A pointer to the first element, and the length of each row
+    type RowsRaw = (*mut N, usize);
+
+    #[inline] fn rows_raw(&mut self) -> (*mut N, usize) {
+        (self.elems.as_mut_ptr(), self.ncols)
+    }
+
+    #[inline]
+    unsafe fn row_from_raw<'a>((ptr, ncols): (*mut N, usize), i: usize)
+                              -> &'a mut [N]
+    where Self: 'a {
+        slice::from_raw_parts_mut(ptr.add(i * ncols), ncols)
+    }
+}
+
+impl<N> Columnar for MatrixN<N>
+where N: Clone {
+    type Column = Vec<N>;
+    type ColumnRef<'a> = Strided<'a, N> where Self: 'a;
+    type ColumnMut<'a> = StridedMut<'a, N> where Self: 'a;
+
+    #[inline] fn ncols(&self) -> usize { self.ncols }
+
+    #[inline]
+    fn column(&self, i: usize) -> Option<Strided<'_, N>> {
+        if i >= self.ncols { return None }
+        Strided::new(&self.elems, i, self.ncols, self.nrows)
+    }
+
+    #[inline]
+    fn column_mut(&mut self, i: usize) -> Option<StridedMut<'_, N>> {
+        if i >= self.ncols { return None }
+        StridedMut::new(&mut self.elems, i, self.ncols, self.nrows)
+    }
+
+    #[inline]
+    fn column_owned(&self, i: usize) -> Option<Vec<N>> {
+        self.column(i).map(|col| col.iter().cloned().collect())
+    }
+}
+
+// columns share no elements, since each column starts at a different offset
+// within the first row and advances by a whole row at a time.
+unsafe impl<N> ColumnarMut for MatrixN<N>
+where N: Clone {
+    /// A pointer to the first element, and the numbers of columns and rows
+    type ColumnsRaw = (*mut N, usize, usize);
+
+    #[inline] fn columns_raw(&mut self) -> (*mut N, usize, usize) {
+        (self.elems.as_mut_ptr(), self.ncols, self.nrows)
+    }
+
+    #[inline]
+    unsafe fn column_from_raw<'a>( (ptr, ncols, nrows): (*mut N, usize, usize)
+                                 , i: usize) -> StridedMut<'a, N>
+    where Self: 'a {
+        StridedMut::from_raw_parts(ptr.add(i), ncols, nrows)
+    }
+}
+
+impl<N> fmt::Display for MatrixN<N>
+where N: fmt::Display {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut widths = vec![0; self.ncols];
+        ::display::write_matrix( f, &self.elems, self.nrows, self.ncols
+                               , &mut widths)
+    }
+}
+
+impl<'a, N> From<MatrixView<'a, N>> for MatrixN<N>
+where N: Clone {
+
+    /// Copy the elements of a view into a new matrix
+    fn from(view: MatrixView<'a, N>) -> MatrixN<N> {
+        MatrixN::from_fn( view.nrows(), view.ncols()
+                        , |x, y| view[(x, y)].clone())
+    }
+}
+
+impl<N> IntoIterator for MatrixN<N> {
+    type Item = N;
+    type IntoIter = vec::IntoIter<N>;
+
+    #[inline] fn into_iter(self) -> Self::IntoIter { self.elems.into_iter() }
+}
+
+impl<'a, N> IntoIterator for &'a MatrixN<N> {
+    type Item = &'a N;
+    type IntoIter = slice::Iter<'a, N>;
+
+    #[inline] fn into_iter(self) -> Self::IntoIter { self.elems.iter() }
+}
+
+impl<'a, N> IntoIterator for &'a mut MatrixN<N> {
+    type Item = &'a mut N;
+    type IntoIter = slice::IterMut<'a, N>;
+
+    #[inline] fn into_iter(self) -> Self::IntoIter { self.elems.iter_mut() }
+}
@@ -1,5 +1,3 @@
-macro_rules! e { ($e:expr) => { $e } }
-
 /// Macro for constructing a new matrix type.
 ///
 /// This can be used to construct fixed-sized matrices of whatever dimension
@@ -19,105 +17,450 @@ macro_rules! e { ($e:expr) => { $e } }
 ///    - `$name`: The name of the new matrix type
 ///    - `$rows`: The number of rows in this matrix
 ///    - `$cols`: The number of columns in this matrix
+///    - `$row`: The vector type of a single row, which should have `$cols`
+///      elements
+///    - `$col`: The vector type of a single column, which should have
+///      `$rows` elements
+///    - `square`: An optional flag marking the matrix as square, which adds
+///      the transpose and the product of two matrices. It fails to compile
+///      unless `$rows` equals `$cols`.
 ///    - `$sub`: The name of each subscript or element of the matrix
-///              Note that the number of `$sub`s should be equal to `$cols` *
-///              `$rows`.
+///      Note that the number of `$sub`s should be equal to `$cols` *
+///      `$rows`.
+///
+/// # Example
+///   Consider the definition of `Matrix2`:
 ///
+///   ```ignore
+///   make_matrix! { Matrix2, rows: 2, cols: 2, row: Vector2, col: Vector2
+///                , square
+///                , x1y1, x2y1
+///                , x1y2, x2y2
+///                }
+///   ```
 #[macro_export]
 macro_rules! make_matrix {
-    ($name: ident, rows: $rows:expr, cols: $cols:expr, $($sub: ident),+) => {
-        #[cfg(not(simd))]
+    ( $name: ident, rows: $rows:expr, cols: $cols:expr
+    , row: $row: ident, col: $col: ident, square
+    , $($sub: ident),+) => {
+        $crate::make_matrix! { $name, rows: $rows, cols: $cols
+                             , row: $row, col: $col, $($sub),+ }
+        $crate::impl_square_matrix_ops! { $name, $rows, $cols }
+    };
+    ( $name: ident, rows: $rows:expr, cols: $cols:expr
+    , row: $row: ident, col: $col: ident
+    , $($sub: ident),+) => {
         #[derive(Clone, Copy, Eq, PartialEq, PartialOrd, Debug, Default)]
         #[repr(C)]
         pub struct $name<N> {
             $(pub $sub: N),+
         }
-        impl_matrix! { $name, $rows, $cols }
-        impl_converts! { $name, $cols, $rows }
-        impl_index! { $name, $cols}
-
-    }
-}
+        $crate::impl_matrix! { $name, $rows, $cols }
+        $crate::impl_matrix_converts! { $name, $cols, $rows }
+        $crate::impl_matrix_index! { $name, $cols, $rows }
+        $crate::impl_iter! { $name, $rows * $cols, $($sub),+ }
+        $crate::impl_approx_eq! { $name, $($sub),+ }
+        $crate::impl_int_arith! { $name, $($sub),+ }
+        $crate::impl_cast! { $name, $($sub),+ }
+        $crate::impl_serde! { $name, $rows * $cols, $($sub),+ }
+        $crate::impl_pod! { $name }
+        $crate::impl_matrix_fmt! { $name, $rows, $cols }
+        $crate::impl_rows_cols! { $name, $rows, $cols, $row, $col }
+        $crate::impl_matrix_ops! { $name, $rows, $cols, $row, $col, $($sub),+ }
+        $crate::impl_arbitrary! { $name, $($sub),+ }
 
-#[cfg(features = "unstable")]
-macro_rules! impl_matrix {
-    ($name: ident, $rows:expr, $cols:expr) => {
-        impl<N> Matrix<N> for $name<N> {
-            const fn nrows(&self) -> usize { $rows }
-            const fn ncols(&self) -> usize { $cols }
-        }
     }
 }
 
-#[cfg(not(features = "unstable"))]
+#[doc(hidden)]
+#[macro_export]
 macro_rules! impl_matrix {
     ($name: ident, $rows:expr, $cols:expr) => {
-        impl<N> Matrix<N> for $name<N> {
+        impl<N> $crate::matrix::Matrix<N> for $name<N> {
             #[inline] fn nrows(&self) -> usize { $rows }
             #[inline] fn ncols(&self) -> usize { $cols }
         }
     }
 }
 
-macro_rules! impl_converts {
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_matrix_converts {
     ($($m: ident, $c: expr, $r: expr),+) => { $(
-        impl<N> convert::AsRef<[[N; $c]; $r]> for $m<N>
+        $crate::impl_layout! { $m, $r * $c }
+
+        impl<N> $crate::__core::convert::AsRef<[[N; $c]; $r]> for $m<N>
         where N: Copy {
 
             #[inline] fn as_ref(&self) -> &[[N; $c]; $r] {
-                unsafe { transmute(self) }
+                // nested arrays are laid out contiguously, without padding
+                let elems: *const [N; $r * $c] = self.as_elems();
+                unsafe { &*(elems as *const [[N; $c]; $r]) }
             }
         }
-        impl<N> convert::AsMut<[[N; $c]; $c]> for $m<N>
+        impl<N> $crate::__core::convert::AsMut<[[N; $c]; $r]> for $m<N>
         where N: Copy {
 
-            #[inline] fn as_mut(&mut self) -> &mut [[N; $c]; $c] {
-                unsafe { transmute(self) }
+            #[inline] fn as_mut(&mut self) -> &mut [[N; $c]; $r] {
+                let elems: *mut [N; $r * $c] = self.as_elems_mut();
+                unsafe { &mut *(elems as *mut [[N; $c]; $r]) }
             }
         }
-        impl<'a, N> convert::From<&'a [[N; $c]; $r]> for &'a $m<N>
-        where N: Numeric
+        impl<'a, N> $crate::__core::convert::From<&'a [[N; $c]; $r]>
+        for &'a $m<N>
+        where N: $crate::Numeric
             , N: Copy {
 
             #[inline] fn from(a: &'a [[N; $c]; $r]) -> &'a $m<N> {
-                unsafe { transmute(a) }
+                let () = $m::<N>::ARRAY_LAYOUT;
+                unsafe { &*(a as *const [[N; $c]; $r] as *const $m<N>) }
             }
         }
-        impl<'a, N> convert::From<&'a mut [[N; $c]; $r]> for &'a mut $m<N>
-        where N: Numeric
+        impl<'a, N> $crate::__core::convert::From<&'a mut [[N; $c]; $r]>
+        for &'a mut $m<N>
+        where N: $crate::Numeric
             , N: Copy {
 
             #[inline] fn from(a: &'a mut [[N; $c]; $r]) -> &'a mut $m<N> {
-                unsafe { transmute(a) }
+                let () = $m::<N>::ARRAY_LAYOUT;
+                unsafe { &mut *(a as *mut [[N; $c]; $r] as *mut $m<N>) }
+            }
+        }
+        impl<N> $crate::__core::convert::From<[[N; $c]; $r]> for $m<N> {
+
+            #[inline] fn from(a: [[N; $c]; $r]) -> $m<N> {
+                let elems = IntoIterator::into_iter(a)
+                    .flat_map(IntoIterator::into_iter);
+                $m::try_from_iter(elems)
+                    .expect("nested array has as many elements as matrix")
             }
         }
+        impl<N> $crate::__core::convert::From<$m<N>> for [[N; $c]; $r] {
+
+            #[inline] fn from(m: $m<N>) -> [[N; $c]; $r] {
+                let mut elems = m.into_iter();
+                $crate::__core::array::from_fn(|_| {
+                    $crate::__core::array::from_fn(|_| {
+                        elems.next().expect("matrix has as many elements \
+                                             as nested array")
+                    })
+                })
+            }
+        }
+        $crate::impl_dynamic! { $m, $r, $c }
     )+}
 }
 
-macro_rules! impl_index {
-    ($($m: ident, $c: expr),+) => { $(
-        impl<N> ops::Index<(usize, usize)> for $m<N>
+/// Implements conversions between a fixed-size matrix type and `MatrixN`,
+/// when an allocator is available.
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_dynamic {
+    ($m: ident, $r: expr, $c: expr) => {
+        impl<N> $crate::__core::convert::From<$m<N>>
+        for $crate::matrix::MatrixN<N> {
+
+            #[inline] fn from(m: $m<N>) -> $crate::matrix::MatrixN<N> {
+                let elems = m.into_iter().collect();
+                $crate::matrix::MatrixN::from_vec($r, $c, elems)
+                    .expect("matrix has as many elements as its shape")
+            }
+        }
+        impl<N> $crate::__core::convert::TryFrom<$crate::matrix::MatrixN<N>>
+        for $m<N> {
+
+            type Error = $crate::error::ShapeError;
+            #[inline]
+            fn try_from(m: $crate::matrix::MatrixN<N>)
+                       -> Result<$m<N>, $crate::error::ShapeError> {
+                if m.shape() != ($r, $c) {
+                    return Err($crate::error::ShapeError { expected: ($r, $c)
+                                                         , found: m.shape() })
+                }
+                Ok($m::try_from_iter(m)
+                      .expect("matrix has as many elements as its shape"))
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_dynamic {
+    ($m: ident, $r: expr, $c: expr) => { }
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_matrix_index {
+    ($($m: ident, $c: expr, $r: expr),+) => { $(
+        impl<N> $crate::__core::ops::Index<(usize, usize)> for $m<N>
         where N: Copy {
 
             type Output = N;
             #[inline] fn index(&self, (x, y): (usize, usize)) -> &N {
-                unsafe {
-                    &transmute::<&$m<N>, &[N; $c * $c]>(self)[x + y * $c]
-                }
+                &self.as_elems()[x + y * $c]
             }
         }
 
-        impl<N> ops::IndexMut<(usize, usize)> for $m<N>
+        impl<N> $crate::__core::ops::IndexMut<(usize, usize)> for $m<N>
         where N: Copy {
 
             #[inline] fn index_mut(&mut self, (x, y): (usize, usize))
                                   -> &mut N {
-                unsafe {
-                    &mut transmute::<&mut $m<N>, &mut [N; $c * $c]>(self)
-                        [x + y * $c]
-                }
+                &mut self.as_elems_mut()[x + y * $c]
             }
         }
 
     )+}
 }
+
+/// Implements `Display`, writing one bracketed row per line with aligned
+/// columns, and `FromStr` accepting any of the forms described in the
+/// `parse` module.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_matrix_fmt {
+    ($m: ident, $r: expr, $c: expr) => {
+        impl<N> $crate::__core::fmt::Display for $m<N>
+        where N: $crate::__core::fmt::Display {
+
+            fn fmt(&self, f: &mut $crate::__core::fmt::Formatter)
+                  -> $crate::__core::fmt::Result {
+                $crate::display::write_matrix( f, self.as_elems(), $r, $c
+                                             , &mut [0; $c])
+            }
+        }
+        $crate::impl_from_str! { $m, parse_matrix, $r, $c }
+    }
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_rows_cols {
+    ($m: ident, $r: expr, $c: expr, $row: ident, $col: ident) => {
+        impl<N> $crate::Tabular for $m<N>
+        where N: Copy {
+
+            type Row = $row<N>;
+            #[inline] fn nrows(&self) -> usize { $r }
+
+            #[inline]
+            fn row(&self, i: usize) -> Option<&Self::Row> {
+                $row::from_slice_of(self.as_elems()).ok()
+                     .and_then(|rows| rows.get(i))
+            }
+
+            #[inline]
+            fn row_mut(&mut self, i: usize) -> Option<&mut Self::Row> {
+                $row::from_slice_of_mut(self.as_elems_mut()).ok()
+                     .and_then(|rows| rows.get_mut(i))
+            }
+
+        }
+
+        // each row is a distinct, contiguous range of elements.
+        unsafe impl<N> $crate::TabularMut for $m<N>
+        where N: Copy {
+            type RowsRaw = *mut $row<N>;
+
+            #[inline] fn rows_raw(&mut self) -> *mut $row<N> {
+                $row::from_slice_of_mut(self.as_elems_mut())
+                     .expect("matrix elements divide evenly into rows")
+                     .as_mut_ptr()
+            }
+
+            #[inline]
+            unsafe fn row_from_raw<'a>(raw: *mut $row<N>, i: usize)
+                                      -> &'a mut $row<N>
+            where Self: 'a {
+                &mut *raw.add(i)
+            }
+        }
+
+        impl<N> $crate::Columnar for $m<N>
+        where N: Copy {
+            type Column = $col<N>;
+            type ColumnRef<'a> = $crate::strided::Strided<'a, N>
+            where Self: 'a;
+            type ColumnMut<'a> = $crate::strided::StridedMut<'a, N>
+            where Self: 'a;
+
+            #[inline] fn ncols(&self) -> usize { $c }
+
+            #[inline]
+            fn column(&self, i: usize) -> Option<Self::ColumnRef<'_>> {
+                if i >= $c { return None }
+                $crate::strided::Strided::new(self.as_elems(), i, $c, $r)
+            }
+
+            #[inline]
+            fn column_mut(&mut self, i: usize)
+                         -> Option<Self::ColumnMut<'_>> {
+                if i >= $c { return None }
+                $crate::strided::StridedMut::new( self.as_elems_mut()
+                                                , i, $c, $r)
+            }
+
+            #[inline]
+            fn column_owned(&self, i: usize) -> Option<$col<N>> {
+                self.column(i)
+                    .and_then(|col| $col::try_from_iter(col.iter().cloned()))
+            }
+
+        }
+
+        // columns share no elements, since each column starts at a different
+        // offset within the first row and advances by a whole row at a time.
+        unsafe impl<N> $crate::ColumnarMut for $m<N>
+        where N: Copy {
+            type ColumnsRaw = *mut N;
+
+            #[inline] fn columns_raw(&mut self) -> *mut N {
+                self.as_elems_mut().as_mut_ptr()
+            }
+
+            #[inline]
+            unsafe fn column_from_raw<'a>(raw: *mut N, i: usize)
+                                         -> $crate::strided::StridedMut<'a, N>
+            where Self: 'a {
+                $crate::strided::StridedMut::from_raw_parts(raw.add(i), $c, $r)
+            }
+        }
+    }
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_matrix_op {
+    ( $name: ident for $m: ident, $fun: ident, $op: tt
+    , $assign: ident, $assign_fun: ident, $($sub: ident),+) => {
+        impl<N> $crate::__core::ops::$name<$m<N>> for $m<N>
+        where N: $crate::__core::ops::$name<Output = N> {
+
+            type Output = $m<N>;
+            #[inline] fn $fun(self, rhs: $m<N>) -> $m<N> {
+                $m { $($sub: $crate::__lin_expr!(self.$sub $op rhs.$sub)),+ }
+            }
+        }
+
+        impl<N> $crate::__core::ops::$assign<$m<N>> for $m<N>
+        where N: $crate::__core::ops::$name<Output = N>
+            , N: Copy {
+
+            #[inline] fn $assign_fun(&mut self, rhs: $m<N>) {
+                *self = $crate::__lin_expr!(*self $op rhs)
+            }
+        }
+    }
+}
+
+/// Implements element-wise addition and subtraction, multiplication by a
+/// scalar, and the product with a vector `$row` of `$cols` elements, giving
+/// a vector `$col` of `$rows` elements, for matrices of any shape.
+///
+/// The operations which need a square matrix are implemented separately, by
+/// `impl_square_matrix_ops!`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_matrix_ops {
+    ( $m: ident, $rows: expr, $cols: expr, $row: ident, $col: ident
+    , $($sub: ident),+) => {
+        $crate::impl_matrix_op! { Add for $m, add, +, AddAssign, add_assign
+                                , $($sub),+ }
+        $crate::impl_matrix_op! { Sub for $m, sub, -, SubAssign, sub_assign
+                                , $($sub),+ }
+
+        impl<N> $crate::__core::ops::Mul<N> for $m<N>
+        where N: $crate::__core::ops::Mul<Output = N>
+            , N: Copy {
+
+            type Output = $m<N>;
+            #[inline] fn mul(self, rhs: N) -> $m<N> {
+                $m { $($sub: self.$sub * rhs),+ }
+            }
+        }
+
+        impl<N> $crate::__core::ops::MulAssign<N> for $m<N>
+        where N: $crate::__core::ops::Mul<Output = N>
+            , N: Copy {
+
+            #[inline] fn mul_assign(&mut self, rhs: N) { *self = *self * rhs }
+        }
+
+        /// Multiplies a matrix by a vector with one element per column,
+        /// giving a vector with one element per row.
+        impl<N> $crate::__core::ops::Mul<$row<N>> for $m<N>
+        where N: $crate::__core::ops::Mul<Output = N>
+            , N: $crate::__core::ops::Add<Output = N>
+            , N: Copy {
+
+            type Output = $col<N>;
+            // a matrix-vector product is a sum of products of elements
+            #[allow(clippy::suspicious_arithmetic_impl)]
+            fn mul(self, rhs: $row<N>) -> $col<N> {
+                let (a, v) = (self.as_elems(), rhs.as_elems());
+                let elems = (0..$rows).map(|y| {
+                    (1..$cols).fold(a[y * $cols] * v[0], |acc, k| {
+                        acc + a[k + y * $cols] * v[k]
+                    })
+                });
+                $col::try_from_iter(elems)
+                     .expect("product has as many elements as vector")
+            }
+        }
+    }
+}
+
+/// Implements the transpose and the product of two matrices, for square
+/// matrices of `$rows` rows and `$cols` columns.
+///
+/// Neither makes sense for any other matrix, whose transpose, or product
+/// with itself, would have a different type, so `make_matrix!` only uses
+/// this for matrices marked `square`, and this refuses to compile unless
+/// `$rows` equals `$cols`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_square_matrix_ops {
+    ($m: ident, $rows: expr, $cols: expr) => {
+        const _: () = assert!( $rows == $cols
+                             , concat!( stringify!($m)
+                                      , " is not a square matrix"));
+
+        impl<N> $m<N> {
+            /// Returns the transpose of this matrix
+            #[inline]
+            pub fn transpose(self) -> Self
+            where N: Copy {
+                let a = self.as_elems();
+                $m::try_from_iter((0..$rows * $cols).map(|i| {
+                        a[i / $cols + i % $cols * $cols]
+                    }))
+                   .expect("transpose has as many elements as matrix")
+            }
+        }
+
+        impl<N> $crate::__core::ops::Mul<$m<N>> for $m<N>
+        where N: $crate::__core::ops::Mul<Output = N>
+            , N: $crate::__core::ops::Add<Output = N>
+            , N: Copy {
+
+            type Output = $m<N>;
+            // a matrix product is a sum of products of elements
+            #[allow(clippy::suspicious_arithmetic_impl)]
+            fn mul(self, rhs: $m<N>) -> $m<N> {
+                let (a, b) = (self.as_elems(), rhs.as_elems());
+                let elems = (0..$rows * $cols).map(|i| {
+                    let (x, y) = (i % $cols, i / $cols);
+                    (1..$cols).fold(a[y * $cols] * b[x], |acc, k| {
+                        acc + a[k + y * $cols] * b[x + k * $cols]
+                    })
+                });
+                $m::try_from_iter(elems)
+                   .expect("product has as many elements as matrix")
+            }
+        }
+    }
+}
@@ -1,12 +1,16 @@
-use super::Numeric;
+use vector::{Vector2, Vector3, Vector4};
 
-use std::ops::{Add, Sub, Mul, Div, Rem};
-use std::ops;
-use std::convert;
-use std::mem::transmute;
+use std::ops::{Add, Sub, Mul};
 
 #[cfg(test)] mod test;
 #[macro_use] mod macros;
+#[cfg(feature = "alloc")]
+mod dynamic;
+mod view;
+
+#[cfg(feature = "alloc")]
+pub use self::dynamic::MatrixN;
+pub use self::view::{MatrixView, MatrixViewMut};
 
 pub trait Matrix<N>: Sized {
 
@@ -42,22 +46,71 @@ pub trait Matrix<N>: Sized {
 // , pub x1y4: N, pub x2y4: N, pub x3y4: N, pub x4y4: N
 // }
 
-make_matrix! { Matrix2, rows: 2, cols: 2
+make_matrix! { Matrix2, rows: 2, cols: 2, row: Vector2, col: Vector2
+             , square
              , x1y1, x2y1
              , x1y2, x2y2
              }
-make_matrix! { Matrix3, rows: 3, cols: 3
+make_matrix! { Matrix3, rows: 3, cols: 3, row: Vector3, col: Vector3
+             , square
              , x1y1, x2y1, x3y1
              , x1y2, x2y2, x3y2
              , x1y3, x2y3, x3y3
              }
-make_matrix! { Matrix4, rows: 4, cols: 4
+make_matrix! { Matrix4, rows: 4, cols: 4, row: Vector4, col: Vector4
+             , square
              , x1y1, x2y1, x3y1, x4y1
              , x1y2, x2y2, x3y2, x4y2
              , x1y3, x2y3, x3y3, x4y3
              , x1y4, x2y4, x3y4, x4y4
              }
 
+impl<N> Matrix2<N>
+where N: Copy + Add<Output = N> + Sub<Output = N> + Mul<Output = N> {
+
+    /// Returns the determinant of this matrix
+    #[inline]
+    pub fn determinant(self) -> N {
+        self.x1y1 * self.x2y2 - self.x2y1 * self.x1y2
+    }
+}
+
+impl<N> Matrix3<N>
+where N: Copy + Add<Output = N> + Sub<Output = N> + Mul<Output = N> {
+
+    /// Returns the determinant of this matrix, by cofactor expansion along
+    /// the first row
+    pub fn determinant(self) -> N {
+        self.x1y1 * (self.x2y2 * self.x3y3 - self.x3y2 * self.x2y3)
+            - self.x2y1 * (self.x1y2 * self.x3y3 - self.x3y2 * self.x1y3)
+            + self.x3y1 * (self.x1y2 * self.x2y3 - self.x2y2 * self.x1y3)
+    }
+}
+
+impl<N> Matrix4<N>
+where N: Copy + Add<Output = N> + Sub<Output = N> + Mul<Output = N> {
+
+    /// Returns the determinant of this matrix, by Laplace expansion along
+    /// the first two rows
+    pub fn determinant(self) -> N {
+        // the 2x2 minors of the first two rows, and of the last two rows,
+        // for each pair of columns
+        let s0 = self.x1y1 * self.x2y2 - self.x2y1 * self.x1y2;
+        let s1 = self.x1y1 * self.x3y2 - self.x3y1 * self.x1y2;
+        let s2 = self.x1y1 * self.x4y2 - self.x4y1 * self.x1y2;
+        let s3 = self.x2y1 * self.x3y2 - self.x3y1 * self.x2y2;
+        let s4 = self.x2y1 * self.x4y2 - self.x4y1 * self.x2y2;
+        let s5 = self.x3y1 * self.x4y2 - self.x4y1 * self.x3y2;
+        let c0 = self.x1y3 * self.x2y4 - self.x2y3 * self.x1y4;
+        let c1 = self.x1y3 * self.x3y4 - self.x3y3 * self.x1y4;
+        let c2 = self.x1y3 * self.x4y4 - self.x4y3 * self.x1y4;
+        let c3 = self.x2y3 * self.x3y4 - self.x3y3 * self.x2y4;
+        let c4 = self.x2y3 * self.x4y4 - self.x4y3 * self.x2y4;
+        let c5 = self.x3y3 * self.x4y4 - self.x4y3 * self.x3y4;
+        s0 * c5 - s1 * c4 + s2 * c3 + s3 * c2 - s4 * c1 + s5 * c0
+    }
+}
+
 //impl_converts! { Matrix2, 2
 //               , Matrix3, 3
 //               , Matrix4, 4
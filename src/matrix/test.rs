@@ -0,0 +1,345 @@
+use super::*;
+use quickcheck::TestResult;
+use {Columnar, ColumnarMut, Tabular, TabularMut};
+use error::LengthError;
+use strided::{Strided, StridedMut};
+
+#[test]
+fn test_m2_iter_row_major() {
+    let m = Matrix2 { x1y1: 1, x2y1: 2
+                    , x1y2: 3, x2y2: 4
+                    };
+    assert_eq!(m.iter().cloned().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    assert_eq!(m.into_iter().collect::<Vec<_>>(), vec![1, 2, 3, 4])
+}
+
+#[test]
+fn test_m2_map_sum() {
+    let m = Matrix2 { x1y1: 1, x2y1: 2
+                    , x1y2: 3, x2y2: 4
+                    };
+    let doubled = m.map(|n| n * 2);
+    assert_eq!(doubled, Matrix2 { x1y1: 2, x2y1: 4
+                                , x1y2: 6, x2y2: 8
+                                });
+    assert_eq!(vec![m, m].into_iter().sum::<Matrix2<i32>>(), doubled)
+}
+
+#[test]
+fn test_m3_from_iter() {
+    let m: Matrix3<i32> = (0..9).collect();
+    assert_eq!(m[(2, 0)], 2);
+    assert_eq!(m[(0, 2)], 6);
+    assert_eq!(Matrix3::try_from_iter(0..8), None)
+}
+
+#[test]
+fn test_m3_rows() {
+    let m: Matrix3<i32> = (0..9).collect();
+    assert_eq!(m.row(1), Some(&Vector3 { x: 3, y: 4, z: 5 }));
+    assert_eq!(m.row(3), None);
+    assert_eq!( m.rows_iter().next_back()
+              , Some(&Vector3 { x: 6, y: 7, z: 8 }))
+}
+
+#[test]
+fn test_m3_columns() {
+    let m: Matrix3<i32> = (0..9).collect();
+    let col = m.column(1).unwrap();
+    assert_eq!(col.iter().cloned().collect::<Vec<_>>(), vec![1, 4, 7]);
+    assert_eq!(col[2], 7);
+    assert_eq!(m.column_owned(2), Some(Vector3 { x: 2, y: 5, z: 8 }));
+    assert!(m.column(3).is_none());
+    assert_eq!(m.cols_iter().len(), 3)
+}
+
+#[test]
+fn test_m3_rows_iter_mut_elimination() {
+    let mut m = Matrix3 { x1y1: 1, x2y1: 2, x3y1: 3
+                        , x1y2: 2, x2y2: 5, x3y2: 8
+                        , x1y3: 3, x2y3: 7, x3y3: 12
+                        };
+    {
+        let mut rows = m.rows_iter_mut();
+        let pivot = *rows.next().unwrap();
+        for row in rows {
+            let factor = row.x / pivot.x;
+            *row -= pivot * factor;
+        }
+    }
+    assert_eq!(m, Matrix3 { x1y1: 1, x2y1: 2, x3y1: 3
+                          , x1y2: 0, x2y2: 1, x3y2: 2
+                          , x1y3: 0, x2y3: 1, x3y3: 3
+                          })
+}
+
+#[test]
+fn test_m2_cols_iter_mut() {
+    let mut m = Matrix2 { x1y1: 1, x2y1: 2
+                        , x1y2: 3, x2y2: 4
+                        };
+    for (i, mut col) in m.cols_iter_mut().enumerate() {
+        for n in col.iter_mut() { *n *= i as i32 + 1 }
+    }
+    assert_eq!(m, Matrix2 { x1y1: 1, x2y1: 4
+                          , x1y2: 3, x2y2: 8
+                          })
+}
+
+#[test]
+fn test_cols_rows_iter_mut_all_at_once() {
+    let mut m: Matrix3<i32> = (0..9).collect();
+    {
+        let mut cols = m.cols_iter_mut().collect::<Vec<_>>();
+        for (i, col) in cols.iter_mut().enumerate() { col[2] = -(i as i32) }
+        cols[0][0] = 100;
+    }
+    assert_eq!(m.as_elems(), &[100, 1, 2, 3, 4, 5, 0, -1, -2]);
+
+    let mut n = MatrixN::from_fn(3, 2, |x, y| x + 2 * y);
+    {
+        let mut rows = n.rows_iter_mut().collect::<Vec<_>>();
+        let (first, rest) = rows.split_at_mut(1);
+        first[0].swap_with_slice(rest[1]);
+        rows[1][0] = 9;
+    }
+    assert_eq!(n.as_slice(), &[4, 5, 9, 3, 0, 1]);
+}
+
+#[test]
+fn test_cols_iter_mut_read_while_writing_sibling() {
+    let mut m = Matrix2 { x1y1: 1, x2y1: 2
+                        , x1y2: 3, x2y2: 4
+                        };
+    {
+        let mut cols = m.cols_iter_mut();
+        let (c0, mut c1) = (cols.next().unwrap(), cols.next().unwrap());
+        for x in c0.iter() { c1[0] += *x }
+        assert_eq!(c0.iter().rev().cloned().collect::<Vec<_>>(), vec![3, 1]);
+    }
+    assert_eq!(m.as_elems(), &[1, 6, 3, 4]);
+}
+
+#[test]
+fn test_strided_rejects_overflowing_strides() {
+    let mut data = [1u64, 2, 3, 4];
+    assert!(Strided::new(&data, 0, usize::MAX, 2).is_none());
+    assert!(Strided::new(&data, 1, usize::MAX / 2 + 1, 3).is_none());
+    assert!(StridedMut::new(&mut data, 0, usize::MAX, 2).is_none());
+    assert!(StridedMut::new(&mut data, 3, usize::MAX, 1).is_some());
+    let view = StridedMut::new(&mut data, 1, 2, 2).unwrap();
+    assert_eq!(view.iter().cloned().collect::<Vec<_>>(), vec![2, 4]);
+}
+
+macro_rules! index_agreement_props {
+    ($($fun:ident, $ty:ident, $rows:expr, $cols:expr),*) => {$(
+        #[quickcheck]
+        fn $fun(elems: Vec<isize>) -> TestResult {
+            if elems.len() < $rows * $cols {
+                return TestResult::discard()
+            }
+            let m = $ty::try_from_iter(elems.iter().cloned()
+                                            .take($rows * $cols))
+                        .unwrap();
+            let arr: &[[isize; $cols]; $rows] = m.as_ref();
+            let rows_agree = (0..$rows).all(|y| {
+                let row = m.row(y).unwrap();
+                (0..$cols).all(|x| m[(x, y)] == elems[x + y * $cols]
+                                && m[(x, y)] == arr[y][x]
+                                && row[x] == arr[y][x])
+            });
+            let cols_agree = (0..$cols).all(|x| {
+                let col = m.column(x).unwrap();
+                let owned = m.column_owned(x).unwrap();
+                col.len() == $rows
+                && (0..$rows).all(|y| col[y] == arr[y][x]
+                                   && owned[y] == arr[y][x])
+            });
+            TestResult::from_bool(
+                m.rows_iter().count() == $rows
+                && m.cols_iter().count() == $cols
+                && m.row($rows).is_none()
+                && m.column($cols).is_none()
+                && rows_agree && cols_agree)
+        }
+    )*}
+}
+
+index_agreement_props!( prop_m2_index_agreement, Matrix2, 2, 2
+                      , prop_m3_index_agreement, Matrix3, 3, 3
+                      , prop_m4_index_agreement, Matrix4, 4, 4
+                      );
+
+#[test]
+fn test_m2_checked_saturating_arith() {
+    let m = Matrix2 { x1y1: 1i16, x2y1: i16::MAX
+                    , x1y2: -1, x2y2: i16::MIN
+                    };
+    assert_eq!(m.checked_add(m), None);
+    assert_eq!(m.checked_mul(1), Some(m));
+    assert_eq!(m.saturating_add(m), Matrix2 { x1y1: 2, x2y1: i16::MAX
+                                            , x1y2: -2, x2y2: i16::MIN
+                                            });
+    assert_eq!(m.wrapping_sub(m), Matrix2::default())
+}
+
+#[test]
+fn test_m2_nested_array_conversions() {
+    let m = Matrix2::from([[1, 2], [3, 4]]);
+    assert_eq!(m, Matrix2 { x1y1: 1, x2y1: 2
+                          , x1y2: 3, x2y2: 4
+                          });
+    assert_eq!(<[[i32; 2]; 2]>::from(m), [[1, 2], [3, 4]]);
+    let r: &Matrix2<i32> = (&[[1, 2], [3, 4]]).into();
+    assert_eq!(*r, m);
+    assert_eq!(Matrix2::as_slice_of(&[m, m]).len(), 8)
+}
+
+#[test]
+fn test_matrix_n_rows_cols() {
+    let mut m = MatrixN::from_fn(2, 3, |x, y| x + 10 * y);
+    assert_eq!(m.as_slice(), &[0, 1, 2, 10, 11, 12]);
+    assert_eq!(m[(2, 1)], 12);
+    assert_eq!(m.row(1), Some(&[10, 11, 12][..]));
+    assert_eq!(m.column_owned(1), Some(vec![1, 11]));
+    for mut col in m.cols_iter_mut() { col[0] += 100 }
+    assert_eq!(m.transpose().as_slice(), &[100, 10, 101, 11, 102, 12]);
+    let m2 = Matrix2 { x1y1: 1, x2y1: 2, x1y2: 3, x2y2: 4 };
+    assert_eq!(MatrixN::from(m2).as_slice(), &[1, 2, 3, 4]);
+}
+
+#[test]
+fn test_matrix_n_rejects_overflowing_sizes() {
+    // `usize::MAX * 2` wraps around to `usize::MAX - 1`
+    let elems = vec![(); usize::MAX - 1];
+    assert_eq!( MatrixN::from_vec(usize::MAX, 2, elems)
+              , Err(LengthError::Exact { expected: usize::MAX
+                                       , found: usize::MAX - 1 }));
+}
+
+#[test]
+fn test_m2_arithmetic() {
+    let a = Matrix2 { x1y1: 1, x2y1: 2, x1y2: 3, x2y2: 4 };
+    let b = Matrix2 { x1y1: 5, x2y1: 6, x1y2: 7, x2y2: 8 };
+    assert_eq!(a + b, Matrix2 { x1y1: 6, x2y1: 8, x1y2: 10, x2y2: 12 });
+    assert_eq!(b - a, Matrix2 { x1y1: 4, x2y1: 4, x1y2: 4, x2y2: 4 });
+    assert_eq!(a * 2, Matrix2 { x1y1: 2, x2y1: 4, x1y2: 6, x2y2: 8 });
+    assert_eq!(a * b, Matrix2 { x1y1: 19, x2y1: 22, x1y2: 43, x2y2: 50 });
+    assert_eq!(a * Vector2 { x: 5, y: 6 }, Vector2 { x: 17, y: 39 });
+    let mut c = a;
+    c += b;
+    c -= a;
+    c *= 3;
+    assert_eq!(c, b * 3)
+}
+
+#[test]
+fn test_transpose_determinant() {
+    let m2 = Matrix2 { x1y1: 1, x2y1: 2, x1y2: 3, x2y2: 4 };
+    assert_eq!(m2.transpose(), Matrix2 { x1y1: 1, x2y1: 3, x1y2: 2, x2y2: 4 });
+    assert_eq!(m2.determinant(), -2);
+    let m3 = Matrix3::try_from_iter(vec![ 2, 0, 1
+                                        , 1, 3, 2
+                                        , 1, 1, 2 ]).unwrap();
+    assert_eq!(m3.determinant(), 6);
+    assert_eq!(m3.transpose().determinant(), 6);
+    assert_eq!(m3.transpose().row(0), Some(&Vector3 { x: 2, y: 1, z: 1 }));
+    let m4 = Matrix4::try_from_iter(vec![ 1, 0, 2, -1
+                                        , 3, 0, 0, 5
+                                        , 2, 1, 4, -3
+                                        , 1, 0, 5, 0 ]).unwrap();
+    assert_eq!(m4.determinant(), 30);
+    assert_eq!(m4.transpose().determinant(), 30)
+}
+
+additive_laws! { m2_i32_laws, Matrix2, i32 }
+additive_laws! { m3_f64_laws, Matrix3, f64 }
+additive_laws! { m4_f32_laws, Matrix4, f32 }
+product_laws! { m2_f32_product_laws, Matrix2, f32, 2 }
+product_laws! { m3_i64_product_laws, Matrix3, i64, 3 }
+product_laws! { m4_i32_product_laws, Matrix4, i32, 4 }
+product_laws! { m4_f64_product_laws, Matrix4, f64, 4 }
+
+#[test]
+fn test_views() {
+    let elems = (0..12).collect::<Vec<_>>();
+    let v = MatrixView::from_row_major(&elems, 3, 4).unwrap();
+    assert_eq!(v[(1, 2)], 9);
+    assert_eq!(v.transpose()[(2, 1)], 9);
+    assert_eq!(v.transpose().shape(), (4, 3));
+    let b = v.block(1, 2, 2, 2).unwrap();
+    assert_eq!((b[(0, 0)], b[(1, 1)]), (6, 11));
+    assert!(v.block(2, 2, 2, 2).is_none());
+    assert_eq!(b.row(1).unwrap().iter().collect::<Vec<_>>(), vec![&10, &11]);
+    assert_eq!(v.column(3).unwrap().iter().cloned().collect::<Vec<_>>()
+              , vec![3, 7, 11]);
+    let c = MatrixView::from_col_major(&elems, 3, 4).unwrap();
+    assert_eq!(c[(1, 2)], 5);
+    assert!(MatrixView::from_row_major(&elems, 4, 4).is_none());
+    assert_eq!( MatrixN::from(v.transpose()).as_slice()
+              , MatrixN::from(v).transpose().as_slice());
+}
+
+#[test]
+fn test_views_mut() {
+    let mut elems = vec![0; 12];
+    // overlapping rows, or a repeated element, can't be borrowed mutably
+    assert!(MatrixViewMut::new(&mut elems, 3, 4, 2, 1).is_none());
+    assert!(MatrixViewMut::new(&mut elems, 2, 2, 0, 1).is_none());
+    assert!(MatrixViewMut::new(&mut elems, 1, 2, 0, 1).is_some());
+    let mut v = MatrixViewMut::from_row_major(&mut elems, 3, 4).unwrap();
+    v[(1, 2)] = 1;
+    {
+        let mut b = v.block_mut(1, 1, 2, 3).unwrap().transpose();
+        b[(1, 2)] = 2;
+        for elem in b.row_mut(0).unwrap().iter_mut() { *elem += 10 }
+    }
+    for elem in v.column_mut(0).unwrap().iter_mut() { *elem = 3 }
+    assert_eq!(v.as_view()[(3, 2)], 2);
+    assert_eq!(elems, vec![ 3, 0, 0, 0
+                          , 3, 10, 0, 0
+                          , 3, 11, 0, 2 ]);
+
+    let v = MatrixViewMut::from_row_major(&mut elems, 3, 4).unwrap();
+    let (mut top, bottom) = v.split_at_row(1);
+    let (mut left, mut right) = bottom.split_at_col(3);
+    assert_eq!( (top.shape(), left.shape(), right.shape())
+              , ((1, 4), (2, 3), (2, 1)));
+    top[(3, 0)] = 4;
+    left[(2, 1)] = 5;
+    right[(0, 0)] = 6;
+    assert_eq!(elems, vec![ 3, 0, 0, 4
+                          , 3, 10, 0, 6
+                          , 3, 11, 5, 2 ]);
+}
+
+#[test]
+fn test_views_read_while_writing_sibling() {
+    let mut elems = vec![1, 2, 3, 4];
+    {
+        let v = MatrixViewMut::from_row_major(&mut elems, 2, 2).unwrap();
+        let (left, mut right) = v.split_at_col(1);
+        let view = left.as_view();
+        for elem in view.column(0).unwrap() { right[(0, 0)] += *elem }
+        right[(0, 1)] = view[(0, 1)] * 10;
+        assert_eq!(view.row(1).unwrap().iter().collect::<Vec<_>>(), vec![&3]);
+    }
+    assert_eq!(elems, vec![1, 6, 3, 30]);
+}
+
+#[test]
+fn test_views_reject_overflowing_strides() {
+    let mut data = [1u64, 2, 3, 4];
+    assert!(MatrixView::new(&data, 2, 1, usize::MAX, 1).is_none());
+    assert!(MatrixView::new(&data, 1, 3, 0, usize::MAX / 2 + 1).is_none());
+    assert!(MatrixViewMut::new(&mut data, 2, 1, usize::MAX, 1).is_none());
+    assert!(MatrixViewMut::new(&mut data, 2, 2, usize::MAX / 2 + 1, 1)
+                .is_none());
+    // a single row's stride is never used
+    assert!(MatrixViewMut::new(&mut data, 1, 2, usize::MAX, 1).is_some());
+
+    let v = MatrixView::from_row_major(&data, 2, 2).unwrap();
+    assert!(v.block(usize::MAX, 0, 2, 1).is_none());
+    let v = MatrixViewMut::from_row_major(&mut data, 2, 2).unwrap();
+    assert!(v.into_block(0, usize::MAX, 1, 2).is_none());
+}
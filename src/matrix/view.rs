@@ -0,0 +1,507 @@
+//! Borrowed views of matrices stored in slices.
+//!
+//! A view selects `nrows * ncols` elements of a slice, where the element at
+//! column `x` and row `y` lies at `y * row_stride + x * col_stride`. A
+//! row-major matrix has a column stride of one, a column-major matrix has a
+//! row stride of one, and swapping the two strides transposes a view
+//! without moving any elements.
+
+use strided::{Strided, StridedMut};
+
+use std::ops;
+use std::marker::PhantomData;
+
+/// An immutable view of a matrix.
+///
+/// Like `Strided`, this holds a raw pointer rather than a slice, so that a
+/// view never borrows the elements it skips over; those may belong to a
+/// sibling `MatrixViewMut` produced by a split.
+#[derive(Debug)]
+pub struct MatrixView<'a, N: 'a> { ptr: *const N
+                                 , nrows: usize
+                                 , ncols: usize
+                                 , row_stride: usize
+                                 , col_stride: usize
+                                 , _lifetime: PhantomData<&'a N>
+                                 }
+
+// an immutable view behaves like a `&` to each of its elements.
+unsafe impl<'a, N> Send for MatrixView<'a, N> where N: Sync {}
+unsafe impl<'a, N> Sync for MatrixView<'a, N> where N: Sync {}
+
+impl<'a, N> Clone for MatrixView<'a, N> {
+    #[inline] fn clone(&self) -> Self { *self }
+}
+
+impl<'a, N> Copy for MatrixView<'a, N> {}
+
+/// A mutable view of a matrix.
+#[derive(Debug)]
+pub struct MatrixViewMut<'a, N: 'a> { ptr: *mut N
+                                    , nrows: usize
+                                    , ncols: usize
+                                    , row_stride: usize
+                                    , col_stride: usize
+                                    , _lifetime: PhantomData<&'a mut N>
+                                    }
+
+// a mutable view behaves like a `&mut` to each of its elements.
+unsafe impl<'a, N> Send for MatrixViewMut<'a, N> where N: Send {}
+unsafe impl<'a, N> Sync for MatrixViewMut<'a, N> where N: Sync {}
+
+/// Returns the number of elements of a slice needed to hold a view, or
+/// `None` if that number overflows a `usize`.
+#[inline]
+fn span( nrows: usize, ncols: usize
+       , row_stride: usize, col_stride: usize) -> Option<usize> {
+    if nrows == 0 || ncols == 0 { return Some(0) }
+    (nrows - 1).checked_mul(row_stride)?
+               .checked_add((ncols - 1).checked_mul(col_stride)?)?
+               .checked_add(1)
+}
+
+/// Returns true if a view with this layout lies within a slice of `len`
+/// elements.
+#[inline]
+fn fits( len: usize, nrows: usize, ncols: usize
+       , row_stride: usize, col_stride: usize) -> bool {
+    span(nrows, ncols, row_stride, col_stride).is_some_and(|n| n <= len)
+}
+
+/// Returns true if the `len` rows or columns starting at `start` lie within
+/// the first `bound`.
+#[inline]
+fn within(start: usize, len: usize, bound: usize) -> bool {
+    start.checked_add(len).is_some_and(|end| end <= bound)
+}
+
+/// Returns true if no two positions in a view with this layout refer to the
+/// same element.
+///
+/// This is the case when one dimension is laid out entirely within a single
+/// step of the other, as for any row- or column-major matrix, or a block of
+/// one.
+#[inline]
+fn is_disjoint( nrows: usize, ncols: usize
+              , row_stride: usize, col_stride: usize) -> bool {
+    if nrows <= 1 { ncols <= 1 || col_stride > 0 }
+    else if ncols <= 1 { row_stride > 0 }
+    else {
+        let spans = |outer: usize, inner: usize, n: usize| {
+            inner > 0 && inner.checked_mul(n).is_some_and(|w| outer >= w)
+        };
+        spans(row_stride, col_stride, ncols)
+        || spans(col_stride, row_stride, nrows)
+    }
+}
+
+impl<'a, N> MatrixView<'a, N> {
+
+    /// Construct a new view of `data`, in which the element at column `x`
+    /// and row `y` is `data[y * row_stride + x * col_stride]`.
+    ///
+    /// # Returns:
+    ///   - `Some(MatrixView)` if every element of the view lies within
+    ///     `data`
+    ///   - `None` if the view would extend past the end of `data`
+    ///
+    pub fn new( data: &'a [N], nrows: usize, ncols: usize
+              , row_stride: usize, col_stride: usize) -> Option<Self> {
+        if !fits(data.len(), nrows, ncols, row_stride, col_stride) {
+            return None
+        }
+        Some(MatrixView { ptr: data.as_ptr()
+                        , nrows
+                        , ncols
+                        , row_stride
+                        , col_stride
+                        , _lifetime: PhantomData
+                        })
+    }
+
+    /// Construct a new view of a matrix stored in row-major order.
+    ///
+    /// # Returns:
+    ///   - `Some(MatrixView)` if `data` holds at least `nrows * ncols`
+    ///     elements
+    ///   - `None` otherwise
+    ///
+    #[inline]
+    pub fn from_row_major(data: &'a [N], nrows: usize, ncols: usize)
+                         -> Option<Self> {
+        MatrixView::new(data, nrows, ncols, ncols, 1)
+    }
+
+    /// Construct a new view of a matrix stored in column-major order.
+    ///
+    /// # Returns:
+    ///   - `Some(MatrixView)` if `data` holds at least `nrows * ncols`
+    ///     elements
+    ///   - `None` otherwise
+    ///
+    #[inline]
+    pub fn from_col_major(data: &'a [N], nrows: usize, ncols: usize)
+                         -> Option<Self> {
+        MatrixView::new(data, nrows, ncols, 1, nrows)
+    }
+
+    /// Returns the number of rows
+    #[inline] pub fn nrows(&self) -> usize { self.nrows }
+
+    /// Returns the number of columns
+    #[inline] pub fn ncols(&self) -> usize { self.ncols }
+
+    /// Returns the number of rows and columns, as `(rows, columns)`
+    #[inline]
+    pub fn shape(&self) -> (usize, usize) { (self.nrows, self.ncols) }
+
+    /// Returns the distance between the starts of consecutive rows
+    #[inline] pub fn row_stride(&self) -> usize { self.row_stride }
+
+    /// Returns the distance between the starts of consecutive columns
+    #[inline] pub fn col_stride(&self) -> usize { self.col_stride }
+
+    /// Select an element by column and row
+    ///
+    /// # Returns:
+    ///   - `Some(&N)` if an element exists at the given position
+    ///   - `None` if the position is out of bounds
+    ///
+    #[inline]
+    pub fn get(&self, x: usize, y: usize) -> Option<&'a N> {
+        if x < self.ncols && y < self.nrows {
+            let i = y * self.row_stride + x * self.col_stride;
+            Some(unsafe { &*self.ptr.add(i) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the transpose of this view, without moving any elements
+    #[inline]
+    pub fn transpose(self) -> Self {
+        MatrixView { ptr: self.ptr
+                   , nrows: self.ncols
+                   , ncols: self.nrows
+                   , row_stride: self.col_stride
+                   , col_stride: self.row_stride
+                   , _lifetime: PhantomData
+                   }
+    }
+
+    /// Select the block of `nrows` rows and `ncols` columns whose top left
+    /// element is at row `row` and column `col`.
+    ///
+    /// # Returns:
+    ///   - `Some(MatrixView)` if the block lies within this view
+    ///   - `None` otherwise
+    ///
+    pub fn block(&self, row: usize, col: usize, nrows: usize, ncols: usize)
+                -> Option<MatrixView<'a, N>> {
+        if !within(row, nrows, self.nrows) || !within(col, ncols, self.ncols) {
+            return None
+        }
+        let start = if nrows == 0 || ncols == 0 { 0 }
+                    else { row * self.row_stride + col * self.col_stride };
+        Some(MatrixView { ptr: unsafe { self.ptr.add(start) }
+                        , nrows
+                        , ncols
+                        , row_stride: self.row_stride
+                        , col_stride: self.col_stride
+                        , _lifetime: PhantomData
+                        })
+    }
+
+    /// Select a row by index
+    ///
+    /// # Returns:
+    ///   - `Some(Strided)` if a row exists for the given index
+    ///   - `None` if the index is out of bounds, or the columns of this
+    ///     view have a stride of zero
+    ///
+    #[inline]
+    pub fn row(&self, y: usize) -> Option<Strided<'a, N>> {
+        if y >= self.nrows || self.col_stride == 0 { return None }
+        Some(unsafe {
+            Strided::from_raw_parts( self.ptr.add(y * self.row_stride)
+                                   , self.col_stride, self.ncols)
+        })
+    }
+
+    /// Select a column by index
+    ///
+    /// # Returns:
+    ///   - `Some(Strided)` if a column exists for the given index
+    ///   - `None` if the index is out of bounds, or the rows of this view
+    ///     have a stride of zero
+    ///
+    #[inline]
+    pub fn column(&self, x: usize) -> Option<Strided<'a, N>> {
+        if x >= self.ncols || self.row_stride == 0 { return None }
+        Some(unsafe {
+            Strided::from_raw_parts( self.ptr.add(x * self.col_stride)
+                                   , self.row_stride, self.nrows)
+        })
+    }
+}
+
+impl<'a, N> ops::Index<(usize, usize)> for MatrixView<'a, N> {
+    type Output = N;
+
+    #[inline] fn index(&self, (x, y): (usize, usize)) -> &N {
+        self.get(x, y).expect("matrix view index out of bounds")
+    }
+}
+
+impl<'a, N> MatrixViewMut<'a, N> {
+
+    /// Construct a new mutable view of `data`, in which the element at
+    /// column `x` and row `y` is `data[y * row_stride + x * col_stride]`.
+    ///
+    /// # Returns:
+    ///   - `Some(MatrixViewMut)` if every element of the view lies within
+    ///     `data`, and no two positions refer to the same element
+    ///   - `None` otherwise
+    ///
+    pub fn new( data: &'a mut [N], nrows: usize, ncols: usize
+              , row_stride: usize, col_stride: usize) -> Option<Self> {
+        if !fits(data.len(), nrows, ncols, row_stride, col_stride)
+           || !is_disjoint(nrows, ncols, row_stride, col_stride) {
+            return None
+        }
+        Some(MatrixViewMut { ptr: data.as_mut_ptr()
+                           , nrows
+                           , ncols
+                           , row_stride
+                           , col_stride
+                           , _lifetime: PhantomData
+                           })
+    }
+
+    /// Construct a new mutable view of a matrix stored in row-major order.
+    ///
+    /// # Returns:
+    ///   - `Some(MatrixViewMut)` if `data` holds at least `nrows * ncols`
+    ///     elements
+    ///   - `None` otherwise
+    ///
+    #[inline]
+    pub fn from_row_major(data: &'a mut [N], nrows: usize, ncols: usize)
+                         -> Option<Self> {
+        MatrixViewMut::new(data, nrows, ncols, ncols, 1)
+    }
+
+    /// Construct a new mutable view of a matrix stored in column-major
+    /// order.
+    ///
+    /// # Returns:
+    ///   - `Some(MatrixViewMut)` if `data` holds at least `nrows * ncols`
+    ///     elements
+    ///   - `None` otherwise
+    ///
+    #[inline]
+    pub fn from_col_major(data: &'a mut [N], nrows: usize, ncols: usize)
+                         -> Option<Self> {
+        MatrixViewMut::new(data, nrows, ncols, 1, nrows)
+    }
+
+    /// Returns the number of rows
+    #[inline] pub fn nrows(&self) -> usize { self.nrows }
+
+    /// Returns the number of columns
+    #[inline] pub fn ncols(&self) -> usize { self.ncols }
+
+    /// Returns the number of rows and columns, as `(rows, columns)`
+    #[inline]
+    pub fn shape(&self) -> (usize, usize) { (self.nrows, self.ncols) }
+
+    /// Returns the distance between the starts of consecutive rows
+    #[inline] pub fn row_stride(&self) -> usize { self.row_stride }
+
+    /// Returns the distance between the starts of consecutive columns
+    #[inline] pub fn col_stride(&self) -> usize { self.col_stride }
+
+    /// Select an element by column and row
+    ///
+    /// # Returns:
+    ///   - `Some(&N)` if an element exists at the given position
+    ///   - `None` if the position is out of bounds
+    ///
+    #[inline]
+    pub fn get(&self, x: usize, y: usize) -> Option<&N> {
+        if x < self.ncols && y < self.nrows {
+            let i = y * self.row_stride + x * self.col_stride;
+            Some(unsafe { &*self.ptr.add(i) })
+        } else {
+            None
+        }
+    }
+
+    /// Mutably select an element by column and row
+    ///
+    /// # Returns:
+    ///   - `Some(&mut N)` if an element exists at the given position
+    ///   - `None` if the position is out of bounds
+    ///
+    #[inline]
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut N> {
+        if x < self.ncols && y < self.nrows {
+            let i = y * self.row_stride + x * self.col_stride;
+            Some(unsafe { &mut *self.ptr.add(i) })
+        } else {
+            None
+        }
+    }
+
+    /// Reborrow this view immutably
+    #[inline]
+    pub fn as_view(&self) -> MatrixView<'_, N> {
+        MatrixView { ptr: self.ptr
+                   , nrows: self.nrows
+                   , ncols: self.ncols
+                   , row_stride: self.row_stride
+                   , col_stride: self.col_stride
+                   , _lifetime: PhantomData
+                   }
+    }
+
+    /// Reborrow this view mutably, for a shorter lifetime
+    #[inline]
+    pub fn reborrow(&mut self) -> MatrixViewMut<'_, N> {
+        MatrixViewMut { ptr: self.ptr
+                      , nrows: self.nrows
+                      , ncols: self.ncols
+                      , row_stride: self.row_stride
+                      , col_stride: self.col_stride
+                      , _lifetime: PhantomData
+                      }
+    }
+
+    /// Returns the transpose of this view, without moving any elements
+    #[inline]
+    pub fn transpose(self) -> Self {
+        MatrixViewMut { ptr: self.ptr
+                      , nrows: self.ncols
+                      , ncols: self.nrows
+                      , row_stride: self.col_stride
+                      , col_stride: self.row_stride
+                      , _lifetime: PhantomData
+                      }
+    }
+
+    /// Select the block of `nrows` rows and `ncols` columns whose top left
+    /// element is at row `row` and column `col`, consuming this view.
+    ///
+    /// # Returns:
+    ///   - `Some(MatrixViewMut)` if the block lies within this view
+    ///   - `None` otherwise
+    ///
+    pub fn into_block( self, row: usize, col: usize
+                     , nrows: usize, ncols: usize)
+                     -> Option<MatrixViewMut<'a, N>> {
+        if !within(row, nrows, self.nrows) || !within(col, ncols, self.ncols) {
+            return None
+        }
+        let start = if nrows == 0 || ncols == 0 { 0 }
+                    else { row * self.row_stride + col * self.col_stride };
+        Some(MatrixViewMut { ptr: unsafe { self.ptr.add(start) }
+                           , nrows
+                           , ncols
+                           , row_stride: self.row_stride
+                           , col_stride: self.col_stride
+                           , _lifetime: PhantomData
+                           })
+    }
+
+    /// Mutably select the block of `nrows` rows and `ncols` columns whose
+    /// top left element is at row `row` and column `col`.
+    ///
+    /// # Returns:
+    ///   - `Some(MatrixViewMut)` if the block lies within this view
+    ///   - `None` otherwise
+    ///
+    #[inline]
+    pub fn block_mut( &mut self, row: usize, col: usize
+                    , nrows: usize, ncols: usize)
+                    -> Option<MatrixViewMut<'_, N>> {
+        self.reborrow().into_block(row, col, nrows, ncols)
+    }
+
+    /// Split this view into the rows above row `y` and the rows from `y`
+    /// onward, consuming it.
+    ///
+    /// # Panics
+    ///   - if `y` is greater than the number of rows
+    ///
+    pub fn split_at_row(self, y: usize)
+                       -> (MatrixViewMut<'a, N>, MatrixViewMut<'a, N>) {
+        assert!(y <= self.nrows, "split row out of bounds");
+        let (nrows, ncols) = self.shape();
+        // the view is disjoint, so distinct rows never share an element.
+        let lower = MatrixViewMut { ptr: self.ptr, ..self }
+                        .into_block(y, 0, nrows - y, ncols)
+                        .expect("lower rows lie within the view");
+        let upper = self.into_block(0, 0, y, ncols)
+                        .expect("upper rows lie within the view");
+        (upper, lower)
+    }
+
+    /// Split this view into the columns left of column `x` and the columns
+    /// from `x` onward, consuming it.
+    ///
+    /// # Panics
+    ///   - if `x` is greater than the number of columns
+    ///
+    #[inline]
+    pub fn split_at_col(self, x: usize)
+                       -> (MatrixViewMut<'a, N>, MatrixViewMut<'a, N>) {
+        let (left, right) = self.transpose().split_at_row(x);
+        (left.transpose(), right.transpose())
+    }
+
+    /// Mutably select a row by index
+    ///
+    /// # Returns:
+    ///   - `Some(StridedMut)` if a row exists for the given index
+    ///   - `None` if the index is out of bounds
+    ///
+    #[inline]
+    pub fn row_mut(&mut self, y: usize) -> Option<StridedMut<'_, N>> {
+        if y >= self.nrows { return None }
+        // the view is disjoint, so its rows are too.
+        Some(unsafe {
+            StridedMut::from_raw_parts( self.ptr.add(y * self.row_stride)
+                                      , self.col_stride, self.ncols)
+        })
+    }
+
+    /// Mutably select a column by index
+    ///
+    /// # Returns:
+    ///   - `Some(StridedMut)` if a column exists for the given index
+    ///   - `None` if the index is out of bounds
+    ///
+    #[inline]
+    pub fn column_mut(&mut self, x: usize) -> Option<StridedMut<'_, N>> {
+        if x >= self.ncols { return None }
+        Some(unsafe {
+            StridedMut::from_raw_parts( self.ptr.add(x * self.col_stride)
+                                      , self.row_stride, self.nrows)
+        })
+    }
+}
+
+impl<'a, N> ops::Index<(usize, usize)> for MatrixViewMut<'a, N> {
+    type Output = N;
+
+    #[inline] fn index(&self, (x, y): (usize, usize)) -> &N {
+        self.get(x, y).expect("matrix view index out of bounds")
+    }
+}
+
+impl<'a, N> ops::IndexMut<(usize, usize)> for MatrixViewMut<'a, N> {
+
+    #[inline] fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut N {
+        self.get_mut(x, y).expect("matrix view index out of bounds")
+    }
+}
@@ -0,0 +1,326 @@
+//! Parsing vectors and matrices from text.
+//!
+//! Vectors may be written with their elements separated by commas and/or
+//! whitespace, optionally enclosed in parentheses or square brackets, such
+//! as `(1, 2, 3)`, `[1 2 3]` or `1, 2, 3`.
+//!
+//! Matrices are written as a sequence of rows, in any of the following
+//! forms:
+//!
+//!   - one row per line, or rows separated by semicolons, optionally enclosed
+//!     in brackets: `[1 2; 3 4]`, or
+//!
+//!     ```text
+//!     [1 2
+//!      3 4]
+//!     ```
+//!   - each row enclosed in brackets, optionally with the whole matrix also
+//!     enclosed in brackets: `[[1, 2], [3, 4]]`, or
+//!
+//!     ```text
+//!     [1 2]
+//!     [3 4]
+//!     ```
+//!
+//! The last of these is the form produced by the `Display` implementations
+//! of the matrix types.
+
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(test)] mod test;
+
+/// An error encountered while parsing a vector or matrix.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// The byte offset in the input at which the error was detected
+    pub position: usize,
+    /// What went wrong
+    pub kind: ParseErrorKind,
+}
+
+/// The kinds of error which may be encountered while parsing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// A character which was not expected at this position, such as a
+    /// mismatched bracket
+    UnexpectedChar(char),
+    /// The input ended while a bracket was still open
+    UnexpectedEnd,
+    /// An element could not be parsed as the element type
+    InvalidElement(String),
+    /// A vector or matrix row had the wrong number of elements
+    WrongLength { expected: usize, found: usize },
+    /// A matrix had the wrong number of rows
+    WrongRowCount { expected: usize, found: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ParseErrorKind::UnexpectedChar(c) =>
+                write!(f, "unexpected character `{}`", c)?
+          , ParseErrorKind::UnexpectedEnd =>
+                write!(f, "unexpected end of input")?
+          , ParseErrorKind::InvalidElement(ref elem) =>
+                write!(f, "invalid element `{}`", elem)?
+          , ParseErrorKind::WrongLength { expected, found } =>
+                write!(f, "expected {} elements, found {}", expected, found)?
+          , ParseErrorKind::WrongRowCount { expected, found } =>
+                write!(f, "expected {} rows, found {}", expected, found)?
+        }
+        write!(f, " at position {}", self.position)
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Token<'a> { Open(char)
+               , Close(char)
+               , Comma
+               , RowSep
+               , Elem(&'a str)
+               }
+
+/// A row of elements, and the positions at which it starts and ends.
+struct Row<'a> { start: usize
+               , end: usize
+               , elems: Vec<(usize, &'a str)>
+               }
+
+fn tokenize(s: &str) -> Vec<(usize, Token<'_>)> {
+    let mut tokens = Vec::new();
+    let mut elem_start = None;
+    for (i, c) in s.char_indices() {
+        let token = match c {
+            '(' | '[' => Some(Token::Open(c))
+          , ')' | ']' => Some(Token::Close(c))
+          , ','       => Some(Token::Comma)
+          , ';' | '\n' => Some(Token::RowSep)
+          , c if c.is_whitespace() => None
+          , _ => {
+                if elem_start.is_none() { elem_start = Some(i) }
+                continue
+            }
+        };
+        if let Some(start) = elem_start.take() {
+            tokens.push((start, Token::Elem(&s[start..i])))
+        }
+        if let Some(token) = token { tokens.push((i, token)) }
+    }
+    if let Some(start) = elem_start {
+        tokens.push((start, Token::Elem(&s[start..])))
+    }
+    tokens
+}
+
+/// Checks that brackets are balanced and nested at most two deep.
+fn check_brackets(tokens: &[(usize, Token)], end: usize)
+                 -> Result<(), ParseError> {
+    let mut open = Vec::new();
+    for &(pos, token) in tokens {
+        match token {
+            Token::Open(c) if open.len() >= 2 =>
+                return Err(unexpected(pos, c))
+          , Token::Open(c) => open.push(c)
+          , Token::Close(c) => match open.pop() {
+                Some('(') if c == ')' => {}
+              , Some('[') if c == ']' => {}
+              , _ => return Err(unexpected(pos, c))
+            }
+          , _ => {}
+        }
+    }
+    if open.is_empty() { Ok(()) }
+    else {
+        Err(ParseError { position: end, kind: ParseErrorKind::UnexpectedEnd })
+    }
+}
+
+#[inline]
+fn unexpected(position: usize, c: char) -> ParseError {
+    ParseError { position, kind: ParseErrorKind::UnexpectedChar(c) }
+}
+
+/// Removes a bracket enclosing the entire input, if there is one.
+fn strip_enclosing<'t, 'a>(tokens: &'t [(usize, Token<'a>)])
+                          -> &'t [(usize, Token<'a>)] {
+    let is_sep = |t: &(usize, Token)| t.1 == Token::RowSep;
+    let first = tokens.iter().position(|t| !is_sep(t));
+    let last = tokens.iter().rposition(|t| !is_sep(t));
+    let (first, last) = match (first, last) {
+        (Some(first), Some(last)) => (first, last)
+      , _ => return tokens
+    };
+    if let Token::Open(_) = tokens[first].1 {
+        let mut depth = 0;
+        for (i, &(_, token)) in tokens.iter().enumerate().skip(first) {
+            match token {
+                Token::Open(_) => depth += 1
+              , Token::Close(_) => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return if i == last { &tokens[first + 1..last] }
+                               else { tokens }
+                    }
+                }
+              , _ => {}
+            }
+        }
+    }
+    tokens
+}
+
+/// Splits tokens which contain no brackets into rows.
+///
+/// If `split` is false, row separators are treated as whitespace, and the
+/// tokens are parsed as a single row.
+fn bare_rows<'a>( tokens: &[(usize, Token<'a>)], end: usize, split: bool)
+               -> Result<Vec<Row<'a>>, ParseError> {
+    let mut rows = Vec::new();
+    let mut row = Row { start: tokens.first().map_or(end, |t| t.0)
+                      , end
+                      , elems: Vec::new()
+                      };
+    let mut after_elem = false;
+    for &(pos, token) in tokens {
+        match token {
+            Token::Elem(elem) => {
+                if row.elems.is_empty() { row.start = pos }
+                row.elems.push((pos, elem));
+                after_elem = true;
+            }
+          , Token::Comma if after_elem => after_elem = false
+          , Token::Comma => return Err(unexpected(pos, ','))
+          , Token::RowSep if split => {
+                if !row.elems.is_empty() {
+                    row.end = pos;
+                    rows.push(row);
+                }
+                row = Row { start: pos, end, elems: Vec::new() };
+                after_elem = false;
+            }
+          , Token::RowSep => {}
+          , Token::Open(c) | Token::Close(c) => return Err(unexpected(pos, c))
+        }
+    }
+    if !row.elems.is_empty() || rows.is_empty() { rows.push(row) }
+    Ok(rows)
+}
+
+/// Parses tokens in which each row is enclosed in brackets.
+fn bracketed_rows<'a>(tokens: &[(usize, Token<'a>)])
+                     -> Result<Vec<Row<'a>>, ParseError> {
+    let mut rows = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        match tokens[i] {
+            (_, Token::Open(_)) => {
+                let close = i + tokens[i..].iter()
+                    .position(|t| matches!(t.1, Token::Close(_)))
+                    .expect("brackets were checked to be balanced");
+                let inner = &tokens[i + 1..close];
+                let mut row = bare_rows(inner, tokens[close].0, false)?
+                    .pop()
+                    .expect("bare_rows always returns at least one row");
+                row.start = tokens[i].0;
+                rows.push(row);
+                i = close + 1;
+            }
+          , (_, Token::Comma) | (_, Token::RowSep) => i += 1
+          , (pos, Token::Elem(elem)) =>
+                return Err(unexpected(pos, elem.chars().next().unwrap_or(' ')))
+          , (pos, Token::Close(c)) => return Err(unexpected(pos, c))
+        }
+    }
+    Ok(rows)
+}
+
+fn rows(s: &str) -> Result<Vec<Row<'_>>, ParseError> {
+    let tokens = tokenize(s);
+    check_brackets(&tokens, s.len())?;
+    let inner = strip_enclosing(&tokens);
+    let end = if inner.len() < tokens.len() {
+        // the position of the enclosing close bracket
+        tokens[tokens.iter().rposition(|t| t.1 != Token::RowSep)
+                            .unwrap_or(0)].0
+    } else {
+        s.len()
+    };
+    let bracketed = inner.iter().any(|t| matches!(t.1, Token::Open(_)));
+    if bracketed { bracketed_rows(inner) } else { bare_rows(inner, end, true) }
+}
+
+fn check_length(row: &Row, expected: usize) -> Result<(), ParseError> {
+    let found = row.elems.len();
+    if found == expected { return Ok(()) }
+    let position = if found > expected { row.elems[expected].0 }
+                   else { row.end };
+    Err(ParseError { position
+                   , kind: ParseErrorKind::WrongLength { expected, found }
+                   })
+}
+
+fn parse_elems<N>(row: Row) -> Result<Vec<N>, ParseError>
+where N: FromStr {
+    row.elems.into_iter()
+        .map(|(pos, elem)| elem.parse().map_err(|_| ParseError {
+            position: pos
+          , kind: ParseErrorKind::InvalidElement(elem.to_owned())
+        }))
+        .collect()
+}
+
+/// Parses the elements of a vector of dimension `dim`.
+///
+/// A vector may be written either as a single row or as a single column,
+/// with one element per row.
+#[doc(hidden)]
+pub fn parse_vector<N>(s: &str, dim: usize) -> Result<Vec<N>, ParseError>
+where N: FromStr {
+    let mut rows = rows(s)?;
+    let row = if rows.len() > 1 && rows.iter().all(|r| r.elems.len() == 1) {
+        Row { start: rows[0].start
+            , end: rows[rows.len() - 1].end
+            , elems: rows.into_iter().flat_map(|r| r.elems).collect()
+            }
+    } else if rows.len() == 1 {
+        rows.pop().unwrap()
+    } else {
+        return Err(ParseError {
+            position: rows[1].start
+          , kind: ParseErrorKind::WrongRowCount { expected: 1
+                                                , found: rows.len() }
+        })
+    };
+    check_length(&row, dim)?;
+    parse_elems(row)
+}
+
+/// Parses the elements of a matrix with `nrows` rows and `ncols` columns, in
+/// row-major order.
+#[doc(hidden)]
+pub fn parse_matrix<N>(s: &str, nrows: usize, ncols: usize)
+                      -> Result<Vec<N>, ParseError>
+where N: FromStr {
+    let rows = rows(s)?;
+    if rows.len() != nrows {
+        let position = if rows.len() > nrows { rows[nrows].start }
+                       else { rows.last().map_or(s.len(), |r| r.end) };
+        return Err(ParseError {
+            position
+          , kind: ParseErrorKind::WrongRowCount { expected: nrows
+                                                , found: rows.len() }
+        })
+    }
+    for row in &rows { check_length(row, ncols)? }
+    let mut elems = Vec::with_capacity(nrows * ncols);
+    for row in rows { elems.extend(parse_elems(row)?) }
+    Ok(elems)
+}
@@ -0,0 +1,98 @@
+use super::*;
+use vector::{Vector2, Vector3};
+use matrix::{Matrix2, Matrix3};
+
+#[test]
+fn test_vector_display() {
+    let v = Vector3 { x: 1.5f32, y: -2.0, z: 30.25 };
+    assert_eq!(format!("{}", v), "(1.5, -2, 30.25)");
+    assert_eq!(format!("{:.1}", v), "(1.5, -2.0, 30.2)");
+    assert_eq!(format!("{:>5}", Vector2 { x: 1, y: 2 }), "(    1,     2)");
+}
+
+#[test]
+fn test_matrix_display_aligns_columns() {
+    let m = Matrix2 { x1y1: 1, x2y1: -200
+                    , x1y2: 30, x2y2: 4 };
+    assert_eq!(format!("{}", m), "[ 1 -200]\n[30    4]");
+    assert_eq!(format!("{:<3}", m), "[1   -200]\n[30  4   ]");
+    let m = Matrix2 { x1y1: 1.0f64, x2y1: 0.5
+                    , x1y2: -1.0, x2y2: 10.0 };
+    assert_eq!(format!("{:.2}", m), "[ 1.00  0.50]\n[-1.00 10.00]");
+}
+
+#[test]
+fn test_parse_vector_forms() {
+    let v = Vector3 { x: 1, y: 2, z: 3 };
+    for s in &["(1, 2, 3)", "[1 2 3]", "1,2,3", " [ 1, 2 3, ] ", "[1\n2\n3]"] {
+        assert_eq!(s.parse::<Vector3<i32>>(), Ok(v), "parsing {:?}", s);
+    }
+}
+
+#[test]
+fn test_vector_display_round_trip() {
+    let v = Vector3 { x: 0.1f64, y: -1.0e-7, z: 12345.678 };
+    assert_eq!(v.to_string().parse::<Vector3<f64>>(), Ok(v));
+}
+
+#[test]
+fn test_parse_matrix_forms() {
+    let m = Matrix2 { x1y1: 1, x2y1: 2
+                    , x1y2: 3, x2y2: 4 };
+    for s in &[ "[1 2\n 3 4]", "[1, 2; 3, 4]", "[[1, 2], [3, 4]]"
+              , "[1 2]\n[3 4]\n", "1 2\n\n3 4", "((1 2) (3 4))" ] {
+        assert_eq!(s.parse::<Matrix2<u8>>(), Ok(m), "parsing {:?}", s);
+    }
+}
+
+#[test]
+fn test_matrix_display_round_trip() {
+    let m = Matrix3 { x1y1: 1, x2y1: -20, x3y1: 300
+                    , x1y2: 4, x2y2: 5, x3y2: -6
+                    , x1y3: 70, x2y3: 8, x3y3: 9 };
+    assert_eq!(m.to_string().parse::<Matrix3<i64>>(), Ok(m));
+}
+
+#[test]
+fn test_parse_wrong_length() {
+    let err = "(1, 2)".parse::<Vector3<i32>>().unwrap_err();
+    assert_eq!(err, ParseError {
+        position: 5
+      , kind: ParseErrorKind::WrongLength { expected: 3, found: 2 }
+    });
+    let err = "[1 2 3 4]".parse::<Vector3<i32>>().unwrap_err();
+    assert_eq!(err.position, 7);
+    let err = "[1 2]\n[3 4 5]".parse::<Matrix2<i32>>().unwrap_err();
+    assert_eq!(err, ParseError {
+        position: 11
+      , kind: ParseErrorKind::WrongLength { expected: 2, found: 3 }
+    });
+    assert_eq!(err.to_string(), "expected 2 elements, found 3 at position 11");
+}
+
+#[test]
+fn test_parse_wrong_row_count() {
+    let err = "1 2\n3 4\n5 6".parse::<Matrix2<i32>>().unwrap_err();
+    assert_eq!(err, ParseError {
+        position: 8
+      , kind: ParseErrorKind::WrongRowCount { expected: 2, found: 3 }
+    });
+}
+
+#[test]
+fn test_parse_syntax_errors() {
+    let err = "(1, 2]".parse::<Vector2<i32>>().unwrap_err();
+    assert_eq!(err, ParseError { position: 5
+                               , kind: ParseErrorKind::UnexpectedChar(']') });
+    let err = "(1, 2".parse::<Vector2<i32>>().unwrap_err();
+    assert_eq!(err, ParseError { position: 5
+                               , kind: ParseErrorKind::UnexpectedEnd });
+    let err = "(1,, 2)".parse::<Vector2<i32>>().unwrap_err();
+    assert_eq!(err, ParseError { position: 3
+                               , kind: ParseErrorKind::UnexpectedChar(',') });
+    let err = "(1, x)".parse::<Vector2<i32>>().unwrap_err();
+    assert_eq!(err, ParseError {
+        position: 4
+      , kind: ParseErrorKind::InvalidElement("x".to_owned())
+    });
+}
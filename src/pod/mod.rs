@@ -0,0 +1,154 @@
+//! Zero-copy conversion between plain-old-data types and bytes.
+//!
+//! A type is plain-old-data (`Pod`) if every bit pattern of the right size
+//! is a valid value, and its values contain no padding bytes. Slices of such
+//! types may be freely reinterpreted as slices of bytes and back, which is
+//! useful for binary file formats and network buffers:
+//!
+//! ```ignore
+//! let points: &[Vector3<f32>] = ...;
+//! socket.write_all(pod::cast_slice::<_, u8>(points).unwrap())?;
+//! ```
+//!
+//! All primitive numeric types are `Pod`, as are every vector and matrix
+//! type whose elements are `Pod`. When the `bytemuck` feature is enabled,
+//! the vector and matrix types also implement `bytemuck::Pod` and
+//! `bytemuck::Zeroable`.
+
+use error::LengthError;
+
+use std::error;
+use std::fmt;
+use std::mem;
+use std::ptr;
+use std::slice;
+
+#[cfg(test)] mod test;
+
+#[cfg(feature = "bytemuck")]
+#[doc(hidden)]
+pub use bytemuck::{Pod as BytemuckPod, Zeroable};
+
+/// A plain-old-data type.
+///
+/// # Safety
+///   Implementors must be `repr(C)` or `repr(transparent)` (or primitive),
+///   must not contain any padding bytes, and must be valid for every bit
+///   pattern. The vector and matrix types satisfy this when their elements
+///   do, since they are `repr(C)` structs whose fields all have the same
+///   type.
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! impl_pod_primitive {
+    ($($t: ty),+) => { $( unsafe impl Pod for $t {} )+ };
+}
+
+impl_pod_primitive! { u8, u16, u32, u64, usize
+                    , i8, i16, i32, i64, isize
+                    , f32, f64 }
+
+unsafe impl<T, const K: usize> Pod for [T; K]
+where T: Pod {}
+
+/// An error indicating that a slice could not be reinterpreted as another
+/// type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PodCastError {
+    /// The slice was not aligned to the alignment of the target type
+    Misaligned { align: usize },
+    /// The slice's length in bytes was not a multiple of the target type's
+    /// size
+    Length(LengthError),
+}
+
+impl fmt::Display for PodCastError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PodCastError::Misaligned { align } =>
+                write!(f, "slice is not aligned to {} bytes", align)
+          , PodCastError::Length(ref err) => err.fmt(f)
+        }
+    }
+}
+
+impl error::Error for PodCastError {}
+
+impl From<LengthError> for PodCastError {
+    #[inline] fn from(err: LengthError) -> Self { PodCastError::Length(err) }
+}
+
+/// Borrow the bytes of a value.
+#[inline]
+pub fn bytes_of<T>(value: &T) -> &[u8]
+where T: Pod {
+    unsafe {
+        slice::from_raw_parts( value as *const T as *const u8
+                             , mem::size_of::<T>())
+    }
+}
+
+/// Mutably borrow the bytes of a value.
+#[inline]
+pub fn bytes_of_mut<T>(value: &mut T) -> &mut [u8]
+where T: Pod {
+    unsafe {
+        slice::from_raw_parts_mut( value as *mut T as *mut u8
+                                 , mem::size_of::<T>())
+    }
+}
+
+/// Checks that `len` bytes at `ptr` may be viewed as a slice of `B`,
+/// returning the length of that slice.
+fn check_cast<B>(ptr: *const u8, len: usize) -> Result<usize, PodCastError> {
+    let size = mem::size_of::<B>();
+    if !(ptr as usize).is_multiple_of(mem::align_of::<B>()) {
+        return Err(PodCastError::Misaligned { align: mem::align_of::<B>() })
+    }
+    if size == 0 || !len.is_multiple_of(size) {
+        return Err(LengthError::Multiple { of: size, found: len }.into())
+    }
+    Ok(len / size)
+}
+
+/// Reinterpret a slice of one `Pod` type as a slice of another.
+///
+/// # Returns:
+///   - `Ok(&[B])` if `a` is suitably aligned for `B`, and its length in
+///     bytes is a multiple of the size of `B`
+///   - `Err(PodCastError)` otherwise
+///
+pub fn cast_slice<A, B>(a: &[A]) -> Result<&[B], PodCastError>
+where A: Pod
+    , B: Pod {
+    let len = check_cast::<B>(a.as_ptr() as *const u8, mem::size_of_val(a))?;
+    Ok(unsafe { slice::from_raw_parts(a.as_ptr() as *const B, len) })
+}
+
+/// Reinterpret a mutable slice of one `Pod` type as a slice of another.
+///
+/// # Returns:
+///   - `Ok(&mut [B])` if `a` is suitably aligned for `B`, and its length in
+///     bytes is a multiple of the size of `B`
+///   - `Err(PodCastError)` otherwise
+///
+pub fn cast_slice_mut<A, B>(a: &mut [A]) -> Result<&mut [B], PodCastError>
+where A: Pod
+    , B: Pod {
+    let len = check_cast::<B>(a.as_ptr() as *const u8, mem::size_of_val(a))?;
+    Ok(unsafe { slice::from_raw_parts_mut(a.as_mut_ptr() as *mut B, len) })
+}
+
+/// Copy a value out of a byte slice which need not be aligned.
+///
+/// # Returns:
+///   - `Ok(T)` if `bytes` is exactly as long as `T`
+///   - `Err(LengthError)` otherwise
+///
+pub fn read_unaligned<T>(bytes: &[u8]) -> Result<T, LengthError>
+where T: Pod {
+    if bytes.len() != mem::size_of::<T>() {
+        return Err(LengthError::Exact { expected: mem::size_of::<T>()
+                                      , found: bytes.len() })
+    }
+    Ok(unsafe { ptr::read_unaligned(bytes.as_ptr() as *const T) })
+}
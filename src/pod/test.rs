@@ -0,0 +1,63 @@
+use super::*;
+use vector::{Vector2, Vector3};
+use matrix::Matrix2;
+use gpu::PaddedVector3;
+
+#[test]
+fn test_vector_slice_to_bytes_and_back() {
+    let points = [ Vector3 { x: 1.0f32, y: 2.0, z: 3.0 }
+                 , Vector3 { x: 4.0, y: 5.0, z: 6.0 } ];
+    let bytes: &[u8] = cast_slice(&points).unwrap();
+    assert_eq!(bytes.len(), 24);
+    assert_eq!(&bytes[4..8], &2.0f32.to_ne_bytes());
+    let back: &[Vector3<f32>] = cast_slice(bytes).unwrap();
+    assert_eq!(back, &points);
+    let floats: &[f32] = cast_slice(&points).unwrap();
+    assert_eq!(floats, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+}
+
+#[test]
+fn test_cast_slice_errors() {
+    let elems = [0u32; 4];
+    let bytes: &[u8] = cast_slice(&elems).unwrap();
+    assert_eq!( cast_slice::<_, Vector3<u32>>(&bytes[..8])
+              , Err(PodCastError::Length(LengthError::Multiple { of: 12
+                                                               , found: 8 })));
+    assert_eq!( cast_slice::<_, u32>(&bytes[1..5])
+              , Err(PodCastError::Misaligned { align: 4 }));
+}
+
+#[test]
+fn test_cast_slice_mut() {
+    let mut m = [Matrix2 { x1y1: 1i16, x2y1: 2, x1y2: 3, x2y2: 4 }];
+    {
+        let elems: &mut [i16] = cast_slice_mut(&mut m).unwrap();
+        elems[3] = 40;
+    }
+    assert_eq!(m[0].x2y2, 40);
+}
+
+#[test]
+fn test_bytes_of_and_read_unaligned() {
+    let v = Vector2 { x: 0x0102u16, y: 0x0304 };
+    let mut buf = vec![0xffu8];
+    buf.extend_from_slice(bytes_of(&v));
+    assert_eq!(read_unaligned::<Vector2<u16>>(&buf[1..]), Ok(v));
+    assert_eq!( read_unaligned::<Vector2<u16>>(&buf)
+              , Err(LengthError::Exact { expected: 4, found: 5 }));
+
+    let mut padded = PaddedVector3::from(Vector3 { x: 1.0f32, y: 2.0, z: 3.0 });
+    assert_eq!(bytes_of(&padded).len(), 16);
+    bytes_of_mut(&mut padded)[8..12].copy_from_slice(&9.0f32.to_ne_bytes());
+    assert_eq!(padded.z, 9.0);
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn test_bytemuck_cast_slice() {
+    let points = [Vector2 { x: 1u32, y: 2 }, Vector2 { x: 3, y: 4 }];
+    let elems: &[u32] = ::bytemuck::cast_slice(&points);
+    assert_eq!(elems, &[1, 2, 3, 4]);
+    let zero: Matrix2<f64> = ::bytemuck::Zeroable::zeroed();
+    assert_eq!(zero, Matrix2::default());
+}
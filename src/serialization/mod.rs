@@ -0,0 +1,139 @@
+//! Serialization support, enabled by the `serde` feature.
+//!
+//! Vectors and matrices serialize compactly by default, as a tuple of their
+//! elements in subscript order (so `Vector3 { x: 1, y: 2, z: 3 }` becomes
+//! `[1, 2, 3]` in JSON). The `Named` wrapper, or the `named` module used with
+//! `#[serde(with = "lin::serialization::named")]`, selects a struct form
+//! keyed by subscript names instead (`{"x": 1, "y": 2, "z": 3}`).
+
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+use serde::de::{self, DeserializeSeed};
+
+#[doc(hidden)]
+pub use serde::{Serialize, Serializer, Deserialize, Deserializer};
+#[doc(hidden)]
+pub use serde::ser::{SerializeTuple, SerializeStruct};
+#[doc(hidden)]
+pub use serde::de::{Error, SeqAccess, MapAccess, Visitor};
+
+#[cfg(test)] mod test;
+
+/// Something which can be serialized as a struct keyed by subscript names.
+pub trait SerializeNamed {
+    fn serialize_named<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where S: Serializer;
+}
+
+/// Something which can be deserialized from a struct keyed by subscript
+/// names.
+pub trait DeserializeNamed<'de>: Sized {
+    fn deserialize_named<D>(deserializer: D) -> Result<Self, D::Error>
+    where D: Deserializer<'de>;
+}
+
+/// A wrapper which serializes and deserializes a vector or matrix in the
+/// struct form, keyed by subscript names.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Named<T>(pub T);
+
+impl<T> Deref for Named<T> {
+    type Target = T;
+    #[inline] fn deref(&self) -> &T { &self.0 }
+}
+
+impl<T> DerefMut for Named<T> {
+    #[inline] fn deref_mut(&mut self) -> &mut T { &mut self.0 }
+}
+
+impl<T> Serialize for Named<T>
+where T: SerializeNamed {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where S: Serializer {
+        self.0.serialize_named(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Named<T>
+where T: DeserializeNamed<'de> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where D: Deserializer<'de> {
+        T::deserialize_named(deserializer).map(Named)
+    }
+}
+
+/// Serializes and deserializes in the struct form, keyed by subscript names.
+///
+/// # Example
+///   ```ignore
+///   #[derive(Serialize, Deserialize)]
+///   struct Transform {
+///       #[serde(with = "lin::serialization::named")]
+///       translation: Vector3<f32>,
+///   }
+///   ```
+pub mod named {
+    use super::{SerializeNamed, DeserializeNamed, Serializer, Deserializer};
+
+    #[inline]
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where T: SerializeNamed
+        , S: Serializer {
+        value.serialize_named(serializer)
+    }
+
+    #[inline]
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where T: DeserializeNamed<'de>
+        , D: Deserializer<'de> {
+        T::deserialize_named(deserializer)
+    }
+}
+
+/// Deserializes a subscript name as its index within `fields`.
+#[doc(hidden)]
+pub struct FieldIndex(pub &'static [&'static str]);
+
+impl<'de> DeserializeSeed<'de> for FieldIndex {
+    type Value = usize;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<usize, D::Error>
+    where D: Deserializer<'de> {
+        deserializer.deserialize_identifier(self)
+    }
+}
+
+impl<'de> Visitor<'de> for FieldIndex {
+    type Value = usize;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "one of the subscripts {:?}", self.0)
+    }
+
+    fn visit_u64<E>(self, i: u64) -> Result<usize, E>
+    where E: de::Error {
+        if (i as usize) < self.0.len() {
+            Ok(i as usize)
+        } else {
+            Err(E::invalid_value(de::Unexpected::Unsigned(i), &self))
+        }
+    }
+
+    fn visit_str<E>(self, name: &str) -> Result<usize, E>
+    where E: de::Error {
+        self.0.iter().position(|field| *field == name)
+            .ok_or_else(|| E::unknown_field(name, self.0))
+    }
+
+    fn visit_bytes<E>(self, name: &[u8]) -> Result<usize, E>
+    where E: de::Error {
+        self.0.iter().position(|field| field.as_bytes() == name)
+            .ok_or_else(|| match ::std::str::from_utf8(name) {
+                Ok(name) => E::unknown_field(name, self.0)
+              , Err(_) => E::invalid_value(de::Unexpected::Bytes(name), &self)
+            })
+    }
+}
@@ -0,0 +1,90 @@
+use super::*;
+use vector::{Vector2, Vector3};
+use matrix::Matrix2;
+
+use serde_test::{Token, assert_tokens, assert_de_tokens, assert_de_tokens_error};
+
+#[test]
+fn test_v3_tuple_round_trip() {
+    let v = Vector3 { x: 1, y: 2, z: 3 };
+    assert_tokens(&v, &[ Token::Tuple { len: 3 }
+                       , Token::I32(1), Token::I32(2), Token::I32(3)
+                       , Token::TupleEnd
+                       ]);
+}
+
+#[test]
+fn test_m2_tuple_round_trip() {
+    let m = Matrix2 { x1y1: 1.0f32, x2y1: 2.0
+                    , x1y2: 3.0, x2y2: 4.0
+                    };
+    assert_tokens(&m, &[ Token::Tuple { len: 4 }
+                       , Token::F32(1.0), Token::F32(2.0)
+                       , Token::F32(3.0), Token::F32(4.0)
+                       , Token::TupleEnd
+                       ]);
+}
+
+#[test]
+fn test_v2_tuple_too_short() {
+    assert_de_tokens_error::<Vector2<i32>>(
+        &[ Token::Tuple { len: 1 }, Token::I32(1), Token::TupleEnd ]
+      , "invalid length 1, expected a tuple of 2 elements");
+}
+
+#[test]
+fn test_v3_named_round_trip() {
+    let v = Named(Vector3 { x: 1, y: 2, z: 3 });
+    assert_tokens(&v, &[ Token::Struct { name: "Vector3", len: 3 }
+                       , Token::Str("x"), Token::I32(1)
+                       , Token::Str("y"), Token::I32(2)
+                       , Token::Str("z"), Token::I32(3)
+                       , Token::StructEnd
+                       ]);
+}
+
+#[test]
+fn test_v3_named_out_of_order() {
+    let v = Named(Vector3 { x: 1, y: 2, z: 3 });
+    assert_de_tokens(&v, &[ Token::Struct { name: "Vector3", len: 3 }
+                          , Token::Str("z"), Token::I32(3)
+                          , Token::Str("x"), Token::I32(1)
+                          , Token::Str("y"), Token::I32(2)
+                          , Token::StructEnd
+                          ]);
+}
+
+#[test]
+fn test_v2_named_errors() {
+    assert_de_tokens_error::<Named<Vector2<i32>>>(
+        &[ Token::Struct { name: "Vector2", len: 1 }
+         , Token::Str("x"), Token::I32(1)
+         , Token::StructEnd
+         ]
+      , "missing field `y`");
+    assert_de_tokens_error::<Named<Vector2<i32>>>(
+        &[ Token::Struct { name: "Vector2", len: 2 }
+         , Token::Str("x"), Token::I32(1)
+         , Token::Str("x"), Token::I32(1)
+         ]
+      , "duplicate field `x`");
+    assert_de_tokens_error::<Named<Vector2<i32>>>(
+        &[ Token::Struct { name: "Vector2", len: 1 }
+         , Token::Str("w")
+         ]
+      , "unknown field `w`, expected `x` or `y`");
+}
+
+#[test]
+fn test_m2_named_round_trip() {
+    let m = Named(Matrix2 { x1y1: 1u8, x2y1: 2
+                          , x1y2: 3, x2y2: 4
+                          });
+    assert_tokens(&m, &[ Token::Struct { name: "Matrix2", len: 4 }
+                       , Token::Str("x1y1"), Token::U8(1)
+                       , Token::Str("x2y1"), Token::U8(2)
+                       , Token::Str("x1y2"), Token::U8(3)
+                       , Token::Str("x2y2"), Token::U8(4)
+                       , Token::StructEnd
+                       ]);
+}
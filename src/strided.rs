@@ -0,0 +1,368 @@
+//! Strided views over slices.
+//!
+//! A strided view selects every `stride`th element of a slice, starting at
+//! some offset. These are used to borrow data which is not stored
+//! contiguously, such as the columns of a row-major matrix.
+
+use std::ops;
+use std::marker::PhantomData;
+
+/// An immutable strided view into a slice.
+///
+/// The view holds a raw pointer rather than a slice, so that it never
+/// borrows the elements lying between the ones it selects; those may belong
+/// to a sibling `StridedMut`, such as another column of the same matrix.
+#[derive(Debug)]
+pub struct Strided<'a, N: 'a> { ptr: *const N
+                              , stride: usize
+                              , len: usize
+                              , _lifetime: PhantomData<&'a N>
+                              }
+
+unsafe impl<'a, N> Send for Strided<'a, N> where N: Sync {}
+unsafe impl<'a, N> Sync for Strided<'a, N> where N: Sync {}
+
+impl<'a, N> Clone for Strided<'a, N> {
+    #[inline] fn clone(&self) -> Self { *self }
+}
+
+impl<'a, N> Copy for Strided<'a, N> {}
+
+/// A mutable strided view into a slice.
+#[derive(Debug)]
+pub struct StridedMut<'a, N: 'a> { ptr: *mut N
+                                 , stride: usize
+                                 , len: usize
+                                 , _lifetime: PhantomData<&'a mut N>
+                                 }
+
+/// Returns the number of elements of `data` needed to hold a strided view,
+/// or `None` if that number does not fit in a `usize`.
+#[inline]
+fn span(stride: usize, len: usize) -> Option<usize> {
+    if len == 0 { Some(0) }
+    else { (len - 1).checked_mul(stride)?.checked_add(1) }
+}
+
+/// Returns true if a strided view lies within a slice of length `data`.
+#[inline]
+fn fits(data: usize, start: usize, stride: usize, len: usize) -> bool {
+    stride != 0 && start <= data
+                && span(stride, len).is_some_and(|span| span <= data - start)
+}
+
+impl<'a, N> Strided<'a, N> {
+
+    /// Construct a new view of `len` elements of `data`, beginning at
+    /// `start` and advancing by `stride` elements each time.
+    ///
+    /// # Returns:
+    ///   - `Some(Strided)` if every element of the view lies within `data`
+    ///   - `None` if the view would extend past the end of `data`, or if
+    ///     `stride` is zero, or if its extent overflows a `usize`
+    ///
+    pub fn new(data: &'a [N], start: usize, stride: usize, len: usize)
+              -> Option<Self> {
+        if fits(data.len(), start, stride, len) {
+            Some(unsafe {
+                Strided::from_raw_parts(data[start..].as_ptr(), stride, len)
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Construct a new view from a raw pointer.
+    ///
+    /// # Safety
+    ///   `ptr` must be valid for reads of every element of the view for the
+    ///   lifetime `'a`, and those elements must not be mutated for that
+    ///   lifetime. Elements between the ones selected by the view are never
+    ///   accessed, and may be borrowed mutably elsewhere. The extent of the
+    ///   view, `(len - 1) * stride + 1` elements, must not overflow.
+    #[inline]
+    pub unsafe fn from_raw_parts(ptr: *const N, stride: usize, len: usize)
+                                -> Self {
+        Strided { ptr, stride, len, _lifetime: PhantomData }
+    }
+
+    /// Returns the number of elements in the view
+    #[inline] pub fn len(&self) -> usize { self.len }
+
+    /// Returns true if the view contains no elements
+    #[inline] pub fn is_empty(&self) -> bool { self.len == 0 }
+
+    /// Returns the distance between consecutive elements of the view
+    #[inline] pub fn stride(&self) -> usize { self.stride }
+
+    /// Select an element of the view by index
+    ///
+    /// # Returns:
+    ///   - `Some(&N)` if an element exists for the given index
+    ///   - `None` if the index is out of bounds
+    ///
+    #[inline]
+    pub fn get(&self, i: usize) -> Option<&'a N> {
+        if i < self.len {
+            Some(unsafe { &*self.ptr.add(i * self.stride) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over the elements of the view
+    #[inline]
+    pub fn iter(&self) -> Iter<'a, N> {
+        Iter { ptr: self.ptr
+             , stride: self.stride
+             , i: 0
+             , len: self.len
+             , _lifetime: PhantomData
+             }
+    }
+}
+
+impl<'a, N> ops::Index<usize> for Strided<'a, N> {
+    type Output = N;
+
+    #[inline] fn index(&self, i: usize) -> &N {
+        self.get(i).expect("strided view index out of bounds")
+    }
+}
+
+impl<'a, N> From<&'a [N]> for Strided<'a, N> {
+    #[inline] fn from(data: &'a [N]) -> Self {
+        unsafe { Strided::from_raw_parts(data.as_ptr(), 1, data.len()) }
+    }
+}
+
+impl<'a, N> IntoIterator for Strided<'a, N> {
+    type Item = &'a N;
+    type IntoIter = Iter<'a, N>;
+
+    #[inline] fn into_iter(self) -> Self::IntoIter { self.iter() }
+}
+
+/// An iterator over the elements of a `Strided` view.
+#[derive(Debug)]
+pub struct Iter<'a, N: 'a> { ptr: *const N
+                           , stride: usize
+                           , i: usize
+                           , len: usize
+                           , _lifetime: PhantomData<&'a N>
+                           }
+
+unsafe impl<'a, N> Send for Iter<'a, N> where N: Sync {}
+unsafe impl<'a, N> Sync for Iter<'a, N> where N: Sync {}
+
+impl<'a, N> Clone for Iter<'a, N> {
+    #[inline] fn clone(&self) -> Self {
+        Iter { ptr: self.ptr
+             , stride: self.stride
+             , i: self.i
+             , len: self.len
+             , _lifetime: PhantomData
+             }
+    }
+}
+
+impl<'a, N> Iterator for Iter<'a, N> {
+    type Item = &'a N;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.i {
+            i if i < self.len => {
+                self.i += 1;
+                Some(unsafe { &*self.ptr.add(i * self.stride) })
+            }
+          , _ => None
+        }
+    }
+
+    #[inline] fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len - self.i;
+        (len, Some(len))
+    }
+}
+
+impl<'a, N> DoubleEndedIterator for Iter<'a, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.i < self.len {
+            self.len -= 1;
+            Some(unsafe { &*self.ptr.add(self.len * self.stride) })
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, N> ExactSizeIterator for Iter<'a, N> {}
+
+impl<'a, N> StridedMut<'a, N> {
+
+    /// Construct a new mutable view of `len` elements of `data`, beginning at
+    /// `start` and advancing by `stride` elements each time.
+    ///
+    /// # Returns:
+    ///   - `Some(StridedMut)` if every element of the view lies within
+    ///     `data`
+    ///   - `None` if the view would extend past the end of `data`, or if
+    ///     `stride` is zero, or if its extent overflows a `usize`
+    ///
+    pub fn new(data: &'a mut [N], start: usize, stride: usize, len: usize)
+              -> Option<Self> {
+        if fits(data.len(), start, stride, len) {
+            Some(unsafe {
+                StridedMut::from_raw_parts( data[start..].as_mut_ptr()
+                                          , stride, len)
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Construct a new mutable view from a raw pointer.
+    ///
+    /// This makes it possible to hold several views into the same slice at
+    /// once, as long as they never select the same element; for example, one
+    /// view for each column of a matrix.
+    ///
+    /// # Safety
+    ///   `ptr` must be valid for reads and writes of every element of the
+    ///   view for the lifetime `'a`, and no other reference may be used to
+    ///   access those elements for that lifetime. In particular, the extent
+    ///   of the view, `(len - 1) * stride + 1` elements, must not overflow.
+    #[inline]
+    pub unsafe fn from_raw_parts(ptr: *mut N, stride: usize, len: usize)
+                                -> Self {
+        StridedMut { ptr, stride, len, _lifetime: PhantomData }
+    }
+
+    /// Returns the number of elements in the view
+    #[inline] pub fn len(&self) -> usize { self.len }
+
+    /// Returns true if the view contains no elements
+    #[inline] pub fn is_empty(&self) -> bool { self.len == 0 }
+
+    /// Returns the distance between consecutive elements of the view
+    #[inline] pub fn stride(&self) -> usize { self.stride }
+
+    /// Select an element of the view by index
+    ///
+    /// # Returns:
+    ///   - `Some(&N)` if an element exists for the given index
+    ///   - `None` if the index is out of bounds
+    ///
+    #[inline]
+    pub fn get(&self, i: usize) -> Option<&N> {
+        if i < self.len {
+            Some(unsafe { &*self.ptr.add(i * self.stride) })
+        } else {
+            None
+        }
+    }
+
+    /// Select an element of the view mutably by index
+    ///
+    /// # Returns:
+    ///   - `Some(&mut N)` if an element exists for the given index
+    ///   - `None` if the index is out of bounds
+    ///
+    #[inline]
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut N> {
+        if i < self.len {
+            Some(unsafe { &mut *self.ptr.add(i * self.stride) })
+        } else {
+            None
+        }
+    }
+
+    /// Reborrow this view immutably
+    #[inline]
+    pub fn as_strided(&self) -> Strided<'_, N> {
+        unsafe { Strided::from_raw_parts(self.ptr, self.stride, self.len) }
+    }
+
+    /// Returns an iterator over the elements of the view
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, N> { self.as_strided().iter() }
+
+    /// Returns an iterator over mutable references to the elements of the
+    /// view
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, N> {
+        IterMut { ptr: self.ptr
+                , stride: self.stride
+                , i: 0
+                , len: self.len
+                , _lifetime: PhantomData
+                }
+    }
+}
+
+impl<'a, N> ops::Index<usize> for StridedMut<'a, N> {
+    type Output = N;
+
+    #[inline] fn index(&self, i: usize) -> &N {
+        self.get(i).expect("strided view index out of bounds")
+    }
+}
+
+impl<'a, N> ops::IndexMut<usize> for StridedMut<'a, N> {
+
+    #[inline] fn index_mut(&mut self, i: usize) -> &mut N {
+        self.get_mut(i).expect("strided view index out of bounds")
+    }
+}
+
+impl<'a, N> From<&'a mut [N]> for StridedMut<'a, N> {
+    #[inline] fn from(data: &'a mut [N]) -> Self {
+        unsafe { StridedMut::from_raw_parts(data.as_mut_ptr(), 1, data.len()) }
+    }
+}
+
+impl<'a, N> IntoIterator for StridedMut<'a, N> {
+    type Item = &'a mut N;
+    type IntoIter = IterMut<'a, N>;
+
+    #[inline] fn into_iter(self) -> Self::IntoIter {
+        IterMut { ptr: self.ptr
+                , stride: self.stride
+                , i: 0
+                , len: self.len
+                , _lifetime: PhantomData
+                }
+    }
+}
+
+/// An iterator over mutable references to the elements of a `StridedMut`
+/// view.
+pub struct IterMut<'a, N: 'a> { ptr: *mut N
+                              , stride: usize
+                              , i: usize
+                              , len: usize
+                              , _lifetime: PhantomData<&'a mut N>
+                              }
+
+impl<'a, N> Iterator for IterMut<'a, N> {
+    type Item = &'a mut N;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.i {
+            i if i < self.len => {
+                self.i += 1;
+                // each index is only ever yielded once, so the references
+                // handed out by this iterator never alias.
+                Some(unsafe { &mut *self.ptr.add(i * self.stride) })
+            }
+          , _ => None
+        }
+    }
+
+    #[inline] fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len - self.i;
+        (len, Some(len))
+    }
+}
+
+impl<'a, N> ExactSizeIterator for IterMut<'a, N> {}
@@ -0,0 +1,193 @@
+//! Multi-threaded versions of operations over large inputs.
+//!
+//! Each function splits its input into contiguous pieces and processes each
+//! piece on a scoped thread, borrowing the input rather than copying it.
+//! Inputs too small for the split to pay for itself, as set by a `Config`'s
+//! threshold, are processed on the calling thread instead:
+//!
+//! ```ignore
+//! let config = Config::new().with_threshold(1 << 20);
+//! threads::transform(&config, &rotation, &mut points);
+//! let sum = threads::map_reduce(&config, &points, 0, |p| p.x, |a, b| a + b);
+//! ```
+//!
+//! Pieces are always combined in the same order, so results depend only on
+//! the input and the `Config`, and never on how the threads were scheduled.
+//! In particular, integer results are exactly those of the single-threaded
+//! computation.
+
+use blas::{self, Scalar};
+use matrix::{MatrixView, MatrixViewMut};
+use error::ShapeError;
+
+use std::cmp::{max, min};
+use std::ops::Mul;
+use std::panic;
+use std::thread;
+
+#[cfg(test)] mod test;
+
+/// Controls how work is divided between threads.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Config { threads: usize
+                  , threshold: usize
+                  }
+
+impl Config {
+
+    /// Construct a new `Config` using every available processor, with a
+    /// threshold of 65536.
+    pub fn new() -> Self {
+        let threads = thread::available_parallelism()
+                             .map(|n| n.get())
+                             .unwrap_or(1);
+        Config { threads, threshold: 1 << 16 }
+    }
+
+    /// Returns this `Config` with the maximum number of threads set to
+    /// `threads`. A value of zero or one disables threading.
+    #[inline]
+    pub fn with_threads(self, threads: usize) -> Self {
+        Config { threads: max(threads, 1), ..self }
+    }
+
+    /// Returns this `Config` with the threshold set to `threshold`.
+    ///
+    /// Each thread is given at least `threshold` units of work: elements
+    /// for element-wise operations, or multiply-adds for `gemm`. Smaller
+    /// inputs stay on the calling thread.
+    #[inline]
+    pub fn with_threshold(self, threshold: usize) -> Self {
+        Config { threshold: max(threshold, 1), ..self }
+    }
+
+    /// Returns the maximum number of threads
+    #[inline] pub fn threads(&self) -> usize { self.threads }
+
+    /// Returns the minimum amount of work given to each thread
+    #[inline] pub fn threshold(&self) -> usize { self.threshold }
+
+    /// Returns the number of pieces to split `work` units of work into.
+    #[inline]
+    fn pieces(&self, work: usize) -> usize {
+        max(1, min(self.threads, work / self.threshold))
+    }
+}
+
+impl Default for Config {
+    #[inline] fn default() -> Self { Config::new() }
+}
+
+/// Returns the length of each of `pieces` chunks covering `len` elements.
+#[inline]
+fn chunk_len(len: usize, pieces: usize) -> usize {
+    max(1, len.div_ceil(pieces))
+}
+
+/// Returns the result of a scoped thread, resuming its panic if it had one.
+#[inline]
+fn join<T>(handle: thread::ScopedJoinHandle<T>) -> T {
+    handle.join().unwrap_or_else(|e| panic::resume_unwind(e))
+}
+
+/// Applies `f` to each element of `data`, in place.
+pub fn map_in_place<T, F>(config: &Config, data: &mut [T], f: F)
+where T: Send
+    , F: Fn(&mut T) + Sync {
+    let pieces = config.pieces(data.len());
+    if pieces == 1 { return data.iter_mut().for_each(f) }
+
+    let len = chunk_len(data.len(), pieces);
+    thread::scope(|s| {
+        for chunk in data.chunks_mut(len) {
+            let f = &f;
+            s.spawn(move || chunk.iter_mut().for_each(f));
+        }
+    })
+}
+
+/// Maps each element of `data` with `map` and combines the results with
+/// `reduce`, starting from `identity`.
+///
+/// Each piece of `data` is reduced in order from a copy of `identity`, and
+/// the results of the pieces are then reduced in order, so `reduce` should
+/// be associative and `identity` should be its identity. For operations
+/// that are only approximately associative, such as float addition, the
+/// result depends on how many pieces `config` splits `data` into.
+pub fn map_reduce<T, A, M, R>( config: &Config, data: &[T], identity: A
+                             , map: M, reduce: R) -> A
+where T: Sync
+    , A: Clone + Send
+    , M: Fn(&T) -> A + Sync
+    , R: Fn(A, A) -> A + Sync {
+    let fold = |chunk: &[T], init: A| {
+        chunk.iter().fold(init, |acc, elem| reduce(acc, map(elem)))
+    };
+    let pieces = config.pieces(data.len());
+    if pieces == 1 { return fold(data, identity) }
+
+    let len = chunk_len(data.len(), pieces);
+    thread::scope(|s| {
+        let handles = data.chunks(len).map(|chunk| {
+            let (fold, init) = (&fold, identity.clone());
+            s.spawn(move || fold(chunk, init))
+        }).collect::<Vec<_>>();
+        handles.into_iter().map(join).fold(identity, &reduce)
+    })
+}
+
+/// Replaces each vector `v` in `vs` with `m * v`.
+///
+/// `m` is typically a matrix, applied to a batch of vectors, as in
+/// `transform(&config, &rotation, &mut points)`.
+#[inline]
+pub fn transform<M, V>(config: &Config, m: &M, vs: &mut [V])
+where M: Mul<V, Output = V> + Copy + Sync
+    , V: Copy + Send {
+    map_in_place(config, vs, |v| *v = *m * *v)
+}
+
+/// Computes `c = alpha * a * b + beta * c`, like `blas::gemm`, computing
+/// blocks of rows of `c` on separate threads.
+///
+/// Every element of `c` is computed exactly as `blas::gemm` would compute
+/// it, so the result does not depend on `config`.
+///
+/// # Returns:
+///   - `Ok(())` if `a` is `m` by `k`, `b` is `k` by `n` and `c` is `m` by
+///     `n`
+///   - `Err(ShapeError)` describing the first operand with the wrong shape
+///     otherwise, in which case `c` is left unchanged
+///
+pub fn gemm<N>( config: &Config
+              , alpha: N, a: MatrixView<N>, b: MatrixView<N>
+              , beta: N, c: MatrixViewMut<N>) -> Result<(), ShapeError>
+where N: Scalar {
+    let (m, k) = a.shape();
+    let n = b.ncols();
+    if b.nrows() != k {
+        return Err(ShapeError { expected: (k, n), found: b.shape() })
+    }
+    if c.shape() != (m, n) {
+        return Err(ShapeError { expected: (m, n), found: c.shape() })
+    }
+
+    let work = m.saturating_mul(n).saturating_mul(k);
+    let pieces = min(m, config.pieces(work));
+    if pieces <= 1 { return blas::gemm(alpha, a, b, beta, c) }
+
+    let rows = chunk_len(m, pieces);
+    thread::scope(|s| {
+        let mut rest = c;
+        for y in (0..m).step_by(rows) {
+            let (c, tail) = rest.split_at_row(min(rows, m - y));
+            rest = tail;
+            let a = a.block(y, 0, c.nrows(), k).expect("rows lie within a");
+            s.spawn(move || {
+                blas::gemm(alpha, a, b, beta, c)
+                     .expect("blocks of operands have matching shapes")
+            });
+        }
+    });
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use super::*;
+use matrix::{Matrix3, MatrixN};
+use vector::Vector3;
+
+/// A config that splits even small inputs between several threads.
+fn eager() -> Config { Config::new().with_threads(4).with_threshold(1) }
+
+#[test]
+fn test_config_pieces() {
+    let config = Config::new().with_threads(4).with_threshold(100);
+    assert_eq!(config.pieces(0), 1);
+    assert_eq!(config.pieces(199), 1);
+    assert_eq!(config.pieces(200), 2);
+    assert_eq!(config.pieces(1_000_000), 4);
+    let config = config.with_threads(0).with_threshold(0);
+    assert_eq!((config.threads(), config.threshold()), (1, 1));
+    assert_eq!(config.pieces(1_000_000), 1);
+}
+
+#[test]
+fn test_map_in_place() {
+    for &len in &[0, 1, 3, 4, 5, 1001] {
+        let mut data = (0..len).collect::<Vec<i64>>();
+        map_in_place(&eager(), &mut data, |x| *x = *x * *x - 1);
+        assert_eq!(data, (0..len).map(|x| x * x - 1).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn test_map_reduce_integers_match_sequential() {
+    let data = (0..10_007).map(|x| x * 7919 % 10_007 - 5000)
+                          .collect::<Vec<i64>>();
+    let sequential = data.iter().map(|x| x * x).sum::<i64>();
+    for threads in 1..9 {
+        let config = Config::new().with_threads(threads).with_threshold(1);
+        assert_eq!( map_reduce(&config, &data, 0, |x| x * x, |a, b| a + b)
+                  , sequential);
+    }
+    assert_eq!( map_reduce(&eager(), &data, i64::MIN, |&x| x, max)
+              , 5006);
+    assert_eq!(map_reduce(&eager(), &[] as &[i64], 1, |&x| x, |a, b| a * b), 1);
+}
+
+#[test]
+fn test_transform_vectors() {
+    let m = Matrix3 { x1y1: 0, x2y1: -1, x3y1: 0
+                    , x1y2: 1, x2y2: 0,  x3y2: 0
+                    , x1y3: 0, x2y3: 0,  x3y3: 2 };
+    let mut vs = (0..100).map(|i| Vector3 { x: i, y: 2 * i, z: 3 * i })
+                         .collect::<Vec<_>>();
+    transform(&eager(), &m, &mut vs);
+    for (i, v) in vs.into_iter().enumerate() {
+        let i = i as i32;
+        assert_eq!(v, Vector3 { x: -2 * i, y: i, z: 6 * i });
+    }
+}
+
+#[test]
+fn test_gemm_matches_single_threaded() {
+    let a = MatrixN::from_fn(67, 45, |x, y| (x * 31 + y * 17) as f64 / 7.0);
+    let b = MatrixN::from_fn(45, 39, |x, y| (x * 13 + y * 5) as f64 / 3.0);
+    let c0 = MatrixN::from_fn(67, 39, |x, y| (x + y) as f64);
+    let mut expected = c0.clone();
+    blas::gemm(0.5, a.view(), b.view(), 2.0, expected.view_mut()).unwrap();
+    for threads in 1..9 {
+        let config = eager().with_threads(threads);
+        let mut c = c0.clone();
+        gemm(&config, 0.5, a.view(), b.view(), 2.0, c.view_mut()).unwrap();
+        // bitwise equal, not merely close.
+        assert_eq!(c, expected);
+
+        let mut ct = c0.transpose();
+        gemm( &config, 0.5, a.view(), b.view()
+            , 2.0, ct.view_mut().transpose()).unwrap();
+        assert_eq!(ct, expected.transpose());
+    }
+}
+
+#[test]
+fn test_gemm_shape_errors() {
+    let a = MatrixN::from_elem(20, 3, 1.0);
+    let mut c = MatrixN::from_elem(20, 20, 1.0);
+    assert_eq!( gemm(&eager(), 1.0, a.view(), a.view(), 0.0, c.view_mut())
+              , Err(ShapeError { expected: (3, 3), found: (20, 3) }));
+    assert_eq!( gemm( &eager(), 1.0, a.view().transpose(), a.view()
+                    , 0.0, c.view_mut())
+              , Err(ShapeError { expected: (3, 3), found: (20, 20) }));
+    assert!(c.iter().all(|&x| x == 1.0));
+}
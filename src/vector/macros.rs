@@ -1,11 +1,3 @@
-macro_rules! e { ($e:expr) => { $e } }
-
-macro_rules! sum {
-    ($x:expr) => { $x };
-    ($x:expr, $($y:expr),+) => { $x + sum!($($y),+) }
-}
-
-
 /// Macro for constructing a new vector type.
 ///
 /// This can be used to construct fixed-sized vectors of whatever dimension
@@ -25,8 +17,8 @@ macro_rules! sum {
 ///    - `$name`: The name of the new vector type
 ///    - `$dim`: the dimension (number of elements) of the new vector type
 ///    - `$sub`: the name of each subscript or element of the vector.
-///              Note that the number of `$sub`s should be the same as the
-///              length of the vector.
+///      Note that the number of `$sub`s should be the same as the
+///      length of the vector.
 ///
 /// # Example
 ///   Consider the definition of `Vector3`:
@@ -37,41 +29,67 @@ macro_rules! sum {
 #[macro_export]
 macro_rules! make_vector {
     ($name: ident, $dim:expr, $($sub: ident),+) => {
-        #[cfg(not(simd))]
         #[derive(Clone, Copy, Eq, PartialEq, PartialOrd, Debug, Default)]
         #[repr(C)]
         pub struct $name<N> {
             $(pub $sub: N),+
         }
 
-        impl_rand! { $name, $($sub),+ }
-
-        impl_ops! { $name, $($sub),+ }
-        impl_converts! { $name, $dim }
-        impl_index! { $name }
+        $crate::impl_rand! { $name, $($sub),+ }
+        $crate::impl_vector! { $name, $dim, $($sub),+ }
+
+        $crate::impl_ops! { $name, $($sub),+ }
+        $crate::impl_vector_converts! { $name, $dim, $($sub),+ }
+        $crate::impl_vector_index! { $name, $dim }
+        $crate::impl_iter! { $name, $dim, $($sub),+ }
+        $crate::impl_approx_eq! { $name, $($sub),+ }
+        $crate::impl_int_arith! { $name, $($sub),+ }
+        $crate::impl_cast! { $name, $($sub),+ }
+        $crate::impl_serde! { $name, $dim, $($sub),+ }
+        $crate::impl_pod! { $name }
+        $crate::impl_vector_fmt! { $name, $dim }
+        $crate::impl_int_dot! { $name, $($sub),+ }
+        $crate::impl_arbitrary! { $name, $($sub),+ }
 
     }
 }
 
-macro_rules! impl_ops {
-    ($ty: ident, $($sub: ident),+) => {
-        impl_op! { Add for $ty, add, +, $($sub),+ }
-        impl_op! { Sub for $ty, sub, -, $($sub),+ }
-        impl_op! { Div for $ty, div, /, $($sub),+ }
-        impl_op! { Rem for $ty, rem, %, $($sub),+ }
-
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_vector {
+    ($v: ident, $c: expr, $($sub: ident),+) => {
+        impl<N> $crate::vector::Vector<N> for $v<N> {
+            const DIM: usize = $c;
+            const SUBSCRIPTS: &'static [&'static str] =
+                &[$(stringify!($sub)),+];
 
-        #[cfg(features = "parallel")]
-        impl<N> Mul<N> for $ty<N>
-        where Self: Simdalize<Elem = N>
-            , N: Mul<Output = N> {
+            #[inline] fn as_slice(&self) -> &[N] { self.as_elems() }
 
-            type Output = Self;
-            fn mul(self, rhs: N) -> Output { self.simdalize() * N::splat(rhs) }
+            #[inline]
+            fn from_slice(slice: &[N])
+                         -> Result<Self, $crate::error::LengthError>
+            where N: Copy {
+                $v::from_slice(slice)
+            }
         }
+    }
+}
 
-        impl<N> Mul<N> for $ty<N>
-        where N: Mul<Output = N>
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_ops {
+    ($ty: ident, $($sub: ident),+) => {
+        $crate::impl_op! { Add for $ty, add, +, AddAssign, add_assign, +=
+                         , $($sub),+ }
+        $crate::impl_op! { Sub for $ty, sub, -, SubAssign, sub_assign, -=
+                         , $($sub),+ }
+        $crate::impl_op! { Div for $ty, div, /, DivAssign, div_assign, /=
+                         , $($sub),+ }
+        $crate::impl_op! { Rem for $ty, rem, %, RemAssign, rem_assign, %=
+                         , $($sub),+ }
+
+        impl<N> $crate::__core::ops::Mul<N> for $ty<N>
+        where N: $crate::__core::ops::Mul<Output = N>
             , N: Copy {
 
             type Output = Self;
@@ -81,71 +99,217 @@ macro_rules! impl_ops {
 
         }
 
-        impl<N> Mul<$ty<N>> for $ty<N>
-        where N: Mul<Output = N> + Add<Output = N>
+        impl<'a, N> $crate::__core::ops::Mul<N> for &'a $ty<N>
+        where N: $crate::__core::ops::Mul<Output = N>
+            , N: Copy {
+
+            type Output = $ty<N>;
+            #[inline] fn mul(self, rhs: N) -> $ty<N> { *self * rhs }
+        }
+
+        impl<N> $crate::__core::ops::MulAssign<N> for $ty<N>
+        where N: $crate::__core::ops::MulAssign
+            , N: Copy {
+
+            #[inline] fn mul_assign(&mut self, rhs: N) {
+                $( self.$sub *= rhs; )+
+            }
+        }
+
+        impl<N> $crate::__core::ops::Mul<$ty<N>> for $ty<N>
+        where N: $crate::__core::ops::Mul<Output = N>
+            , N: $crate::__core::ops::Add<Output = N>
             , N: Copy {
 
             type Output = N;
             fn mul(self, rhs: Self) -> N {
-                sum!( $(self.$sub * rhs.$sub),+ )
+                $crate::__lin_sum!( $(self.$sub * rhs.$sub),+ )
             }
         }
+
+        impl<'a, N> $crate::__core::ops::Mul<&'a $ty<N>> for $ty<N>
+        where N: $crate::__core::ops::Mul<Output = N>
+            , N: $crate::__core::ops::Add<Output = N>
+            , N: Copy {
+
+            type Output = N;
+            #[inline] fn mul(self, rhs: &'a $ty<N>) -> N { self * *rhs }
+        }
+
+        impl<'a, N> $crate::__core::ops::Mul<$ty<N>> for &'a $ty<N>
+        where N: $crate::__core::ops::Mul<Output = N>
+            , N: $crate::__core::ops::Add<Output = N>
+            , N: Copy {
+
+            type Output = N;
+            #[inline] fn mul(self, rhs: $ty<N>) -> N { *self * rhs }
+        }
+
+        impl<'a, 'b, N> $crate::__core::ops::Mul<&'b $ty<N>> for &'a $ty<N>
+        where N: $crate::__core::ops::Mul<Output = N>
+            , N: $crate::__core::ops::Add<Output = N>
+            , N: Copy {
+
+            type Output = N;
+            #[inline] fn mul(self, rhs: &'b $ty<N>) -> N { *self * *rhs }
+        }
+
+        impl<N> $crate::__core::ops::Neg for $ty<N>
+        where N: $crate::__core::ops::Neg<Output = N> {
+
+            type Output = Self;
+            #[inline] fn neg(self) -> Self {
+                $ty { $($sub: -self.$sub),+ }
+            }
+        }
+
+        impl<'a, N> $crate::__core::ops::Neg for &'a $ty<N>
+        where N: $crate::__core::ops::Neg<Output = N>
+            , N: Copy {
+
+            type Output = $ty<N>;
+            #[inline] fn neg(self) -> $ty<N> { -*self }
+        }
+
+        $crate::impl_scalar_mul! { $ty, ($($sub),+)
+                         , u8, u16, u32, u64, usize
+                         , i8, i16, i32, i64, isize
+                         , f32, f64
+                         }
     }
 }
 
+/// Implements `scalar * vector` for each of the given primitive types.
+///
+/// Since a blanket `impl<N> Mul<$ty<N>> for N` would violate the orphan
+/// rules, this has to be done separately for every concrete scalar type.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_scalar_mul {
+    (@impl $ty: ident, ($($sub: ident),+), $n: ty) => {
+        impl $crate::__core::ops::Mul<$ty<$n>> for $n {
+            type Output = $ty<$n>;
+            #[inline] fn mul(self, rhs: $ty<$n>) -> $ty<$n> {
+                $ty { $($sub: self * rhs.$sub),+ }
+            }
+        }
+
+        impl<'a> $crate::__core::ops::Mul<&'a $ty<$n>> for $n {
+            type Output = $ty<$n>;
+            #[inline] fn mul(self, rhs: &'a $ty<$n>) -> $ty<$n> {
+                self * *rhs
+            }
+        }
+    };
+    ($ty: ident, $subs: tt, $($n: ty),+) => {
+        $( $crate::impl_scalar_mul! { @impl $ty, $subs, $n } )+
+    }
+}
+
+#[doc(hidden)]
+#[macro_export]
 macro_rules! impl_op {
-    ($name: ident for $ty:ident, $fun: ident, $op:tt, $($sub: ident),+) => {
-        // implement the operation for vector & vectorI
-        impl<N> $name<$ty<N>> for $ty<N>
-        where N: $name<Output=N>
+    ( $name: ident for $ty:ident, $fun: ident, $op:tt
+    , $assign: ident, $assign_fun: ident, $assign_op: tt
+    , $($sub: ident),+) => {
+        // implement the operation for vector & vector
+        impl<N> $crate::__core::ops::$name<$ty<N>> for $ty<N>
+        where N: $crate::__core::ops::$name<Output=N>
             , N: Copy {
 
             type Output = Self;
             fn $fun(self, rhs: Self) -> Self::Output {
-                $ty { $($sub: e!(self.$sub $op rhs.$sub)),+ }
+                $ty { $($sub: $crate::__lin_expr!(self.$sub $op rhs.$sub)),+ }
             }
         }
 
         // implement the operation for vector & scalar
-        impl<N> $name<N> for $ty<N>
-        where N: $name<Output=N>
+        impl<N> $crate::__core::ops::$name<N> for $ty<N>
+        where N: $crate::__core::ops::$name<Output=N>
             , N: Copy {
 
             type Output = Self;
             fn $fun(self, rhs: N) -> Self::Output {
-                $ty { $($sub: e!(self.$sub $op rhs)),+ }
+                $ty { $($sub: $crate::__lin_expr!(self.$sub $op rhs)),+ }
             }
         }
 
-        #[cfg(features = "parallel")]
-        impl<N> $name<N> for $ty<N>
-        where Self: Simdalize<Elem = N>
-            , N: $name<Output = N>
+        // implement the operation for references to vectors
+        impl<'a, N> $crate::__core::ops::$name<&'a $ty<N>> for $ty<N>
+        where N: $crate::__core::ops::$name<Output=N>
             , N: Copy {
 
-            type Output = Self;
-            fn $fun(self, rhs: N) -> Output {
-                e!(self.simdalize() $op N::splat(rhs))
+            type Output = $ty<N>;
+            #[inline] fn $fun(self, rhs: &'a $ty<N>) -> Self::Output {
+                $crate::__lin_expr!(self $op *rhs)
             }
         }
 
-        #[cfg(features = "parallel")]
-        impl<N> $name<N> for $ty<N>
-        where Self: Simdalize<Elem = N>
-            , N: $name<Output = N> {
+        impl<'a, N> $crate::__core::ops::$name<$ty<N>> for &'a $ty<N>
+        where N: $crate::__core::ops::$name<Output=N>
+            , N: Copy {
 
-            type Output = Self;
-            fn $fun(self, rhs: Self) -> Output {
-                e!(self.simdalize() $op rhs.simdalize())
+            type Output = $ty<N>;
+            #[inline] fn $fun(self, rhs: $ty<N>) -> Self::Output {
+                $crate::__lin_expr!(*self $op rhs)
+            }
+        }
+
+        impl<'a, 'b, N> $crate::__core::ops::$name<&'b $ty<N>> for &'a $ty<N>
+        where N: $crate::__core::ops::$name<Output=N>
+            , N: Copy {
+
+            type Output = $ty<N>;
+            #[inline] fn $fun(self, rhs: &'b $ty<N>) -> Self::Output {
+                $crate::__lin_expr!(*self $op *rhs)
+            }
+        }
+
+        impl<'a, N> $crate::__core::ops::$name<N> for &'a $ty<N>
+        where N: $crate::__core::ops::$name<Output=N>
+            , N: Copy {
+
+            type Output = $ty<N>;
+            #[inline] fn $fun(self, rhs: N) -> Self::Output {
+                $crate::__lin_expr!(*self $op rhs)
             }
         }
 
+        // implement the compound assignment operators
+        impl<N> $crate::__core::ops::$assign<$ty<N>> for $ty<N>
+        where N: $crate::__core::ops::$assign
+            , N: Copy {
+
+            #[inline] fn $assign_fun(&mut self, rhs: Self) {
+                $( $crate::__lin_expr!(self.$sub $assign_op rhs.$sub); )+
+            }
+        }
+
+        impl<'a, N> $crate::__core::ops::$assign<&'a $ty<N>> for $ty<N>
+        where N: $crate::__core::ops::$assign
+            , N: Copy {
+
+            #[inline] fn $assign_fun(&mut self, rhs: &'a $ty<N>) {
+                $( $crate::__lin_expr!(self.$sub $assign_op rhs.$sub); )+
+            }
+        }
+
+        impl<N> $crate::__core::ops::$assign<N> for $ty<N>
+        where N: $crate::__core::ops::$assign
+            , N: Copy {
+
+            #[inline] fn $assign_fun(&mut self, rhs: N) {
+                $( $crate::__lin_expr!(self.$sub $assign_op rhs); )+
+            }
+        }
     }
 }
 
-macro_rules! impl_index {
-    ($($v: ident),+) => { $(
-        impl<N> ops::Index<usize> for $v<N>
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_vector_index {
+    ($($v: ident, $c: expr),+) => { $(
+        impl<N> $crate::__core::ops::Index<usize> for $v<N>
         where N: Copy {
 
             type Output = N;
@@ -154,7 +318,7 @@ macro_rules! impl_index {
             }
         }
 
-        impl<N> ops::IndexMut<usize> for $v<N>
+        impl<N> $crate::__core::ops::IndexMut<usize> for $v<N>
         where N: Copy {
 
             #[inline] fn index_mut(&mut self, i: usize) -> &mut N {
@@ -162,28 +326,34 @@ macro_rules! impl_index {
             }
         }
 
-        impl<N> Columnar for $v<N>
+        impl<N> $crate::Columnar for $v<N>
         where N: Copy {
             type Column = $v<N>;
+            type ColumnRef<'a> = &'a $v<N> where Self: 'a;
+            type ColumnMut<'a> = &'a mut $v<N> where Self: 'a;
 
             #[inline] fn ncols(&self) -> usize { 1 }
 
-            #[inline] fn column(&self, i: usize) -> Option<&Self::Column> {
+            #[inline] fn column(&self, i: usize) -> Option<&$v<N>> {
                 if i == 0 { Some(self) } else { None }
             }
 
             #[inline]
-            fn column_mut(&mut self, i: usize) -> Option<&mut Self::Column> {
+            fn column_mut(&mut self, i: usize) -> Option<&mut $v<N>> {
                 if i == 0 { Some(self) } else { None }
             }
 
+            #[inline] fn column_owned(&self, i: usize) -> Option<$v<N>> {
+                if i == 0 { Some(*self) } else { None }
+            }
+
         }
 
-        impl<N> Tabular for $v<N>
+        impl<N> $crate::Tabular for $v<N>
         where N: Copy {
 
             type Row = N;
-            #[inline] fn nrows(&self) -> usize { 3 }
+            #[inline] fn nrows(&self) -> usize { $c }
 
             #[inline]
             fn row(&self, i: usize) -> Option<&Self::Row> {
@@ -197,64 +367,202 @@ macro_rules! impl_index {
             }
 
         }
+
+        // a vector has exactly one column, and each row is a distinct element.
+        unsafe impl<N> $crate::ColumnarMut for $v<N>
+        where N: Copy {
+            type ColumnsRaw = *mut $v<N>;
+
+            #[inline] fn columns_raw(&mut self) -> *mut $v<N> { self }
+
+            #[inline]
+            unsafe fn column_from_raw<'a>(raw: *mut $v<N>, _: usize)
+                                         -> &'a mut $v<N>
+            where Self: 'a {
+                &mut *raw
+            }
+        }
+
+        unsafe impl<N> $crate::TabularMut for $v<N>
+        where N: Copy {
+            type RowsRaw = *mut N;
+
+            #[inline] fn rows_raw(&mut self) -> *mut N {
+                self.as_elems_mut().as_mut_ptr()
+            }
+
+            #[inline]
+            unsafe fn row_from_raw<'a>(raw: *mut N, i: usize) -> &'a mut N
+            where Self: 'a {
+                &mut *raw.add(i)
+            }
+        }
     )+}
 }
 
-#[cfg(features = "rand")]
+#[cfg(feature = "rand")]
+#[doc(hidden)]
+#[macro_export]
 macro_rules! impl_rand {
     ($ty: ident, $($sub: ident),+) => {
-        impl<N> Rand for $ty<N>
-        where N: Rand {
+        impl<N> $crate::rand::Rand for $ty<N>
+        where N: $crate::rand::Rand {
 
-            fn rand<R: Rng>(rng: &mut R) -> Self {
+            fn rand<R: $crate::rand::Rng>(rng: &mut R) -> Self {
                 $ty { $($sub: N::rand(rng)),+ }
             }
         }
     }
 }
 
+#[cfg(not(feature = "rand"))]
+#[doc(hidden)]
+#[macro_export]
 macro_rules! impl_rand {
     ($ty: ident, $($sub: ident),+) => { }
 }
 
 
-macro_rules! impl_converts {
-    ($($v: ident, $c: expr),+) => { $(
-        impl<N> convert::AsRef<[N; $c]> for $v<N>
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_vector_converts {
+    ($v: ident, $c: expr, $($sub: ident),+) => {
+        $crate::impl_layout! { $v, $c }
+
+        impl<N> $crate::__core::convert::AsRef<[N; $c]> for $v<N>
         where N: Copy {
 
-            #[inline] fn as_ref(&self) -> &[N; $c] {
-                unsafe { transmute(self) }
-            }
+            #[inline] fn as_ref(&self) -> &[N; $c] { self.as_elems() }
         }
-        impl<N> convert::AsMut<[N; $c]> for $v<N>
+        impl<N> $crate::__core::convert::AsMut<[N; $c]> for $v<N>
         where N: Copy {
 
             #[inline] fn as_mut(&mut self) -> &mut [N; $c] {
-                unsafe { transmute(self) }
+                self.as_elems_mut()
             }
         }
-        impl<'a, N> convert::From<&'a [N; $c]> for &'a $v<N>
+        impl<'a, N> $crate::__core::convert::From<&'a [N; $c]> for &'a $v<N>
         where N: Copy {
 
             #[inline] fn from(a: &'a [N; $c]) -> &'a $v<N> {
-                unsafe { transmute(a) }
+                let () = $v::<N>::ARRAY_LAYOUT;
+                unsafe { &*(a as *const [N; $c] as *const $v<N>) }
             }
         }
-        impl<'a, N> convert::From<&'a mut [N; $c]> for &'a mut $v<N>
+        impl<'a, N> $crate::__core::convert::From<&'a mut [N; $c]>
+        for &'a mut $v<N>
         where N: Copy {
 
             #[inline] fn from(a: &'a mut [N; $c]) -> &'a mut $v<N> {
-                unsafe { transmute(a) }
+                let () = $v::<N>::ARRAY_LAYOUT;
+                unsafe { &mut *(a as *mut [N; $c] as *mut $v<N>) }
             }
         }
-        // impl<N: ?Sized> From<[N; $c]> for $v<N>
-        // where N: Numeric
-        //     , N: Copy {
-        //     #[inline]
-        //     fn from(a: [N; $c]) -> $v<N> {
-        //         unsafe { transmute(a) }
-        //     }
-        // }
-    )+}
+        impl<N> $crate::__core::convert::From<[N; $c]> for $v<N> {
+
+            #[inline] fn from(a: [N; $c]) -> $v<N> {
+                let [$($sub),+] = a;
+                $v { $($sub: $sub),+ }
+            }
+        }
+        impl<N> $crate::__core::convert::From<$v<N>> for [N; $c] {
+
+            #[inline] fn from(v: $v<N>) -> [N; $c] { [$(v.$sub),+] }
+        }
+        impl<N>
+        $crate::__core::convert::From<($($crate::tuple_elem!($sub, N)),+)>
+        for $v<N> {
+
+            #[inline]
+            fn from(t: ($($crate::tuple_elem!($sub, N)),+)) -> $v<N> {
+                let ($($sub),+) = t;
+                $v { $($sub: $sub),+ }
+            }
+        }
+        impl<N> $crate::__core::convert::From<$v<N>>
+        for ($($crate::tuple_elem!($sub, N)),+) {
+
+            #[inline]
+            fn from(v: $v<N>) -> ($($crate::tuple_elem!($sub, N)),+) {
+                ($(v.$sub),+)
+            }
+        }
+    }
 }
+
+/// Implements `Display` as `(x, y, ...)`, formatting each element with the
+/// formatter's flags, and `FromStr` accepting any of the forms described in
+/// the `parse` module.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_vector_fmt {
+    ($v: ident, $c: expr) => {
+        impl<N> $crate::__core::fmt::Display for $v<N>
+        where N: $crate::__core::fmt::Display {
+
+            fn fmt(&self, f: &mut $crate::__core::fmt::Formatter)
+                  -> $crate::__core::fmt::Result {
+                f.write_str("(")?;
+                for (i, elem) in self.as_elems().iter().enumerate() {
+                    if i > 0 { f.write_str(", ")? }
+                    elem.fmt(f)?;
+                }
+                f.write_str(")")
+            }
+        }
+        $crate::impl_from_str! { $v, parse_vector, $c }
+    }
+}
+
+/// Expands to the type `$t` once for each subscript, in order to write out
+/// the tuple type corresponding to a vector.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! tuple_elem {
+    ($sub: ident, $t: ty) => { $t }
+}
+
+/// Implements overflow-aware dot products for vectors with integer elements.
+///
+/// The products of each pair of elements are accumulated in subscript order;
+/// for `saturating_dot`, this means the result may depend on the order of the
+/// subscripts if an intermediate sum saturates.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_int_dot {
+    ($ty: ident, $first: ident $(, $rest: ident)*) => {
+        impl<N> $ty<N>
+        where N: $crate::Integer {
+
+            /// Dot product, returning `None` if any product or partial sum
+            /// overflows.
+            pub fn checked_dot(self, rhs: Self) -> Option<N> {
+                use $crate::Integer;
+                let acc = Integer::checked_mul(self.$first, rhs.$first)?;
+                $( let acc = Integer::checked_add(
+                    acc, Integer::checked_mul(self.$rest, rhs.$rest)?)?; )*
+                Some(acc)
+            }
+
+            /// Dot product, wrapping around on overflow.
+            pub fn wrapping_dot(self, rhs: Self) -> N {
+                use $crate::Integer;
+                let acc = Integer::wrapping_mul(self.$first, rhs.$first);
+                $( let acc = Integer::wrapping_add(
+                    acc, Integer::wrapping_mul(self.$rest, rhs.$rest)); )*
+                acc
+            }
+
+            /// Dot product, clamping each product and partial sum to the
+            /// bounds of `N`.
+            pub fn saturating_dot(self, rhs: Self) -> N {
+                use $crate::Integer;
+                let acc = Integer::saturating_mul(self.$first, rhs.$first);
+                $( let acc = Integer::saturating_add(
+                    acc, Integer::saturating_mul(self.$rest, rhs.$rest)); )*
+                acc
+            }
+        }
+    }
+}
+
@@ -1,29 +1,31 @@
-use super::{Numeric, Columnar, Tabular};
-
-use std::ops::{Add, Sub, Mul, Div, Rem};
-use std::ops;
-use std::convert;
-use std::mem::transmute;
-
-#[cfg(features = "parallel")]
-use super::parallel::*;
-
-#[cfg(features = "rand")]
-use rand::Rand;
+use error::LengthError;
 
 #[cfg(test)] mod test;
-#[cfg(test)] mod bench;
 
 #[macro_use] mod macros;
+mod wide;
+
+pub use self::wide::{Vector3x4, Vector3x8};
 
 pub trait Vector<N>: Sized {
 
-    #[cfg(features = "unstable")]
-    fn is_perpendicular_to<M>(self, v_prime: Self) -> bool
-    where Self: Mul<Self, Output=M>
-        , M: PartialEq {
-        (self * v_prime) == M::zero()
-    }
+    /// The number of elements in this vector type
+    const DIM: usize;
+
+    /// The names of this vector type's subscripts, in order
+    const SUBSCRIPTS: &'static [&'static str];
+
+    /// Borrow the elements of this vector as a slice
+    fn as_slice(&self) -> &[N];
+
+    /// Copies the elements of `slice` into a new vector.
+    ///
+    /// # Returns:
+    ///   - `Ok(Self)` if `slice` contains exactly `DIM` elements
+    ///   - `Err(LengthError)` otherwise
+    ///
+    fn from_slice(slice: &[N]) -> Result<Self, LengthError>
+    where N: Copy;
 }
 
 make_vector! { Vector2, 2, x, y }
@@ -33,5 +35,3 @@ make_vector! { Vector3, 3, x, y, z }
 make_vector! { Vector4, 4, x, y, z, w }
 
 make_vector! { Vector5, 5, x, y, z, w, a }
-
-pub struct VectorN<'a, N: Numeric + 'a>(&'a [N]);
@@ -1,6 +1,7 @@
 use super::*;
-// use quickcheck::quickcheck;
 use quickcheck::TestResult;
+use matrix::Matrix3;
+use {Columnar, Tabular, TabularMut};
 
 
 #[test]
@@ -24,43 +25,319 @@ fn test_v2_dot_simple() {
     assert_eq!(v1 * v2, 10)
 }
 
+#[test]
+fn test_v2_neg_simple() {
+    let v = Vector2 { x: 1, y: -2 };
+    assert_eq!(-v, Vector2 { x: -1, y: 2 });
+    assert_eq!(-&v, Vector2 { x: -1, y: 2 })
+}
+
+#[test]
+fn test_v3_assign_ops_simple() {
+    let mut v = Vector3 { x: 1, y: 2, z: 3 };
+    v += Vector3 { x: 1, y: 1, z: 1 };
+    assert_eq!(v, Vector3 { x: 2, y: 3, z: 4 });
+    v -= &Vector3 { x: 2, y: 2, z: 2 };
+    assert_eq!(v, Vector3 { x: 0, y: 1, z: 2 });
+    v *= 3;
+    assert_eq!(v, Vector3 { x: 0, y: 3, z: 6 });
+    v /= 3;
+    assert_eq!(v, Vector3 { x: 0, y: 1, z: 2 });
+    v %= 2;
+    assert_eq!(v, Vector3 { x: 0, y: 1, z: 0 })
+}
+
+#[test]
+// the references are the point of this test
+#[allow(clippy::op_ref)]
+fn test_v2_ref_ops_simple() {
+    let v1 = Vector2 { x: 1, y: 1 };
+    let v2 = Vector2 { x: 2, y: 3 };
+    assert_eq!(&v1 + &v2, Vector2 { x: 3, y: 4 });
+    assert_eq!(v1 + &v2, Vector2 { x: 3, y: 4 });
+    assert_eq!(&v2 - v1, Vector2 { x: 1, y: 2 });
+    assert_eq!(&v2 * 2, Vector2 { x: 4, y: 6 });
+    assert_eq!(&v1 * &v2, 5)
+}
+
+#[test]
+fn test_v3_scalar_lhs_mul() {
+    let v = Vector3 { x: 1.0, y: 2.0, z: 3.0 };
+    assert_eq!(2.0 * v, Vector3 { x: 2.0, y: 4.0, z: 6.0 });
+    assert_eq!(2.0 * &v, v * 2.0);
+    assert_eq!(3u8 * Vector2 { x: 1, y: 2 }, Vector2 { x: 3, y: 6 })
+}
+
+#[test]
+fn test_v3_iter_simple() {
+    let mut v = Vector3 { x: 1, y: 2, z: 3 };
+    assert_eq!(v.iter().cloned().collect::<Vec<_>>(), vec![1, 2, 3]);
+    for n in &mut v { *n *= 2 }
+    assert_eq!(v, Vector3 { x: 2, y: 4, z: 6 });
+    assert_eq!(v.into_iter().collect::<Vec<_>>(), vec![2, 4, 6])
+}
+
+#[test]
+fn test_v3_map_zip_map() {
+    let v1 = Vector3 { x: 1, y: 2, z: 3 };
+    let v2 = Vector3 { x: 4, y: 5, z: 6 };
+    assert_eq!(v1.map(|n| n as f32 / 2.0), Vector3 { x: 0.5, y: 1.0, z: 1.5 });
+    assert_eq!(v1.zip_map(v2, |a, b| a * b), Vector3 { x: 4, y: 10, z: 18 })
+}
+
+#[test]
+fn test_v3_from_iter() {
+    let v: Vector3<i32> = (1..4).collect();
+    assert_eq!(v, Vector3 { x: 1, y: 2, z: 3 });
+    assert_eq!(Vector3::try_from_iter(1..3), None);
+    assert_eq!(Vector3::try_from_iter(1..5), None);
+    assert_eq!(Vector3::try_from_iter(1..4), Some(v))
+}
+
+#[test]
+#[should_panic]
+fn test_v3_from_iter_too_short() {
+    let _: Vector3<i32> = (1..3).collect();
+}
+
+#[test]
+fn test_v2_sum() {
+    let vs = vec![ Vector2 { x: 1, y: 2 }
+                 , Vector2 { x: 3, y: 4 }
+                 , Vector2 { x: 5, y: 6 }
+                 ];
+    assert_eq!(vs.iter().sum::<Vector2<i32>>(), Vector2 { x: 9, y: 12 });
+    assert_eq!(vs.into_iter().sum::<Vector2<i32>>(), Vector2 { x: 9, y: 12 })
+}
+
+#[test]
+fn test_v2_product() {
+    let vs = vec![ Vector2 { x: 1, y: 2 }
+                 , Vector2 { x: 3, y: 4 }
+                 , Vector2 { x: 5, y: 6 }
+                 ];
+    assert_eq!( vs.iter().product::<Vector2<i32>>()
+              , Vector2 { x: 15, y: 48 });
+    assert_eq!( vs.into_iter().product::<Vector2<i32>>()
+              , Vector2 { x: 15, y: 48 });
+    let none: Vec<Vector2<f64>> = Vec::new();
+    assert_eq!( none.into_iter().product::<Vector2<f64>>()
+              , Vector2 { x: 1.0, y: 1.0 })
+}
+
+#[test]
+fn test_v3_rows_iter_mut() {
+    let mut v = Vector3 { x: 1, y: 2, z: 3 };
+    for row in v.rows_iter_mut() { *row += 1 }
+    assert_eq!(v, Vector3 { x: 2, y: 3, z: 4 });
+    {
+        let mut rows = v.rows_iter_mut();
+        let first = rows.next().unwrap();
+        let last = rows.next_back().unwrap();
+        ::std::mem::swap(first, last);
+    }
+    assert_eq!(v, Vector3 { x: 4, y: 3, z: 2 })
+}
+
+#[test]
+fn test_v3_rows_iter_double_ended() {
+    let v = Vector3 { x: 1, y: 2, z: 3 };
+    assert_eq!(v.rows_iter().len(), 3);
+    assert_eq!(v.rows_iter().rev().cloned().collect::<Vec<_>>(), vec![3, 2, 1]);
+    assert_eq!(v.cols_iter().len(), 1);
+    assert_eq!(v.cols_iter().next_back(), Some(&v))
+}
+
+#[test]
+fn test_v2_checked_arith() {
+    let v = Vector2 { x: 200u8, y: 10 };
+    assert_eq!( v.checked_add(Vector2 { x: 55, y: 1 })
+              , Some(Vector2 { x: 255, y: 11 }));
+    assert_eq!(v.checked_add(Vector2 { x: 56, y: 1 }), None);
+    assert_eq!(v.checked_sub(Vector2 { x: 1, y: 11 }), None);
+    assert_eq!(v.checked_mul(2), None);
+    assert_eq!(v.checked_div(0), None);
+    assert_eq!(v.checked_div(10), Some(Vector2 { x: 20, y: 1 }))
+}
+
+#[test]
+fn test_v3_wrapping_saturating_arith() {
+    let v = Vector3 { x: 250u8, y: 5, z: 0 };
+    let w = Vector3 { x: 10u8, y: 5, z: 1 };
+    assert_eq!(v.wrapping_add(w), Vector3 { x: 4, y: 10, z: 1 });
+    assert_eq!(v.wrapping_sub(w), Vector3 { x: 240, y: 0, z: 255 });
+    assert_eq!(v.saturating_add(w), Vector3 { x: 255, y: 10, z: 1 });
+    assert_eq!(v.saturating_sub(w), Vector3 { x: 240, y: 0, z: 0 });
+    assert_eq!(v.saturating_mul(2), Vector3 { x: 255, y: 10, z: 0 });
+    assert_eq!(v.wrapping_mul(2), Vector3 { x: 244, y: 10, z: 0 })
+}
+
+#[test]
+fn test_v3_overflowing_dot() {
+    let v = Vector3 { x: 100i8, y: 1, z: 1 };
+    let w = Vector3 { x: 1i8, y: 26, z: 1 };
+    assert_eq!(v.checked_dot(w), Some(127));
+    assert_eq!(v.checked_dot(w + Vector3 { x: 0, y: 0, z: 1 }), None);
+    assert_eq!(v.wrapping_dot(Vector3 { x: 1, y: 28, z: 0 }), -128);
+    assert_eq!(v.saturating_dot(Vector3 { x: 2, y: 28, z: 0 }), 127)
+}
+
+#[test]
+fn test_v3_array_tuple_conversions() {
+    let v = Vector3::from([1, 2, 3]);
+    assert_eq!(v, Vector3 { x: 1, y: 2, z: 3 });
+    assert_eq!(<[i32; 3]>::from(v), [1, 2, 3]);
+    assert_eq!(Vector3::from((1, 2, 3)), v);
+    let t: (i32, i32, i32) = v.into();
+    assert_eq!(t, (1, 2, 3))
+}
+
+#[test]
+fn test_v3_from_slice() {
+    use error::LengthError;
+    assert_eq!( Vector3::from_slice(&[1, 2, 3])
+              , Ok(Vector3 { x: 1, y: 2, z: 3 }));
+    assert_eq!( Vector3::<i32>::from_slice(&[1, 2])
+              , Err(LengthError::Exact { expected: 3, found: 2 }));
+    let mut buf = [0; 3];
+    Vector3 { x: 4, y: 5, z: 6 }.copy_to_slice(&mut buf).unwrap();
+    assert_eq!(buf, [4, 5, 6]);
+    assert!(Vector3 { x: 4, y: 5, z: 6 }.copy_to_slice(&mut [0; 4]).is_err())
+}
+
+#[test]
+fn test_v3_slice_of() {
+    use error::LengthError;
+    let vs = [ Vector3 { x: 1.0f32, y: 2.0, z: 3.0 }
+             , Vector3 { x: 4.0, y: 5.0, z: 6.0 }
+             ];
+    let elems = Vector3::as_slice_of(&vs);
+    assert_eq!(elems, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    assert_eq!(Vector3::from_slice_of(elems), Ok(&vs[..]));
+    assert_eq!( Vector3::from_slice_of(&elems[1..])
+              , Err(LengthError::Multiple { of: 3, found: 5 }))
+}
+
 macro_rules! e { ($e:expr) => { $e } }
+// the expected result is computed with the checked operation, so that
+// inputs which would overflow (or divide by zero) are discarded rather than
+// panicking.
 macro_rules! v2_arith_props {
-    ($($fun:ident, $op:tt),*) => {$(
+    ($($fun:ident, $op:tt, $checked:ident),*) => {$(
         #[quickcheck]
         fn $fun( x1: isize, x2: isize
-               , y1: isize, y2: isize) -> bool
+               , y1: isize, y2: isize) -> TestResult
         {
-            let v1 = Vector2 { x: x1, y: y1 };
-            let v2 = Vector2 { x: x2, y: y2 };
-            Vector2 { x: e!(x1 $op x2), y: e!(y1 $op y2)} == e!(v1 $op v2)
+            match (x1.$checked(x2), y1.$checked(y2)) {
+                (Some(x), Some(y)) => {
+                    let v1 = Vector2 { x: x1, y: y1 };
+                    let v2 = Vector2 { x: x2, y: y2 };
+                    TestResult::from_bool(Vector2 { x, y }
+                                          == e!(v1 $op v2))
+                }
+              , _ => TestResult::discard()
+            }
         }
     )*}
 }
 
-macro_rules! v2_div_props {
-    ($($fun:ident, $op:tt),*) => {$(
+v2_arith_props!( prop_v2_addition, +, checked_add
+               , prop_v2_subtraction, -, checked_sub
+               , prop_v2_division, /, checked_div
+               , prop_v2_mod, %, checked_rem
+               );
+
+macro_rules! index_agreement_props {
+    ($($fun:ident, $ty:ident, $dim:expr),*) => {$(
         #[quickcheck]
-        fn $fun( x1: isize, x2: isize
-               , y1: isize, y2: isize) -> TestResult
-        {
-            if x1 == 0 || x2 == 0 || y1 == 0 || y2 == 0 {
-                TestResult::discard()
-            } else {
-                let v1 = Vector2 { x: x1, y: y1 };
-                let v2 = Vector2 { x: x2, y: y2 };
-                let v3 = Vector2 { x: e!(x1 $op x2), y: e!(y1 $op y2)};
-                TestResult::from_bool(v3 == e!(v1 $op v2))
+        fn $fun(elems: Vec<isize>) -> TestResult {
+            if elems.len() < $dim {
+                return TestResult::discard()
             }
+            let v = $ty::try_from_iter(elems.iter().cloned().take($dim))
+                        .unwrap();
+            let arr: &[isize; $dim] = v.as_ref();
+            TestResult::from_bool(
+                v.nrows() == $dim && v.ncols() == 1
+                && v.rows_iter().count() == $dim
+                && v.rows_iter().zip(arr.iter()).all(|(a, b)| a == b)
+                && (0..$dim).all(|i| v[i] == elems[i]
+                                  && v[i] == arr[i]
+                                  && v.row(i) == Some(&arr[i]))
+                && v.row($dim).is_none()
+                && v.column(0) == Some(&v)
+                && v.column(1).is_none())
         }
     )*}
 }
 
-v2_arith_props!( prop_v2_addition, +
-               , prop_v2_subtraction, -
-                //  prop_v2_division, /
-                //  prop_v2_mod, %
-                );
-v2_div_props!( prop_v2_division, /
-             , prop_v2_mod, %
-             );
+index_agreement_props!( prop_v2_index_agreement, Vector2, 2
+                      , prop_v3_index_agreement, Vector3, 3
+                      , prop_v4_index_agreement, Vector4, 4
+                      , prop_v5_index_agreement, Vector5, 5
+                      );
+
+additive_laws! { v2_i32_laws, Vector2, i32 }
+additive_laws! { v3_f32_laws, Vector3, f32 }
+additive_laws! { v4_f64_laws, Vector4, f64 }
+additive_laws! { v5_i64_laws, Vector5, i64 }
+
+#[quickcheck]
+fn prop_wide_ops_match_lanes(vs: Vec<Vector3<i32>>, k: i32) -> TestResult {
+    if vs.len() < 16 { return TestResult::discard() }
+    let vs = vs.iter().map(|v| v.map(|x| x % 1000)).collect::<Vec<_>>();
+    // nonzero, so that division is defined
+    let k = (k % 1000) | 1;
+    let (a, b) = (&vs[..8], vs[8..16].iter().map(|v| v.map(|x| x | 1))
+                                              .collect::<Vec<_>>());
+    let (wa, wb) = ( Vector3x8::gather(a).unwrap()
+                   , Vector3x8::gather(&b).unwrap());
+    let m = Matrix3 { x1y1: k, x2y1: 2, x3y1: -3
+                    , x1y2: 4, x2y2: k, x3y2: 6
+                    , x1y3: -7, x2y3: 8, x3y3: k };
+    let dot = wa * wb;
+    let magnitude = wa * wa;
+    TestResult::from_bool((0..8).all(|i| {
+        let lane = |w: Vector3x8<i32>| w.lane(i).unwrap();
+        let (a, b) = (a[i], b[i]);
+        lane(wa + wb) == a + b && lane(wa - wb) == a - b
+        && lane(wa / wb) == a / b && lane(wa % wb) == a % b
+        && lane(wa + k) == a + k && lane(wa / k) == a / k
+        && lane(wa * k) == a * k && lane(k * wa) == a * k
+        && lane(-wa) == -a && dot[i] == a * b && magnitude[i] == a * a
+        && lane(m * wa) == m * a
+    }))
+}
+
+#[test]
+fn test_wide_gather_scatter() {
+    let vs = (0..9).map(|i| Vector3 { x: i, y: 10 * i, z: -i })
+                   .collect::<Vec<_>>();
+    let w = Vector3x4::gather(&vs[1..5]).unwrap();
+    assert_eq!(w.x, [1, 2, 3, 4]);
+    assert_eq!(w.y, [10, 20, 30, 40]);
+    assert_eq!(w.lane(3), Some(vs[4]));
+    assert_eq!(w.lane(4), None);
+    assert_eq!( Vector3x4::gather(&vs[..3])
+              , Err(LengthError::Exact { expected: 4, found: 3 }));
+    assert_eq!( Vector3x8::gather(&vs)
+              , Err(LengthError::Exact { expected: 8, found: 9 }));
+
+    let mut out = vec![Vector3::default(); 4];
+    let mut w2 = w;
+    w2 += Vector3x4::splat(Vector3 { x: 100, y: 0, z: 0 });
+    w2.scatter(&mut out).unwrap();
+    assert_eq!(out[0], Vector3 { x: 101, y: 10, z: -1 });
+    assert_eq!( w2.scatter(&mut out[..2])
+              , Err(LengthError::Exact { expected: 4, found: 2 }));
+    let arr: [Vector3<i32>; 4] = w.into();
+    assert_eq!(&arr[..], &vs[1..5]);
+    assert_eq!(Vector3x4::from(arr), w);
+}
+
+#[test]
+fn test_wide_layout() {
+    assert_eq!(::std::mem::align_of::<Vector3x4<f32>>(), 16);
+    assert_eq!(::std::mem::align_of::<Vector3x8<f32>>(), 32);
+    assert_eq!(::std::mem::size_of::<Vector3x8<f32>>(), 3 * 8 * 4);
+}
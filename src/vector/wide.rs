@@ -0,0 +1,402 @@
+//! Wide vectors, which hold several vectors in structure-of-arrays layout.
+//!
+//! A `Vector3x8<f32>` holds eight `Vector3<f32>`s as three arrays of eight
+//! lanes, one array for each component, aligned so that each array can be
+//! loaded into a SIMD register at once. Arithmetic on wide vectors applies
+//! the same operation to every lane, which the compiler turns into one SIMD
+//! instruction per component, whereas the array-of-structures layout of a
+//! `[Vector3<f32>]` needs to be shuffled first.
+//!
+//! Wide vectors support the same operators as the vectors they hold. A
+//! scalar operand applies to every lane, and products with one result per
+//! lane, such as the dot product, return an array of results. Slices of
+//! ordinary vectors are processed a chunk at a time:
+//!
+//! ```ignore
+//! let offset = Vector3x8::splat(offset);
+//! let mut chunks = points.chunks_exact_mut(Vector3x8::<f32>::LANES);
+//! for chunk in &mut chunks {
+//!     let v = Vector3x8::gather(chunk).unwrap();
+//!     (rotation * v + offset).scatter(chunk).unwrap();
+//! }
+//! for p in chunks.into_remainder() {
+//!     *p = rotation * *p + offset.lane(0).unwrap();
+//! }
+//! ```
+
+use super::Vector3;
+use matrix::Matrix3;
+use error::LengthError;
+
+use std::ops::{Add, Sub, Mul, Div, Rem, Neg};
+use std::ops::{AddAssign, SubAssign, MulAssign, DivAssign, RemAssign};
+
+/// Returns the result of applying `f` to each lane of `a`.
+#[inline(always)]
+fn map<N, F, const L: usize>(a: [N; L], f: F) -> [N; L]
+where N: Copy
+    , F: Fn(N) -> N {
+    let mut out = a;
+    for i in 0..L { out[i] = f(a[i]) }
+    out
+}
+
+/// Returns the result of applying `f` to each pair of corresponding lanes of
+/// `a` and `b`.
+#[inline(always)]
+fn zip<N, F, const L: usize>(a: [N; L], b: [N; L], f: F) -> [N; L]
+where N: Copy
+    , F: Fn(N, N) -> N {
+    let mut out = a;
+    for i in 0..L { out[i] = f(a[i], b[i]) }
+    out
+}
+
+macro_rules! make_wide {
+    ( $(#[$attr: meta])* $name: ident, $v: ident, $lanes: expr, $align: tt
+    , $($sub: ident),+) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Eq, PartialEq, Debug, Default)]
+        #[repr(C, align($align))]
+        pub struct $name<N> {
+            $(pub $sub: [N; $lanes]),+
+        }
+
+        impl<N> $name<N>
+        where N: Copy {
+
+            /// The number of vectors held in each wide vector
+            pub const LANES: usize = $lanes;
+
+            /// Returns a wide vector holding `v` in every lane
+            #[inline] pub fn splat(v: $v<N>) -> Self {
+                $name { $($sub: [v.$sub; $lanes]),+ }
+            }
+
+            /// Returns the vector held in lane `i`
+            ///
+            /// # Returns:
+            ///   - `Some($v)` if `i` is less than `LANES`
+            ///   - `None` otherwise
+            ///
+            #[inline] pub fn lane(&self, i: usize) -> Option<$v<N>> {
+                if i < $lanes { Some($v { $($sub: self.$sub[i]),+ }) }
+                else { None }
+            }
+
+            /// Copies the vectors in `vs` into the lanes of a new wide
+            /// vector, in order.
+            ///
+            /// # Returns:
+            ///   - `Ok(Self)` if `vs` contains exactly `LANES` vectors
+            ///   - `Err(LengthError)` otherwise
+            ///
+            #[inline]
+            pub fn gather(vs: &[$v<N>]) -> Result<Self, LengthError> {
+                if vs.len() != $lanes {
+                    return Err(LengthError::Exact { expected: $lanes
+                                                  , found: vs.len() })
+                }
+                Ok($name { $($sub: map_lanes(vs, |v| v.$sub)),+ })
+            }
+
+            /// Copies the lanes of this wide vector into `vs`, in order.
+            ///
+            /// # Returns:
+            ///   - `Ok(())` if `vs` contains exactly `LANES` vectors
+            ///   - `Err(LengthError)` otherwise, in which case `vs` is left
+            ///     unchanged
+            ///
+            #[inline]
+            pub fn scatter(&self, vs: &mut [$v<N>])
+                          -> Result<(), LengthError> {
+                if vs.len() != $lanes {
+                    return Err(LengthError::Exact { expected: $lanes
+                                                  , found: vs.len() })
+                }
+                for (i, v) in vs.iter_mut().enumerate() {
+                    $( v.$sub = self.$sub[i]; )+
+                }
+                Ok(())
+            }
+        }
+
+        impl<N> From<[$v<N>; $lanes]> for $name<N>
+        where N: Copy {
+            #[inline] fn from(vs: [$v<N>; $lanes]) -> Self {
+                $name { $($sub: map_lanes(&vs, |v| v.$sub)),+ }
+            }
+        }
+
+        impl<N> From<$name<N>> for [$v<N>; $lanes]
+        where N: Copy {
+            #[inline] fn from(w: $name<N>) -> Self {
+                ::std::array::from_fn(|i| $v { $($sub: w.$sub[i]),+ })
+            }
+        }
+
+        impl_wide_op! { Add for $name, add, +, AddAssign, add_assign, +=
+                      , $($sub),+ }
+        impl_wide_op! { Sub for $name, sub, -, SubAssign, sub_assign, -=
+                      , $($sub),+ }
+        impl_wide_op! { Div for $name, div, /, DivAssign, div_assign, /=
+                      , $($sub),+ }
+        impl_wide_op! { Rem for $name, rem, %, RemAssign, rem_assign, %=
+                      , $($sub),+ }
+
+        impl<N> Mul<N> for $name<N>
+        where N: Mul<Output = N>
+            , N: Copy {
+
+            type Output = Self;
+            #[inline] fn mul(self, rhs: N) -> Self {
+                $name { $($sub: map(self.$sub, |a| a * rhs)),+ }
+            }
+        }
+
+        impl<'a, N> Mul<N> for &'a $name<N>
+        where N: Mul<Output = N>
+            , N: Copy {
+
+            type Output = $name<N>;
+            #[inline] fn mul(self, rhs: N) -> $name<N> { *self * rhs }
+        }
+
+        impl<N> MulAssign<N> for $name<N>
+        where N: Mul<Output = N>
+            , N: Copy {
+
+            #[inline] fn mul_assign(&mut self, rhs: N) { *self = *self * rhs }
+        }
+
+        /// The dot product of the vectors in each lane
+        impl<N> Mul<$name<N>> for $name<N>
+        where N: Mul<Output = N> + Add<Output = N>
+            , N: Copy {
+
+            type Output = [N; $lanes];
+            #[inline] fn mul(self, rhs: Self) -> [N; $lanes] {
+                ::std::array::from_fn(|i| {
+                    __lin_sum!( $(self.$sub[i] * rhs.$sub[i]),+ )
+                })
+            }
+        }
+
+        impl<'a, N> Mul<&'a $name<N>> for $name<N>
+        where N: Mul<Output = N> + Add<Output = N>
+            , N: Copy {
+
+            type Output = [N; $lanes];
+            #[inline] fn mul(self, rhs: &'a $name<N>) -> [N; $lanes] {
+                self * *rhs
+            }
+        }
+
+        impl<'a, N> Mul<$name<N>> for &'a $name<N>
+        where N: Mul<Output = N> + Add<Output = N>
+            , N: Copy {
+
+            type Output = [N; $lanes];
+            #[inline] fn mul(self, rhs: $name<N>) -> [N; $lanes] {
+                *self * rhs
+            }
+        }
+
+        impl<'a, 'b, N> Mul<&'b $name<N>> for &'a $name<N>
+        where N: Mul<Output = N> + Add<Output = N>
+            , N: Copy {
+
+            type Output = [N; $lanes];
+            #[inline] fn mul(self, rhs: &'b $name<N>) -> [N; $lanes] {
+                *self * *rhs
+            }
+        }
+
+        impl<N> Neg for $name<N>
+        where N: Neg<Output = N>
+            , N: Copy {
+
+            type Output = Self;
+            #[inline] fn neg(self) -> Self {
+                $name { $($sub: map(self.$sub, |a| -a)),+ }
+            }
+        }
+
+        impl<'a, N> Neg for &'a $name<N>
+        where N: Neg<Output = N>
+            , N: Copy {
+
+            type Output = $name<N>;
+            #[inline] fn neg(self) -> $name<N> { -*self }
+        }
+
+        impl_wide_scalar_mul! { $name
+                              , u8, u16, u32, u64, usize
+                              , i8, i16, i32, i64, isize
+                              , f32, f64
+                              }
+    }
+}
+
+/// Returns an array of `f` applied to each of the first `L` elements of
+/// `vs`.
+#[inline(always)]
+fn map_lanes<V, N, F, const L: usize>(vs: &[V], f: F) -> [N; L]
+where F: Fn(&V) -> N {
+    ::std::array::from_fn(|i| f(&vs[i]))
+}
+
+/// Implements a lane-wise operator and its compound assignment, for a wide
+/// vector and either another wide vector or a scalar.
+macro_rules! impl_wide_op {
+    ( $name: ident for $ty:ident, $fun: ident, $op:tt
+    , $assign: ident, $assign_fun: ident, $assign_op: tt
+    , $($sub: ident),+) => {
+        impl<N> $name<$ty<N>> for $ty<N>
+        where N: $name<Output = N>
+            , N: Copy {
+
+            type Output = Self;
+            #[inline] fn $fun(self, rhs: Self) -> Self {
+                let f = |a: N, b: N| __lin_expr!(a $op b);
+                $ty { $($sub: zip(self.$sub, rhs.$sub, f)),+ }
+            }
+        }
+
+        impl<N> $name<N> for $ty<N>
+        where N: $name<Output = N>
+            , N: Copy {
+
+            type Output = Self;
+            #[inline] fn $fun(self, rhs: N) -> Self {
+                $ty { $($sub: map(self.$sub, |a| __lin_expr!(a $op rhs))),+ }
+            }
+        }
+
+        impl<'a, N> $name<&'a $ty<N>> for $ty<N>
+        where N: $name<Output = N>
+            , N: Copy {
+
+            type Output = $ty<N>;
+            #[inline] fn $fun(self, rhs: &'a $ty<N>) -> $ty<N> {
+                __lin_expr!(self $op *rhs)
+            }
+        }
+
+        impl<'a, N> $name<$ty<N>> for &'a $ty<N>
+        where N: $name<Output = N>
+            , N: Copy {
+
+            type Output = $ty<N>;
+            #[inline] fn $fun(self, rhs: $ty<N>) -> $ty<N> {
+                __lin_expr!(*self $op rhs)
+            }
+        }
+
+        impl<'a, 'b, N> $name<&'b $ty<N>> for &'a $ty<N>
+        where N: $name<Output = N>
+            , N: Copy {
+
+            type Output = $ty<N>;
+            #[inline] fn $fun(self, rhs: &'b $ty<N>) -> $ty<N> {
+                __lin_expr!(*self $op *rhs)
+            }
+        }
+
+        impl<'a, N> $name<N> for &'a $ty<N>
+        where N: $name<Output = N>
+            , N: Copy {
+
+            type Output = $ty<N>;
+            #[inline] fn $fun(self, rhs: N) -> $ty<N> {
+                __lin_expr!(*self $op rhs)
+            }
+        }
+
+        impl<N> $assign<$ty<N>> for $ty<N>
+        where N: $name<Output = N>
+            , N: Copy {
+
+            #[inline] fn $assign_fun(&mut self, rhs: Self) {
+                *self = __lin_expr!(*self $op rhs)
+            }
+        }
+
+        impl<'a, N> $assign<&'a $ty<N>> for $ty<N>
+        where N: $name<Output = N>
+            , N: Copy {
+
+            #[inline] fn $assign_fun(&mut self, rhs: &'a $ty<N>) {
+                *self = __lin_expr!(*self $op *rhs)
+            }
+        }
+
+        impl<N> $assign<N> for $ty<N>
+        where N: $name<Output = N>
+            , N: Copy {
+
+            #[inline] fn $assign_fun(&mut self, rhs: N) {
+                *self = __lin_expr!(*self $op rhs)
+            }
+        }
+    }
+}
+
+/// Implements `scalar * wide vector` for each of the given primitive types.
+macro_rules! impl_wide_scalar_mul {
+    (@impl $ty: ident, $n: ty) => {
+        impl Mul<$ty<$n>> for $n {
+            type Output = $ty<$n>;
+            #[inline] fn mul(self, rhs: $ty<$n>) -> $ty<$n> { rhs * self }
+        }
+
+        impl<'a> Mul<&'a $ty<$n>> for $n {
+            type Output = $ty<$n>;
+            #[inline] fn mul(self, rhs: &'a $ty<$n>) -> $ty<$n> {
+                *rhs * self
+            }
+        }
+    };
+    ($ty: ident, $($n: ty),+) => {
+        $( impl_wide_scalar_mul! { @impl $ty, $n } )+
+    }
+}
+
+/// Implements the product of a matrix and a wide vector, which multiplies
+/// the vector in each lane by the matrix.
+macro_rules! impl_wide_transform {
+    ($m: ident, $ty: ident) => {
+        impl<N> Mul<$ty<N>> for $m<N>
+        where N: Mul<Output = N> + Add<Output = N>
+            , N: Copy {
+
+            type Output = $ty<N>;
+            #[inline] fn mul(self, v: $ty<N>) -> $ty<N> {
+                let row = |a: N, b: N, c: N| {
+                    let mut out = v.x;
+                    for i in 0..out.len() {
+                        out[i] = a * v.x[i] + b * v.y[i] + c * v.z[i];
+                    }
+                    out
+                };
+                $ty { x: row(self.x1y1, self.x2y1, self.x3y1)
+                    , y: row(self.x1y2, self.x2y2, self.x3y2)
+                    , z: row(self.x1y3, self.x2y3, self.x3y3)
+                    }
+            }
+        }
+    }
+}
+
+make_wide! {
+    /// Four `Vector3`s, with each component in a 16-byte aligned array.
+    Vector3x4, Vector3, 4, 16, x, y, z
+}
+
+make_wide! {
+    /// Eight `Vector3`s, with each component in a 32-byte aligned array.
+    Vector3x8, Vector3, 8, 32, x, y, z
+}
+
+impl_wide_transform! { Matrix3, Vector3x4 }
+impl_wide_transform! { Matrix3, Vector3x8 }
@@ -0,0 +1,81 @@
+//! Checks that `make_vector!` and `make_matrix!` can be used from outside
+//! this crate, without importing anything which the expansion refers to.
+
+#[macro_use] extern crate lin;
+#[cfg(feature = "serde")] extern crate serde_test;
+
+mod colors {
+    make_vector! { Rgb, 3, r, g, b }
+    make_vector! { Rg, 2, r, g }
+
+    make_matrix! { Mixer, rows: 3, cols: 3, row: Rgb, col: Rgb
+                 , square
+                 , rr, gr, br
+                 , rg, gg, bg
+                 , rb, gb, bb
+                 }
+
+    make_matrix! { Splitter, rows: 3, cols: 2, row: Rg, col: Rgb
+                 , rr, gr
+                 , rg, gg
+                 , rb, gb
+                 }
+}
+
+use colors::{Rgb, Rg, Mixer, Splitter};
+use lin::{Columnar, Tabular};
+
+#[test]
+fn test_user_vector() {
+    let a = Rgb { r: 1, g: 2, b: 3 };
+    let b = Rgb { r: 4, g: 5, b: 6 };
+    assert_eq!(a + b, Rgb { r: 5, g: 7, b: 9 });
+    assert_eq!(-a * 2, Rgb { r: -2, g: -4, b: -6 });
+    assert_eq!(a * b, 32);
+    assert_eq!(a[2], 3);
+    assert_eq!(a.iter().sum::<i32>(), 6);
+    assert_eq!(a.to_string(), "(1, 2, 3)");
+    assert_eq!("(1, 2, 3)".parse(), Ok(a));
+    assert_eq!(Rgb::from([1, 2, 3]), a);
+    assert_eq!(a.checked_mul(i32::MAX), None);
+    assert_eq!(Rg { r: 1u8, g: 2 }.cast::<f32>(), Rg { r: 1.0, g: 2.0 });
+}
+
+#[test]
+fn test_user_matrix() {
+    let m: Mixer<i32> = (0..9).collect();
+    let v = Rgb { r: 1, g: 0, b: 0 };
+    assert_eq!(m * v, Rgb { r: 0, g: 3, b: 6 });
+    assert_eq!(m.transpose()[(0, 1)], 1);
+    assert_eq!(m.row(2), Some(&Rgb { r: 6, g: 7, b: 8 }));
+    assert_eq!(m.column_owned(1), Some(Rgb { r: 1, g: 4, b: 7 }));
+    assert_eq!(m.to_string().parse(), Ok(m));
+}
+
+#[test]
+fn test_user_non_square_matrix() {
+    let m: Splitter<i32> = (1..7).collect();
+    assert_eq!(m * Rg { r: 1, g: 10 }, Rgb { r: 21, g: 43, b: 65 });
+    assert_eq!(m * 2, (2..13).step_by(2).collect());
+    assert_eq!(m.row(2), Some(&Rg { r: 5, g: 6 }));
+    assert_eq!(m.column_owned(0), Some(Rgb { r: 1, g: 3, b: 5 }));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_user_vector_serde() {
+    use serde_test::{Token, assert_tokens};
+    assert_tokens( &Rgb { r: 1u8, g: 2, b: 3 }
+                 , &[ Token::Tuple { len: 3 }
+                    , Token::U8(1), Token::U8(2), Token::U8(3)
+                    , Token::TupleEnd ]);
+}
+
+#[cfg(feature = "quickcheck")]
+mod laws {
+    use colors::{Rgb, Mixer, Splitter};
+
+    additive_laws! { rgb_i32_laws, Rgb, i32 }
+    additive_laws! { mixer_f64_laws, Mixer, f64 }
+    additive_laws! { splitter_f32_laws, Splitter, f32 }
+}
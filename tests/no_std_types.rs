@@ -0,0 +1,39 @@
+//! Checks that `make_vector!` and `make_matrix!` can be used from a
+//! `no_std` crate, whose expansions cannot name `std`.
+//!
+//! The test harness needs `std`, so it is linked under another name, which
+//! keeps `::std` paths from resolving in the rest of this crate.
+
+#![no_std]
+
+#[macro_use] extern crate lin;
+extern crate std as host;
+
+mod colors {
+    make_vector! { Rgb, 3, r, g, b }
+    make_vector! { Rg, 2, r, g }
+
+    make_matrix! { Mixer, rows: 3, cols: 3, row: Rgb, col: Rgb
+                 , square
+                 , rr, gr, br
+                 , rg, gg, bg
+                 , rb, gb, bb
+                 }
+
+    make_matrix! { Splitter, rows: 3, cols: 2, row: Rg, col: Rgb
+                 , rr, gr
+                 , rg, gg
+                 , rb, gb
+                 }
+}
+
+use colors::{Rgb, Rg, Mixer, Splitter};
+
+#[test]
+fn test_no_std_user_types() {
+    let m: Mixer<i32> = Mixer::from([[0, 1, 2], [3, 4, 5], [6, 7, 8]]);
+    let v = Rgb { r: 1, g: 0, b: 0 };
+    assert_eq!(m * v + v, Rgb { r: 1, g: 3, b: 6 });
+    let s: Splitter<i32> = Splitter::from([[1, 2], [3, 4], [5, 6]]);
+    assert_eq!(s * Rg { r: 1, g: 10 }, Rgb { r: 21, g: 43, b: 65 });
+}